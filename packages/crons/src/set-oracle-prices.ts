@@ -63,6 +63,7 @@ export async function findCoingeckoPrice(token: string): Promise<number> {
       .accountsPartial({
         priceOracle,
         oracle: provider.wallet.publicKey,
+        priceHistory: priceOracleAcc.priceHistory,
       })
       .instruction();
     await sendInstructionsWithPriorityFee(provider, [instruction]);
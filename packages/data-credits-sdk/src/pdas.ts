@@ -50,3 +50,18 @@ export function escrowAccountKey(
     programId
   );
 }
+
+export function delegatedDataCreditsDepositKey(
+  delegatedDataCredits: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+) {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("delegated_data_credits_deposit", "utf-8"),
+      delegatedDataCredits.toBuffer(),
+      owner.toBuffer(),
+    ],
+    programId
+  );
+}
@@ -2,8 +2,10 @@ import * as anchor from "@coral-xyz/anchor";
 import {
   init as initLazy,
   isExecuted,
+  isRevoked,
   lazyTransactionsKey,
 } from "@helium/lazy-transactions-sdk";
+import { PublicKey } from "@solana/web3.js";
 import os from "os";
 import yargs from "yargs/yargs";
 
@@ -43,4 +45,11 @@ export async function run(args: any = process.argv) {
   const hasRun = isExecuted(executed, argv.index);
 
   console.log("Executed: ", hasRun);
+
+  if (!lt.revokedTransactions.equals(PublicKey.default)) {
+    const revoked = (
+      await provider.connection.getAccountInfo(lt.revokedTransactions)
+    )?.data.subarray(1)!;
+    console.log("Revoked: ", isRevoked(revoked, argv.index));
+  }
 }
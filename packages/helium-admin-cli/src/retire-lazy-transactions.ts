@@ -0,0 +1,147 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  getBitmapLen,
+  init as initLazy,
+  lazyTransactionsKey,
+} from "@helium/lazy-transactions-sdk";
+import { chunks, sendInstructions } from "@helium/spl-utils";
+import {
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import os from "os";
+import yargs from "yargs/yargs";
+import { sendInstructionsOrSquadsV4 } from "./utils";
+
+export async function run(args: any = process.argv) {
+  const yarg = yargs(args).options({
+    wallet: {
+      alias: "k",
+      describe: "Anchor wallet keypair",
+      default: `${os.homedir()}/.config/solana/id.json`,
+    },
+    url: {
+      alias: "u",
+      default: "http://127.0.0.1:8899",
+      describe: "The solana url",
+    },
+    multisig: {
+      type: "string",
+      describe:
+        "Address of the squads multisig that is the authority. If not provided, your wallet must be the authority",
+    },
+    name: {
+      required: true,
+      type: "string",
+      describe: "The lazy transactions instance name",
+    },
+    validFrom: {
+      type: "number",
+      describe: "Unix timestamp from which transactions can be executed",
+    },
+    validUntil: {
+      type: "number",
+      describe: "Unix timestamp from which transactions can no longer be executed",
+    },
+    revoke: {
+      type: "array",
+      number: true,
+      default: [],
+      describe: "Indices of the transactions to revoke",
+    },
+  });
+  const argv = await yarg.argv;
+  process.env.ANCHOR_WALLET = argv.wallet;
+  process.env.ANCHOR_PROVIDER_URL = argv.url;
+  anchor.setProvider(anchor.AnchorProvider.local(argv.url));
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const lazyProgram = await initLazy(provider);
+  const [lazyTransactions] = lazyTransactionsKey(argv.name);
+  const lt = await lazyProgram.account.lazyTransactionsV0.fetch(
+    lazyTransactions
+  );
+
+  const instructions: TransactionInstruction[] = [];
+  if (
+    typeof argv.validFrom !== "undefined" ||
+    typeof argv.validUntil !== "undefined"
+  ) {
+    console.log(
+      `Setting validity window to [${argv.validFrom}, ${argv.validUntil})`
+    );
+    instructions.push(
+      await lazyProgram.methods
+        .setValidityWindowV0({
+          validFrom:
+            typeof argv.validFrom !== "undefined"
+              ? new anchor.BN(argv.validFrom)
+              : null,
+          validUntil:
+            typeof argv.validUntil !== "undefined"
+              ? new anchor.BN(argv.validUntil)
+              : null,
+        })
+        .accountsPartial({
+          lazyTransactions,
+          authority: lt.authority,
+        })
+        .instruction()
+    );
+  }
+
+  const revoke = argv.revoke as number[];
+  if (revoke.length > 0) {
+    let revokedTransactions = lt.revokedTransactions;
+    if (revokedTransactions.equals(PublicKey.default)) {
+      const revokedTransactionsKeypair = Keypair.generate();
+      const revokedTransactionsSize = 1 + getBitmapLen(lt.maxDepth);
+      await sendInstructions(
+        provider,
+        [
+          SystemProgram.createAccount({
+            fromPubkey: provider.wallet.publicKey,
+            newAccountPubkey: revokedTransactionsKeypair.publicKey,
+            space: revokedTransactionsSize,
+            lamports:
+              await provider.connection.getMinimumBalanceForRentExemption(
+                revokedTransactionsSize
+              ),
+            programId: lazyProgram.programId,
+          }),
+        ],
+        [revokedTransactionsKeypair]
+      );
+      revokedTransactions = revokedTransactionsKeypair.publicKey;
+      console.log(
+        `Created revoked transactions bitmap ${revokedTransactions.toBase58()}`
+      );
+    }
+
+    console.log(
+      `Revoking ${revoke.length} transactions. Executors must use executeTransactionV1 from now on`
+    );
+    for (const indices of chunks(revoke, 100)) {
+      instructions.push(
+        await lazyProgram.methods
+          .revokeTransactionsV0({ indices })
+          .accountsPartial({
+            lazyTransactions,
+            authority: lt.authority,
+            revokedTransactions,
+          })
+          .instruction()
+      );
+    }
+  }
+
+  for (const chunk of chunks(instructions, 4)) {
+    await sendInstructionsOrSquadsV4({
+      provider,
+      instructions: chunk,
+      multisig: argv.multisig ? new PublicKey(argv.multisig) : undefined,
+      signers: [],
+    });
+  }
+}
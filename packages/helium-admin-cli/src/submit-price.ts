@@ -128,6 +128,7 @@ export async function run(args: any = process.argv) {
     .accountsPartial({
       priceOracle,
       oracle: provider.wallet.publicKey,
+      priceHistory: priceOracleAcc.priceHistory,
     })
     .rpc({ skipPreflight: true });
 
@@ -74,7 +74,9 @@ export const daoEpochInfoResolver = resolveIndividual(
 export const subDaoEpochInfoResolver = resolveIndividual(
   async ({ provider, path, accounts, args }) => {
     const isOld = path[path.length - 1] === "oldSubDaoEpochInfo";
-    const isNew = path[path.length - 1] === "subDaoEpochInfo";
+    // Claims take the claimed epoch as an arg, but compounding updates the current epoch
+    const isCompound = path[path.length - 1] === "compoundSubDaoEpochInfo";
+    const isNew = path[path.length - 1] === "subDaoEpochInfo" || isCompound;
     if ((isOld || isNew) && accounts.registrar) {
       const vsr = await init(provider as AnchorProvider, VSR_PROGRAM_ID);
       let registrar;
@@ -90,7 +92,7 @@ export const subDaoEpochInfoResolver = resolveIndividual(
         SYSVAR_CLOCK_PUBKEY
       );
       let unixTime;
-      if (args && args[0] && args[0].epoch) {
+      if (args && args[0] && args[0].epoch && !isCompound) {
         unixTime = args[0].epoch.toNumber() * EPOCH_LENGTH;
       } else {
         unixTime =
@@ -180,18 +182,27 @@ export const closingTimeEpochInfoResolver = resolveIndividual(
         ...path.slice(0, path.length - 1),
         isNew ? "subDao" : "oldSubDao",
       ]) as PublicKey;
-      const position = get(accounts, [
+      // Merges close on the target position's epoch
+      const position = (get(accounts, [
         ...path.slice(0, path.length - 1),
         "position",
-      ]) as PublicKey;
+      ]) ||
+        get(accounts, [
+          ...path.slice(0, path.length - 1),
+          "targetPosition",
+        ])) as PublicKey;
       const proxyConfig = get(accounts, [
         ...path.slice(0, path.length - 1),
         "proxyConfig",
       ]) as PublicKey;
-      const delegatedPosition = get(accounts, [
+      const delegatedPosition = (get(accounts, [
         ...path.slice(0, path.length - 1),
         "delegatedPosition",
-      ]) as PublicKey;
+      ]) ||
+        get(accounts, [
+          ...path.slice(0, path.length - 1),
+          "targetDelegatedPosition",
+        ])) as PublicKey;
       const positionAcc =
         position && (await program.account.positionV0.fetch(position));
       const delegatedPositionAcc =
@@ -334,12 +345,84 @@ export const heliumSubDaosResolvers = combineResolvers(
     mint: "hntMint",
     owner: "positionAuthority",
   }),
+  ataResolver({
+    instruction: "claimRewardsV1",
+    account: "compoundEscrow",
+    mint: "hntMint",
+    owner: "dao",
+  }),
+  ataResolver({
+    instruction: "claimRewardsV1",
+    account: "positionVault",
+    mint: "hntMint",
+    owner: "position",
+  }),
+  ataResolver({
+    instruction: "batchClaimRewardsV0",
+    account: "compoundEscrow",
+    mint: "hntMint",
+    owner: "dao",
+  }),
+  ataResolver({
+    instruction: "batchClaimRewardsV0",
+    account: "positionVault",
+    mint: "hntMint",
+    owner: "position",
+  }),
   ataResolver({
     instruction: "tempClaimFailedClaims",
     account: "delegatorAta",
     mint: "dntMint",
     owner: "positionAuthority",
   }),
+  ataResolver({
+    instruction: "splitDelegatedPositionV0",
+    account: "vault",
+    mint: "depositMint",
+    owner: "position",
+  }),
+  ataResolver({
+    instruction: "splitDelegatedPositionV0",
+    account: "newVault",
+    mint: "depositMint",
+    owner: "newPosition",
+  }),
+  ataResolver({
+    instruction: "splitDelegatedPositionV0",
+    account: "newPositionTokenAccount",
+    mint: "newMint",
+    owner: "positionAuthority",
+  }),
+  ataResolver({
+    instruction: "mergeDelegatedPositionsV0",
+    account: "sourceVault",
+    mint: "depositMint",
+    owner: "sourcePosition",
+  }),
+  ataResolver({
+    instruction: "mergeDelegatedPositionsV0",
+    account: "targetVault",
+    mint: "depositMint",
+    owner: "targetPosition",
+  }),
+  ataResolver({
+    instruction: "mergePositionsV0",
+    account: "common.sourceVault",
+    mint: "common.depositMint",
+    owner: "common.sourcePosition",
+  }),
+  ataResolver({
+    instruction: "mergePositionsV0",
+    account: "common.targetVault",
+    mint: "common.depositMint",
+    owner: "common.targetPosition",
+  }),
+  ataResolver({
+    instruction: "mergePositionsV0",
+    account: "common.positionTokenAccount",
+    mint: "common.mint",
+    owner: "common.positionAuthority",
+  }),
   ataResolver({
     account: "positionTokenAccount",
     mint: "mint",
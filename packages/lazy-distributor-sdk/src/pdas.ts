@@ -25,3 +25,13 @@ export function recipientKey(
     programId
   );
 }
+
+export function vestingEscrowKey(
+  recipient: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("vesting_escrow", "utf-8"), recipient.toBuffer()],
+    programId
+  );
+}
@@ -73,6 +73,60 @@ export const lazyDistributorResolvers = combineResolvers(
     mint: "common.rewardsMint",
     owner: "common.owner",
   }),
+  ataResolver({
+    instruction: "distributeVestingDestinationV0",
+    account: "common.destinationAccount",
+    mint: "common.rewardsMint",
+    owner: "common.owner",
+  }),
+  ataResolver({
+    instruction: "claimVestingV0",
+    account: "common.escrowAccount",
+    mint: "common.rewardsMint",
+    owner: "common.vestingEscrow",
+  }),
+  ataResolver({
+    instruction: "claimVestingV0",
+    account: "common.destinationAccount",
+    mint: "common.rewardsMint",
+    owner: "common.owner",
+  }),
+  ataResolver({
+    instruction: "claimCompressionVestingV0",
+    account: "common.escrowAccount",
+    mint: "common.rewardsMint",
+    owner: "common.vestingEscrow",
+  }),
+  ataResolver({
+    instruction: "claimCompressionVestingV0",
+    account: "common.destinationAccount",
+    mint: "common.rewardsMint",
+    owner: "common.owner",
+  }),
+  ataResolver({
+    instruction: "closeVestingEscrowV0",
+    account: "common.escrowAccount",
+    mint: "common.rewardsMint",
+    owner: "common.vestingEscrow",
+  }),
+  ataResolver({
+    instruction: "closeVestingEscrowV0",
+    account: "common.destinationAccount",
+    mint: "common.rewardsMint",
+    owner: "common.owner",
+  }),
+  ataResolver({
+    instruction: "closeCompressionVestingEscrowV0",
+    account: "common.escrowAccount",
+    mint: "common.rewardsMint",
+    owner: "common.vestingEscrow",
+  }),
+  ataResolver({
+    instruction: "closeCompressionVestingEscrowV0",
+    account: "common.destinationAccount",
+    mint: "common.rewardsMint",
+    owner: "common.owner",
+  }),
   circuitBreakerResolvers,
   resolveIndividual(async ({ path, accounts, idlIx }) => {
     if (path[path.length - 1] === "targetMetadata") {
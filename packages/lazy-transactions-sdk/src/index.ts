@@ -346,6 +346,10 @@ export function isExecuted(executed: Buffer, index: number): boolean {
   return (byte & mask) !== 0;
 }
 
+export function isRevoked(revoked: Buffer, index: number): boolean {
+  return isExecuted(revoked, index);
+}
+
 export function getBitmapLen(maxDepth: number): number {
   const numTxns = 1 << maxDepth;
   // Calculate the ceiling division
@@ -7,6 +7,7 @@ import {
   blockKey,
   init,
   isExecuted,
+  isRevoked,
   lazySignerKey,
   lazyTransactionsKey,
 } from "@helium/lazy-transactions-sdk";
@@ -135,6 +136,16 @@ async function getTransactions(
   const lazyTxns = await program.account.lazyTransactionsV0.fetch(
     lazyTransactions
   );
+  // Revoked leaves and leaves outside of the validity window can never execute
+  const revoked = lt.revokedTransactions.equals(PublicKey.default)
+    ? null
+    : (
+        await provider.connection.getAccountInfo(lt.revokedTransactions)
+      )?.data.subarray(1)!;
+  const now = Date.now() / 1000;
+  const isActive =
+    (!lt.validFrom || now >= lt.validFrom.toNumber()) &&
+    (!lt.validUntil || now < lt.validUntil.toNumber());
 
   const lookupTableAccs = await getAddressLookupTableAccounts(
     provider.connection,
@@ -146,7 +157,7 @@ async function getTransactions(
   // the ~1.8x precomputed fallback.
   const SIMULATE_CONCURRENCY = 10;
   // One fee estimate per request, seeded from the first tx's accounts — the
-  // executeTransactionV0 txs differ only by block PDA, so the writable-account
+  // executeTransactionV1 txs differ only by block PDA, so the writable-account
   // fee median is effectively identical across them; per-tx estimates would
   // just be N extra RPC calls.
   let feeEstimate: Promise<number> | null = null;
@@ -163,26 +174,30 @@ async function getTransactions(
     signers: Buffer;
     compute: number;
   }) => {
-    const hasRun = isExecuted(executed, id);
+    const hasRun =
+      isExecuted(executed, id) || (!!revoked && isRevoked(revoked, id));
     const compiledTx = decompress(compiled);
     const block = blockKey(lazyTransactions, id)[0];
     const signers = decompressSigners(signersRaw);
 
-    if (!hasRun && compiledTx.instructions.length > 0) {
+    if (isActive && !hasRun && compiledTx.instructions.length > 0) {
       const ix = await program.methods
-        .executeTransactionV0({
+        .executeTransactionV1({
           instructions: compiledTx.instructions,
           index: compiledTx.index,
           signerSeeds: signers,
         })
         .accountsStrict({
-          payer: provider.wallet.publicKey,
-          lazyTransactions,
-          canopy: lazyTxns.canopy,
-          lazySigner,
-          block,
-          systemProgram: SystemProgram.programId,
-          executedTransactions: lt.executedTransactions,
+          common: {
+            payer: provider.wallet.publicKey,
+            lazyTransactions,
+            canopy: lazyTxns.canopy,
+            lazySigner,
+            block,
+            systemProgram: SystemProgram.programId,
+            executedTransactions: lt.executedTransactions,
+          },
+          revokedTransactions: lt.revokedTransactions,
         })
         .remainingAccounts([
           ...compiledTx.accounts,
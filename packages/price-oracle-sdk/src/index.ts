@@ -5,6 +5,7 @@ import { PROGRAM_ID } from "./constants";
 import { fetchBackwardsCompatibleIdl } from "@helium/spl-utils";
 
 export * from "./constants";
+export * from "./pdas";
 
 export async function init(
   provider: AnchorProvider,
@@ -0,0 +1,12 @@
+import { PublicKey } from "@solana/web3.js";
+import { PROGRAM_ID } from "./constants";
+
+export function priceHistoryKey(
+  priceOracle: PublicKey,
+  programId: PublicKey = PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("price_history", "utf-8"), priceOracle.toBuffer()],
+    programId
+  );
+}
@@ -71,6 +71,48 @@ export const vsrResolvers = combineResolvers(
     mint: "depositMint",
     owner: "targetPosition",
   }),
+  ataResolver({
+    instruction: "splitPositionV0",
+    account: "sourceVault",
+    mint: "depositMint",
+    owner: "sourcePosition",
+  }),
+  ataResolver({
+    instruction: "splitPositionV0",
+    account: "vault",
+    mint: "depositMint",
+    owner: "position",
+  }),
+  ataResolver({
+    instruction: "splitPositionV0",
+    account: "sourcePositionTokenAccount",
+    mint: "sourceMint",
+    owner: "positionAuthority",
+  }),
+  ataResolver({
+    instruction: "splitPositionV0",
+    account: "positionTokenAccount",
+    mint: "mint",
+    owner: "recipient",
+  }),
+  ataResolver({
+    instruction: "mergePositionsV0",
+    account: "common.sourceVault",
+    mint: "common.depositMint",
+    owner: "common.sourcePosition",
+  }),
+  ataResolver({
+    instruction: "mergePositionsV0",
+    account: "common.targetVault",
+    mint: "common.depositMint",
+    owner: "common.targetPosition",
+  }),
+  ataResolver({
+    instruction: "mergePositionsV0",
+    account: "common.positionTokenAccount",
+    mint: "common.mint",
+    owner: "common.positionAuthority",
+  }),
   ataResolver({
     account: "positionTokenAccount",
     mint: "mint",
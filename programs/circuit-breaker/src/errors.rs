@@ -10,4 +10,10 @@ pub enum ErrorCode {
 
   #[msg("Invalid config")]
   InvalidConfig,
+
+  #[msg("The circuit breaker is paused")]
+  CircuitBreakerPaused,
+
+  #[msg("An active pause can only be extended")]
+  PauseCannotBeShortened,
 }
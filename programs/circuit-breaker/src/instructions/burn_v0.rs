@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
 
-use crate::{window::enforce_window, AccountWindowedCircuitBreakerV0};
+use crate::{
+  errors::ErrorCode,
+  window::{enforce_additional_windows, enforce_window},
+  AccountWindowedCircuitBreakerV0,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct BurnArgsV0 {
@@ -27,13 +31,24 @@ pub struct BurnV0<'info> {
 
 pub fn handler(ctx: Context<BurnV0>, args: BurnArgsV0) -> Result<()> {
   let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+  let unix_timestamp = Clock::get()?.unix_timestamp;
+  require!(
+    !circuit_breaker.is_paused(unix_timestamp),
+    ErrorCode::CircuitBreakerPaused
+  );
 
   circuit_breaker.last_window = enforce_window(
     &circuit_breaker.config,
     &circuit_breaker.last_window,
     args.amount,
     ctx.accounts.from.amount,
-    Clock::get()?.unix_timestamp,
+    unix_timestamp,
+  )?;
+  circuit_breaker.additional_windows = enforce_additional_windows(
+    &circuit_breaker.additional_windows,
+    args.amount,
+    ctx.accounts.from.amount,
+    unix_timestamp,
   )?;
 
   burn(
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+  window::{get_capacity, WindowedBreakerCapacityV0},
+  AccountWindowedCircuitBreakerV0, GetWindowedBreakerCapacityArgsV0,
+};
+
+#[derive(Accounts)]
+pub struct GetAccountWindowedBreakerCapacityV0<'info> {
+  pub token_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+    has_one = token_account,
+  )]
+  pub circuit_breaker: Box<Account<'info, AccountWindowedCircuitBreakerV0>>,
+}
+
+pub fn handler(
+  ctx: Context<GetAccountWindowedBreakerCapacityV0>,
+  args: GetWindowedBreakerCapacityArgsV0,
+) -> Result<WindowedBreakerCapacityV0> {
+  let circuit_breaker = &ctx.accounts.circuit_breaker;
+  let unix_timestamp = Clock::get()?.unix_timestamp;
+
+  let mut capacity = get_capacity(
+    &circuit_breaker.config,
+    &circuit_breaker.last_window,
+    &circuit_breaker.additional_windows,
+    args.amount,
+    ctx.accounts.token_account.amount,
+    unix_timestamp,
+  )?;
+  if circuit_breaker.is_paused(unix_timestamp) {
+    capacity.apply_pause(circuit_breaker.pause_expiry_ts);
+  }
+
+  Ok(capacity)
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+  window::{get_capacity, WindowedBreakerCapacityV0},
+  MintWindowedCircuitBreakerV0,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct GetWindowedBreakerCapacityArgsV0 {
+  // If set, also returns the earliest time this amount would fit
+  pub amount: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct GetMintWindowedBreakerCapacityV0<'info> {
+  pub mint: Box<Account<'info, Mint>>,
+  #[account(
+    has_one = mint,
+  )]
+  pub circuit_breaker: Box<Account<'info, MintWindowedCircuitBreakerV0>>,
+}
+
+pub fn handler(
+  ctx: Context<GetMintWindowedBreakerCapacityV0>,
+  args: GetWindowedBreakerCapacityArgsV0,
+) -> Result<WindowedBreakerCapacityV0> {
+  let circuit_breaker = &ctx.accounts.circuit_breaker;
+  let unix_timestamp = Clock::get()?.unix_timestamp;
+
+  let mut capacity = get_capacity(
+    &circuit_breaker.config,
+    &circuit_breaker.last_window,
+    &circuit_breaker.additional_windows,
+    args.amount,
+    ctx.accounts.mint.supply,
+    unix_timestamp,
+  )?;
+  if circuit_breaker.is_paused(unix_timestamp) {
+    capacity.apply_pause(circuit_breaker.pause_expiry_ts);
+  }
+
+  Ok(capacity)
+}
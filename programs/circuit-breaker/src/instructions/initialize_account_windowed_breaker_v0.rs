@@ -56,6 +56,10 @@ pub fn handler(
         last_unix_timestamp: 0,
       },
       bump_seed: ctx.bumps.circuit_breaker,
+      additional_windows: vec![],
+      guardian: Pubkey::default(),
+      paused: false,
+      pause_expiry_ts: None,
     });
 
   set_authority(
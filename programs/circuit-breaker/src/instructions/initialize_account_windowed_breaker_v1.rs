@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+  set_authority, spl_token::instruction::AuthorityType, SetAuthority, Token, TokenAccount,
+};
+
+use crate::{
+  errors::ErrorCode, window::apply_config_v1, AccountWindowedCircuitBreakerV0, AdditionalWindowV0,
+  WindowV0, WindowedCircuitBreakerConfigV1,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct InitializeAccountWindowedBreakerArgsV1 {
+  pub authority: Pubkey,
+  pub owner: Pubkey,
+  pub config: WindowedCircuitBreakerConfigV1,
+  pub guardian: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: InitializeAccountWindowedBreakerArgsV1)]
+pub struct InitializeAccountWindowedBreakerV1<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  #[account(
+    init,
+    payer = payer,
+    space = 8 + 60 + std::mem::size_of::<AccountWindowedCircuitBreakerV0>()
+      + args.config.windows.len() * std::mem::size_of::<AdditionalWindowV0>(),
+    seeds = ["account_windowed_breaker".as_bytes(), token_account.key().as_ref()],
+    bump
+  )]
+  pub circuit_breaker: Box<Account<'info, AccountWindowedCircuitBreakerV0>>,
+  #[account(
+    mut,
+    has_one = owner
+  )]
+  pub token_account: Box<Account<'info, TokenAccount>>,
+  pub owner: Signer<'info>,
+
+  pub token_program: Program<'info, Token>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<InitializeAccountWindowedBreakerV1>,
+  args: InitializeAccountWindowedBreakerArgsV1,
+) -> Result<()> {
+  require!(args.config.is_valid(), ErrorCode::InvalidConfig);
+  require!(
+    args.guardian != Some(args.authority),
+    ErrorCode::InvalidConfig
+  );
+
+  let last_window = WindowV0 {
+    last_aggregated_value: 0,
+    last_unix_timestamp: 0,
+  };
+  let (config, additional_windows) = apply_config_v1(args.config, &last_window, &[]);
+  ctx
+    .accounts
+    .circuit_breaker
+    .set_inner(AccountWindowedCircuitBreakerV0 {
+      token_account: ctx.accounts.token_account.key(),
+      authority: args.authority,
+      owner: args.owner,
+      config,
+      last_window,
+      bump_seed: ctx.bumps.circuit_breaker,
+      additional_windows,
+      guardian: args.guardian.unwrap_or_default(),
+      paused: false,
+      pause_expiry_ts: None,
+    });
+
+  set_authority(
+    CpiContext::new(
+      ctx.accounts.token_program.to_account_info(),
+      SetAuthority {
+        account_or_mint: ctx.accounts.token_account.to_account_info(),
+        current_authority: ctx.accounts.owner.to_account_info(),
+      },
+    ),
+    AuthorityType::CloseAccount,
+    Some(ctx.accounts.circuit_breaker.key()),
+  )?;
+
+  set_authority(
+    CpiContext::new(
+      ctx.accounts.token_program.to_account_info(),
+      SetAuthority {
+        account_or_mint: ctx.accounts.token_account.to_account_info(),
+        current_authority: ctx.accounts.owner.to_account_info(),
+      },
+    ),
+    AuthorityType::AccountOwner,
+    Some(ctx.accounts.circuit_breaker.key()),
+  )?;
+
+  Ok(())
+}
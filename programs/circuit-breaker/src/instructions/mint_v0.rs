@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
 
-use crate::{window::enforce_window, MintWindowedCircuitBreakerV0};
+use crate::{
+  errors::ErrorCode,
+  window::{enforce_additional_windows, enforce_window},
+  MintWindowedCircuitBreakerV0,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct MintArgsV0 {
@@ -28,13 +32,24 @@ pub struct MintV0<'info> {
 
 pub fn handler(ctx: Context<MintV0>, args: MintArgsV0) -> Result<()> {
   let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+  let unix_timestamp = Clock::get()?.unix_timestamp;
+  require!(
+    !circuit_breaker.is_paused(unix_timestamp),
+    ErrorCode::CircuitBreakerPaused
+  );
 
   circuit_breaker.last_window = enforce_window(
     &circuit_breaker.config,
     &circuit_breaker.last_window,
     args.amount,
     ctx.accounts.mint.supply,
-    Clock::get()?.unix_timestamp,
+    unix_timestamp,
+  )?;
+  circuit_breaker.additional_windows = enforce_additional_windows(
+    &circuit_breaker.additional_windows,
+    args.amount,
+    ctx.accounts.mint.supply,
+    unix_timestamp,
   )?;
 
   mint_to(
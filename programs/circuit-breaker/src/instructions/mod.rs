@@ -1,17 +1,37 @@
 pub mod burn_v0;
+pub mod get_account_windowed_breaker_capacity_v0;
+pub mod get_mint_windowed_breaker_capacity_v0;
 pub mod initialize_account_windowed_breaker_v0;
+pub mod initialize_account_windowed_breaker_v1;
 pub mod initialize_mint_windowed_breaker_v0;
+pub mod initialize_mint_windowed_breaker_v1;
 pub mod mint_v0;
+pub mod pause_account_windowed_breaker_v0;
+pub mod pause_mint_windowed_breaker_v0;
 pub mod remove_mint_authority_v0;
+pub mod resume_account_windowed_breaker_v0;
+pub mod resume_mint_windowed_breaker_v0;
 pub mod transfer_v0;
 pub mod update_account_windowed_breaker_v0;
+pub mod update_account_windowed_breaker_v1;
 pub mod update_mint_windowed_breaker_v0;
+pub mod update_mint_windowed_breaker_v1;
 
 pub use burn_v0::*;
+pub use get_account_windowed_breaker_capacity_v0::*;
+pub use get_mint_windowed_breaker_capacity_v0::*;
 pub use initialize_account_windowed_breaker_v0::*;
+pub use initialize_account_windowed_breaker_v1::*;
 pub use initialize_mint_windowed_breaker_v0::*;
+pub use initialize_mint_windowed_breaker_v1::*;
 pub use mint_v0::*;
+pub use pause_account_windowed_breaker_v0::*;
+pub use pause_mint_windowed_breaker_v0::*;
 pub use remove_mint_authority_v0::*;
+pub use resume_account_windowed_breaker_v0::*;
+pub use resume_mint_windowed_breaker_v0::*;
 pub use transfer_v0::*;
 pub use update_account_windowed_breaker_v0::*;
+pub use update_account_windowed_breaker_v1::*;
 pub use update_mint_windowed_breaker_v0::*;
+pub use update_mint_windowed_breaker_v1::*;
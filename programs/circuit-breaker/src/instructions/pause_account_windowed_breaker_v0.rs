@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ErrorCode, AccountWindowedCircuitBreakerV0, PauseWindowedBreakerArgsV0};
+
+#[derive(Accounts)]
+pub struct PauseAccountWindowedBreakerV0<'info> {
+  pub guardian: Signer<'info>,
+  #[account(
+    mut,
+    has_one = guardian,
+  )]
+  pub circuit_breaker: Box<Account<'info, AccountWindowedCircuitBreakerV0>>,
+}
+
+pub fn handler(
+  ctx: Context<PauseAccountWindowedBreakerV0>,
+  args: PauseWindowedBreakerArgsV0,
+) -> Result<()> {
+  let unix_timestamp = Clock::get()?.unix_timestamp;
+  if let Some(expiry_ts) = args.expiry_ts {
+    require_gt!(expiry_ts, unix_timestamp, ErrorCode::InvalidConfig);
+  }
+
+  let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+  require!(
+    circuit_breaker.extends_pause(args.expiry_ts, unix_timestamp),
+    ErrorCode::PauseCannotBeShortened
+  );
+  circuit_breaker.paused = true;
+  circuit_breaker.pause_expiry_ts = args.expiry_ts;
+
+  Ok(())
+}
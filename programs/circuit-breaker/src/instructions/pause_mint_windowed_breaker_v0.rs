@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::ErrorCode, MintWindowedCircuitBreakerV0};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PauseWindowedBreakerArgsV0 {
+  // If set, the pause lifts automatically at this time
+  pub expiry_ts: Option<i64>,
+}
+
+#[derive(Accounts)]
+pub struct PauseMintWindowedBreakerV0<'info> {
+  pub guardian: Signer<'info>,
+  #[account(
+    mut,
+    has_one = guardian,
+  )]
+  pub circuit_breaker: Box<Account<'info, MintWindowedCircuitBreakerV0>>,
+}
+
+pub fn handler(
+  ctx: Context<PauseMintWindowedBreakerV0>,
+  args: PauseWindowedBreakerArgsV0,
+) -> Result<()> {
+  let unix_timestamp = Clock::get()?.unix_timestamp;
+  if let Some(expiry_ts) = args.expiry_ts {
+    require_gt!(expiry_ts, unix_timestamp, ErrorCode::InvalidConfig);
+  }
+
+  let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+  require!(
+    circuit_breaker.extends_pause(args.expiry_ts, unix_timestamp),
+    ErrorCode::PauseCannotBeShortened
+  );
+  circuit_breaker.paused = true;
+  circuit_breaker.pause_expiry_ts = args.expiry_ts;
+
+  Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::AccountWindowedCircuitBreakerV0;
+
+#[derive(Accounts)]
+pub struct ResumeAccountWindowedBreakerV0<'info> {
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    has_one = authority,
+  )]
+  pub circuit_breaker: Box<Account<'info, AccountWindowedCircuitBreakerV0>>,
+}
+
+pub fn handler(ctx: Context<ResumeAccountWindowedBreakerV0>) -> Result<()> {
+  let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+  circuit_breaker.paused = false;
+  circuit_breaker.pause_expiry_ts = None;
+
+  Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::MintWindowedCircuitBreakerV0;
+
+#[derive(Accounts)]
+pub struct ResumeMintWindowedBreakerV0<'info> {
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    has_one = authority,
+  )]
+  pub circuit_breaker: Box<Account<'info, MintWindowedCircuitBreakerV0>>,
+}
+
+pub fn handler(ctx: Context<ResumeMintWindowedBreakerV0>) -> Result<()> {
+  let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+  circuit_breaker.paused = false;
+  circuit_breaker.pause_expiry_ts = None;
+
+  Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use shared_utils::resize_to_fit;
+
+use crate::{
+  errors::ErrorCode, window::apply_config_v1, AccountWindowedCircuitBreakerV0,
+  WindowedCircuitBreakerConfigV1,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateAccountWindowedBreakerArgsV1 {
+  pub new_authority: Option<Pubkey>,
+  pub config: Option<WindowedCircuitBreakerConfigV1>,
+  pub new_guardian: Option<Pubkey>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAccountWindowedBreakerV1<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    has_one = authority,
+  )]
+  pub circuit_breaker: Box<Account<'info, AccountWindowedCircuitBreakerV0>>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<UpdateAccountWindowedBreakerV1>,
+  args: UpdateAccountWindowedBreakerArgsV1,
+) -> Result<()> {
+  let circuit_breaker = &mut ctx.accounts.circuit_breaker;
+  if let Some(new_authority) = args.new_authority {
+    circuit_breaker.authority = new_authority;
+  }
+  if let Some(new_guardian) = args.new_guardian {
+    circuit_breaker.guardian = new_guardian;
+  }
+  // A guardian that is also the authority could pause and resume at will
+  require_keys_neq!(
+    circuit_breaker.guardian,
+    circuit_breaker.authority,
+    ErrorCode::InvalidConfig
+  );
+  if let Some(config) = args.config {
+    require!(config.is_valid(), ErrorCode::InvalidConfig);
+    let (primary, additional_windows) = apply_config_v1(
+      config,
+      &circuit_breaker.last_window,
+      &circuit_breaker.additional_windows,
+    );
+    circuit_breaker.config = primary;
+    circuit_breaker.additional_windows = additional_windows;
+  }
+
+  resize_to_fit(
+    &ctx.accounts.payer,
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.circuit_breaker,
+  )?;
+
+  Ok(())
+}
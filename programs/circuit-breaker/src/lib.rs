@@ -11,6 +11,7 @@ pub mod window;
 
 pub use instructions::*;
 pub use state::*;
+pub use window::{WindowCapacityV0, WindowedBreakerCapacityV0};
 
 #[cfg(not(feature = "no-entrypoint"))]
 security_txt! {
@@ -55,6 +56,20 @@ pub mod circuit_breaker {
     instructions::initialize_account_windowed_breaker_v0::handler(ctx, args)
   }
 
+  pub fn initialize_mint_windowed_breaker_v1(
+    ctx: Context<InitializeMintWindowedBreakerV1>,
+    args: InitializeMintWindowedBreakerArgsV1,
+  ) -> Result<()> {
+    instructions::initialize_mint_windowed_breaker_v1::handler(ctx, args)
+  }
+
+  pub fn initialize_account_windowed_breaker_v1(
+    ctx: Context<InitializeAccountWindowedBreakerV1>,
+    args: InitializeAccountWindowedBreakerArgsV1,
+  ) -> Result<()> {
+    instructions::initialize_account_windowed_breaker_v1::handler(ctx, args)
+  }
+
   pub fn mint_v0(ctx: Context<MintV0>, args: MintArgsV0) -> Result<()> {
     instructions::mint_v0::handler(ctx, args)
   }
@@ -81,6 +96,58 @@ pub mod circuit_breaker {
     instructions::update_mint_windowed_breaker_v0::handler(ctx, args)
   }
 
+  pub fn update_account_windowed_breaker_v1(
+    ctx: Context<UpdateAccountWindowedBreakerV1>,
+    args: UpdateAccountWindowedBreakerArgsV1,
+  ) -> Result<()> {
+    instructions::update_account_windowed_breaker_v1::handler(ctx, args)
+  }
+
+  pub fn update_mint_windowed_breaker_v1(
+    ctx: Context<UpdateMintWindowedBreakerV1>,
+    args: UpdateMintWindowedBreakerArgsV1,
+  ) -> Result<()> {
+    instructions::update_mint_windowed_breaker_v1::handler(ctx, args)
+  }
+
+  pub fn get_mint_windowed_breaker_capacity_v0(
+    ctx: Context<GetMintWindowedBreakerCapacityV0>,
+    args: GetWindowedBreakerCapacityArgsV0,
+  ) -> Result<WindowedBreakerCapacityV0> {
+    instructions::get_mint_windowed_breaker_capacity_v0::handler(ctx, args)
+  }
+
+  pub fn get_account_windowed_breaker_capacity_v0(
+    ctx: Context<GetAccountWindowedBreakerCapacityV0>,
+    args: GetWindowedBreakerCapacityArgsV0,
+  ) -> Result<WindowedBreakerCapacityV0> {
+    instructions::get_account_windowed_breaker_capacity_v0::handler(ctx, args)
+  }
+
+  pub fn pause_mint_windowed_breaker_v0(
+    ctx: Context<PauseMintWindowedBreakerV0>,
+    args: PauseWindowedBreakerArgsV0,
+  ) -> Result<()> {
+    instructions::pause_mint_windowed_breaker_v0::handler(ctx, args)
+  }
+
+  pub fn pause_account_windowed_breaker_v0(
+    ctx: Context<PauseAccountWindowedBreakerV0>,
+    args: PauseWindowedBreakerArgsV0,
+  ) -> Result<()> {
+    instructions::pause_account_windowed_breaker_v0::handler(ctx, args)
+  }
+
+  pub fn resume_mint_windowed_breaker_v0(ctx: Context<ResumeMintWindowedBreakerV0>) -> Result<()> {
+    instructions::resume_mint_windowed_breaker_v0::handler(ctx)
+  }
+
+  pub fn resume_account_windowed_breaker_v0(
+    ctx: Context<ResumeAccountWindowedBreakerV0>,
+  ) -> Result<()> {
+    instructions::resume_account_windowed_breaker_v0::handler(ctx)
+  }
+
   pub fn remove_mint_authority_v0(ctx: Context<RemoveMintAuthorityV0>) -> Result<()> {
     instructions::remove_mint_authority_v0::handler(ctx)
   }
@@ -28,6 +28,29 @@ impl WindowedCircuitBreakerConfigV0 {
   }
 }
 
+pub const MAX_WINDOWS: usize = 5;
+
+// Layered limits, ie "max 1% per hour AND max 5% per day". Every window must pass.
+// The first window is stored as the breaker's primary `config`, the rest as `additional_windows`.
+#[derive(Default, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct WindowedCircuitBreakerConfigV1 {
+  pub windows: Vec<WindowedCircuitBreakerConfigV0>,
+}
+
+impl WindowedCircuitBreakerConfigV1 {
+  pub fn is_valid(&self) -> bool {
+    !self.windows.is_empty()
+      && self.windows.len() <= MAX_WINDOWS
+      && self.windows.iter().all(|window| window.is_valid())
+  }
+}
+
+#[derive(Default, AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AdditionalWindowV0 {
+  pub config: WindowedCircuitBreakerConfigV0,
+  pub last_window: WindowV0,
+}
+
 #[account]
 #[derive(Default)]
 pub struct MintWindowedCircuitBreakerV0 {
@@ -37,6 +60,22 @@ pub struct MintWindowedCircuitBreakerV0 {
   pub config: WindowedCircuitBreakerConfigV0,
   pub last_window: WindowV0,
   pub bump_seed: u8,
+  pub additional_windows: Vec<AdditionalWindowV0>,
+  // Emergency key that can pause the breaker. Only the authority can resume.
+  pub guardian: Pubkey,
+  pub paused: bool,
+  // If set, the pause lifts automatically at this time
+  pub pause_expiry_ts: Option<i64>,
+}
+
+impl MintWindowedCircuitBreakerV0 {
+  pub fn is_paused(&self, unix_timestamp: i64) -> bool {
+    is_paused(self.paused, self.pause_expiry_ts, unix_timestamp)
+  }
+
+  pub fn extends_pause(&self, expiry_ts: Option<i64>, unix_timestamp: i64) -> bool {
+    extends_pause(self.paused, self.pause_expiry_ts, expiry_ts, unix_timestamp)
+  }
 }
 
 #[macro_export]
@@ -59,4 +98,41 @@ pub struct AccountWindowedCircuitBreakerV0 {
   pub config: WindowedCircuitBreakerConfigV0,
   pub last_window: WindowV0,
   pub bump_seed: u8,
+  pub additional_windows: Vec<AdditionalWindowV0>,
+  // Emergency key that can pause the breaker. Only the authority can resume.
+  pub guardian: Pubkey,
+  pub paused: bool,
+  // If set, the pause lifts automatically at this time
+  pub pause_expiry_ts: Option<i64>,
+}
+
+impl AccountWindowedCircuitBreakerV0 {
+  pub fn is_paused(&self, unix_timestamp: i64) -> bool {
+    is_paused(self.paused, self.pause_expiry_ts, unix_timestamp)
+  }
+
+  pub fn extends_pause(&self, expiry_ts: Option<i64>, unix_timestamp: i64) -> bool {
+    extends_pause(self.paused, self.pause_expiry_ts, expiry_ts, unix_timestamp)
+  }
+}
+
+fn is_paused(paused: bool, pause_expiry_ts: Option<i64>, unix_timestamp: i64) -> bool {
+  paused && !matches!(pause_expiry_ts, Some(expiry_ts) if unix_timestamp >= expiry_ts)
+}
+
+// An active pause can be made indefinite or pushed later, never cut short
+fn extends_pause(
+  paused: bool,
+  pause_expiry_ts: Option<i64>,
+  expiry_ts: Option<i64>,
+  unix_timestamp: i64,
+) -> bool {
+  if !is_paused(paused, pause_expiry_ts, unix_timestamp) {
+    return true;
+  }
+  match (pause_expiry_ts, expiry_ts) {
+    (_, None) => true,
+    (Some(current), Some(new)) => new > current,
+    (None, Some(_)) => false,
+  }
 }
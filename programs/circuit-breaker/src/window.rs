@@ -1,4 +1,7 @@
-use crate::{errors::ErrorCode, ThresholdType, WindowV0, WindowedCircuitBreakerConfigV0};
+use crate::{
+  errors::ErrorCode, AdditionalWindowV0, ThresholdType, WindowV0, WindowedCircuitBreakerConfigV0,
+  WindowedCircuitBreakerConfigV1,
+};
 use anchor_lang::prelude::*;
 
 pub fn time_decay_previous_value(
@@ -60,3 +63,288 @@ pub fn enforce_window(
     last_unix_timestamp: unix_timestamp,
   })
 }
+
+pub fn enforce_additional_windows(
+  windows: &[AdditionalWindowV0],
+  amount: u64,
+  account_value: u64,
+  unix_timestamp: i64,
+) -> Result<Vec<AdditionalWindowV0>> {
+  windows
+    .iter()
+    .map(|window| {
+      Ok(AdditionalWindowV0 {
+        config: window.config.clone(),
+        last_window: enforce_window(
+          &window.config,
+          &window.last_window,
+          amount,
+          account_value,
+          unix_timestamp,
+        )?,
+      })
+    })
+    .collect()
+}
+
+// Splits a V1 config into the primary config and additional windows. Windows that already
+// existed keep their decay state. Newly added windows start from the primary window's state
+// so that recent flow is not forgotten when tightening limits.
+pub fn apply_config_v1(
+  config: WindowedCircuitBreakerConfigV1,
+  last_window: &WindowV0,
+  existing: &[AdditionalWindowV0],
+) -> (WindowedCircuitBreakerConfigV0, Vec<AdditionalWindowV0>) {
+  let mut windows = config.windows.into_iter();
+  let primary = windows.next().unwrap_or_default();
+  let additional_windows = windows
+    .enumerate()
+    .map(|(index, config)| AdditionalWindowV0 {
+      config,
+      last_window: existing
+        .get(index)
+        .map(|window| window.last_window.clone())
+        .unwrap_or_else(|| last_window.clone()),
+    })
+    .collect();
+
+  (primary, additional_windows)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct WindowCapacityV0 {
+  pub window_size_seconds: u64,
+  // The aggregated value after time decay
+  pub aggregated_value: u64,
+  pub threshold: u64,
+  pub remaining: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct WindowedBreakerCapacityV0 {
+  pub unix_timestamp: i64,
+  // The most that can flow through the breaker right now, across all windows
+  pub remaining: u64,
+  // The earliest time at which the requested amount fits in every window, assuming the
+  // supply or balance does not change. None if no amount was requested, or if it never fits.
+  pub amount_fits_at: Option<i64>,
+  pub windows: Vec<WindowCapacityV0>,
+  pub paused: bool,
+}
+
+impl WindowedBreakerCapacityV0 {
+  // Nothing can flow while paused. A requested amount fits no earlier than the pause expiry,
+  // and never if the pause has no expiry.
+  pub fn apply_pause(&mut self, pause_expiry_ts: Option<i64>) {
+    self.paused = true;
+    self.remaining = 0;
+    self.amount_fits_at = match (self.amount_fits_at, pause_expiry_ts) {
+      (Some(fits_at), Some(expiry_ts)) => Some(std::cmp::max(fits_at, expiry_ts)),
+      _ => None,
+    };
+  }
+}
+
+pub fn get_window_capacity(
+  config: &WindowedCircuitBreakerConfigV0,
+  window: &WindowV0,
+  account_value: u64,
+  unix_timestamp: i64,
+) -> Option<WindowCapacityV0> {
+  let aggregated_value = time_decay_previous_value(config, window, unix_timestamp)?;
+  let threshold = get_threshold(config, account_value)?;
+
+  Some(WindowCapacityV0 {
+    window_size_seconds: config.window_size_seconds,
+    aggregated_value,
+    threshold,
+    remaining: threshold.saturating_sub(aggregated_value),
+  })
+}
+
+// Finds the earliest timestamp at or after unix_timestamp where amount plus the decayed
+// aggregate is within the threshold. Inverts the floor in time_decay_previous_value:
+//   floor(v * (w - e) / w) <= room  <=>  v * (w - e) <= (room + 1) * w - 1
+pub fn get_amount_fits_at(
+  config: &WindowedCircuitBreakerConfigV0,
+  window: &WindowV0,
+  amount: u64,
+  account_value: u64,
+  unix_timestamp: i64,
+) -> Option<i64> {
+  let threshold = get_threshold(config, account_value)?;
+  let room = threshold.checked_sub(amount)?;
+  if time_decay_previous_value(config, window, unix_timestamp)? <= room {
+    return Some(unix_timestamp);
+  }
+
+  let window_size = u128::from(config.window_size_seconds);
+  let max_remaining_seconds = (u128::from(room) + 1)
+    .checked_mul(window_size)?
+    .checked_sub(1)?
+    .checked_div(u128::from(window.last_aggregated_value))?;
+  let elapsed_needed = window_size.saturating_sub(max_remaining_seconds);
+
+  Some(std::cmp::max(
+    window
+      .last_unix_timestamp
+      .checked_add(i64::try_from(elapsed_needed).ok()?)?,
+    unix_timestamp,
+  ))
+}
+
+pub fn get_capacity(
+  config: &WindowedCircuitBreakerConfigV0,
+  last_window: &WindowV0,
+  additional_windows: &[AdditionalWindowV0],
+  amount: Option<u64>,
+  account_value: u64,
+  unix_timestamp: i64,
+) -> Result<WindowedBreakerCapacityV0> {
+  let all_windows = std::iter::once((config, last_window)).chain(
+    additional_windows
+      .iter()
+      .map(|window| (&window.config, &window.last_window)),
+  );
+
+  let mut windows = vec![];
+  let mut amount_fits_at = amount.map(|_| unix_timestamp);
+  for (config, window) in all_windows {
+    windows.push(
+      get_window_capacity(config, window, account_value, unix_timestamp)
+        .ok_or_else(|| error!(ErrorCode::ArithmeticError))?,
+    );
+    if let (Some(amount), Some(fits_at)) = (amount, amount_fits_at) {
+      amount_fits_at = get_amount_fits_at(config, window, amount, account_value, unix_timestamp)
+        .map(|window_fits_at| std::cmp::max(fits_at, window_fits_at));
+    }
+  }
+
+  Ok(WindowedBreakerCapacityV0 {
+    unix_timestamp,
+    remaining: windows
+      .iter()
+      .map(|window| window.remaining)
+      .min()
+      .unwrap_or_default(),
+    amount_fits_at,
+    windows,
+    paused: false,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn absolute(window_size_seconds: u64, threshold: u64) -> WindowedCircuitBreakerConfigV0 {
+    WindowedCircuitBreakerConfigV0 {
+      window_size_seconds,
+      threshold_type: ThresholdType::Absolute,
+      threshold,
+    }
+  }
+
+  #[test]
+  fn test_additional_windows_enforced_together() {
+    // 50 per 10 seconds, and 60 per 100 seconds
+    let (primary, additional) = apply_config_v1(
+      WindowedCircuitBreakerConfigV1 {
+        windows: vec![absolute(10, 50), absolute(100, 60)],
+      },
+      &WindowV0::default(),
+      &[],
+    );
+    assert_eq!(additional.len(), 1);
+
+    let last_window = enforce_window(&primary, &WindowV0::default(), 50, 0, 0).unwrap();
+    let additional = enforce_additional_windows(&additional, 50, 0, 0).unwrap();
+    assert_eq!(additional[0].last_window.last_aggregated_value, 50);
+
+    // The short window has fully decayed, but the long window has only decayed by 10%
+    assert!(enforce_window(&primary, &last_window, 50, 0, 10).is_ok());
+    assert!(enforce_additional_windows(&additional, 50, 0, 10).is_err());
+    assert!(enforce_additional_windows(&additional, 15, 0, 10).is_ok());
+  }
+
+  #[test]
+  fn test_apply_config_v1_preserves_window_state() {
+    let last_window = WindowV0 {
+      last_aggregated_value: 10,
+      last_unix_timestamp: 5,
+    };
+    let existing = vec![AdditionalWindowV0 {
+      config: absolute(100, 60),
+      last_window: WindowV0 {
+        last_aggregated_value: 40,
+        last_unix_timestamp: 5,
+      },
+    }];
+    let (primary, additional) = apply_config_v1(
+      WindowedCircuitBreakerConfigV1 {
+        windows: vec![absolute(10, 50), absolute(200, 60), absolute(1000, 100)],
+      },
+      &last_window,
+      &existing,
+    );
+
+    assert_eq!(primary.window_size_seconds, 10);
+    assert_eq!(additional[0].config.window_size_seconds, 200);
+    assert_eq!(additional[0].last_window.last_aggregated_value, 40);
+    assert_eq!(additional[1].last_window.last_aggregated_value, 10);
+  }
+
+  #[test]
+  fn test_config_v1_validity() {
+    assert!(!WindowedCircuitBreakerConfigV1 { windows: vec![] }.is_valid());
+    assert!(!WindowedCircuitBreakerConfigV1 {
+      windows: vec![absolute(0, 50)]
+    }
+    .is_valid());
+    assert!(!WindowedCircuitBreakerConfigV1 {
+      windows: vec![absolute(10, 50); crate::MAX_WINDOWS + 1]
+    }
+    .is_valid());
+    assert!(WindowedCircuitBreakerConfigV1 {
+      windows: vec![absolute(10, 50), absolute(100, 60)]
+    }
+    .is_valid());
+  }
+
+  #[test]
+  fn test_amount_fits_at() {
+    let config = absolute(100, 60);
+    let window = WindowV0 {
+      last_aggregated_value: 50,
+      last_unix_timestamp: 0,
+    };
+
+    // Fits right away
+    assert_eq!(get_amount_fits_at(&config, &window, 10, 0, 0), Some(0));
+    // Never fits
+    assert_eq!(get_amount_fits_at(&config, &window, 61, 0, 0), None);
+    // Need the aggregate to decay to 20
+    let fits_at = get_amount_fits_at(&config, &window, 40, 0, 0).unwrap();
+    assert!(enforce_window(&config, &window, 40, 0, fits_at).is_ok());
+    assert!(enforce_window(&config, &window, 40, 0, fits_at - 1).is_err());
+
+    let capacity = get_capacity(&absolute(10, 50), &window, &[], Some(40), 0, 5).unwrap();
+    assert_eq!(capacity.windows[0].aggregated_value, 25);
+    assert_eq!(capacity.remaining, 25);
+    assert_eq!(capacity.amount_fits_at, Some(8));
+  }
+
+  #[test]
+  fn test_apply_pause() {
+    let window = WindowV0::default();
+    let mut capacity = get_capacity(&absolute(10, 50), &window, &[], Some(10), 0, 5).unwrap();
+    capacity.apply_pause(Some(100));
+    assert!(capacity.paused);
+    assert_eq!(capacity.remaining, 0);
+    assert_eq!(capacity.amount_fits_at, Some(100));
+
+    let mut capacity = get_capacity(&absolute(10, 50), &window, &[], Some(10), 0, 5).unwrap();
+    capacity.apply_pause(None);
+    assert_eq!(capacity.amount_fits_at, None);
+  }
+}
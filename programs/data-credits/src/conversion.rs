@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::*, price::HntPriceV0};
+
+// $1 = 10^5 DC
+pub const DC_EXPONENT: i32 = 5;
+
+// dc_exponent = 5 since $1 = 10^5 DC
+// expo is a negative number, i.e. normally -8 for 8 hnt decimals
+// dc = (price * 10^expo) * (hnt_amount * 10^-hnt_decimals) * 10^dc_exponent
+// dc = price * hnt_amount * 10^(expo - hnt_decimals + dc_exponent)
+// dc = price * hnt_amount / 10^(hnt_decimals - expo - dc_exponent)
+// hnt_amount = dc * 10^(hnt_decimals - expo - dc_exponent) / price
+pub fn decimals_factor(hnt_decimals: u8, price: &HntPriceV0) -> Result<u128> {
+  let exponent = i32::from(hnt_decimals)
+    .checked_sub(price.exponent)
+    .and_then(|exponent| exponent.checked_sub(DC_EXPONENT))
+    .and_then(|exponent| u32::try_from(exponent).ok())
+    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?;
+  10_u128
+    .checked_pow(exponent)
+    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))
+}
+
+// Rounds down, so the DC minted never exceeds the value of the HNT burned
+pub fn hnt_to_dc(hnt_amount: u64, hnt_decimals: u8, price: &HntPriceV0) -> Result<u64> {
+  let dc_amount = u128::from(hnt_amount)
+    .checked_mul(u128::from(price.price))
+    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?
+    / decimals_factor(hnt_decimals, price)?;
+
+  u64::try_from(dc_amount).map_err(|_| error!(DataCreditsErrors::ArithmeticError))
+}
+
+// Rounds down, matching what mint_data_credits_v0 burns for an exact dc amount
+pub fn dc_to_hnt(dc_amount: u64, hnt_decimals: u8, price: &HntPriceV0) -> Result<u64> {
+  let hnt_amount = u128::from(dc_amount)
+    .checked_mul(decimals_factor(hnt_decimals, price)?)
+    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?
+    .checked_div(u128::from(price.price))
+    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?;
+
+  u64::try_from(hnt_amount).map_err(|_| error!(DataCreditsErrors::ArithmeticError))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // $2.50 with pyth's usual exponent
+  const PRICE: HntPriceV0 = HntPriceV0 {
+    price: 250_000_000,
+    exponent: -8,
+  };
+
+  #[test]
+  fn test_decimals_factor() {
+    assert_eq!(decimals_factor(8, &PRICE).unwrap(), 10_u128.pow(11));
+    assert!(decimals_factor(
+      0,
+      &HntPriceV0 {
+        price: 1,
+        exponent: 0
+      }
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn test_hnt_to_dc() {
+    // 1 HNT at $2.50 is 250,000 DC
+    assert_eq!(hnt_to_dc(100_000_000, 8, &PRICE).unwrap(), 250_000);
+    // Dust rounds down
+    assert_eq!(hnt_to_dc(399, 8, &PRICE).unwrap(), 0);
+    assert_eq!(hnt_to_dc(400, 8, &PRICE).unwrap(), 1);
+  }
+
+  #[test]
+  fn test_dc_to_hnt() {
+    assert_eq!(dc_to_hnt(250_000, 8, &PRICE).unwrap(), 100_000_000);
+    assert_eq!(dc_to_hnt(1, 8, &PRICE).unwrap(), 400);
+    assert_eq!(
+      dc_to_hnt(
+        1,
+        8,
+        &HntPriceV0 {
+          price: 0,
+          exponent: -8
+        }
+      ),
+      Err(error!(DataCreditsErrors::ArithmeticError))
+    );
+  }
+
+  #[test]
+  fn test_round_trip_never_mints_more() {
+    for hnt_amount in [1, 7, 12_345, 99_999_999, 100_000_001] {
+      let dc_amount = hnt_to_dc(hnt_amount, 8, &PRICE).unwrap();
+      assert!(dc_to_hnt(dc_amount, 8, &PRICE).unwrap() <= hnt_amount);
+    }
+  }
+}
@@ -26,4 +26,31 @@ pub enum DataCreditsErrors {
 
   #[msg("Price oracle is not owned by a known Pyth receiver")]
   InvalidPriceOracleOwner,
+
+  #[msg("Price source is not allowed on this cluster")]
+  InvalidPriceSource,
+
+  #[msg("HNT price is not available")]
+  PriceNotAvailable,
+
+  #[msg("Delegated data credits are paused by their owner")]
+  DelegationPaused,
+
+  #[msg("Burn exceeds the remaining delegated allowance")]
+  AllowanceExceeded,
+
+  #[msg("Not authorized to update the delegated allowance")]
+  InvalidAllowanceAuthority,
+
+  #[msg("Not authorized to withdraw delegated data credits")]
+  InvalidRefundAuthority,
+
+  #[msg("No withdrawal has been requested")]
+  NoPendingWithdrawal,
+
+  #[msg("Withdrawal is still timelocked")]
+  WithdrawalLocked,
+
+  #[msg("Delegated DC held by deposits cannot change sub dao")]
+  EscrowHasDeposits,
 }
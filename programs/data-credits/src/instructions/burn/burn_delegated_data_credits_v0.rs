@@ -1,10 +1,10 @@
-use crate::{DataCreditsV0, DelegatedDataCreditsV0};
+use crate::{errors::DataCreditsErrors, DataCreditsV0, DelegatedDataCreditsV0};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::hash::hash;
 use anchor_spl::token::{self, Mint, Token, TokenAccount};
 use helium_sub_daos::{
   cpi::{accounts::TrackDcBurnV0, track_dc_burn_v0},
-  DaoV0, SubDaoV0, TrackDcBurnArgsV0,
+  current_epoch, DaoV0, SubDaoV0, TrackDcBurnArgsV0,
 };
 
 #[derive(Debug, Clone)]
@@ -59,6 +59,7 @@ pub struct BurnDelegatedDataCreditsV0<'info> {
   )]
   pub data_credits: Box<Account<'info, DataCreditsV0>>,
   #[account(
+    mut,
     has_one = escrow_account,
     has_one = sub_dao,
     has_one = data_credits,
@@ -92,6 +93,13 @@ pub fn handler(
   ctx: Context<BurnDelegatedDataCreditsV0>,
   args: BurnDelegatedDataCreditsArgsV0,
 ) -> Result<()> {
+  if let Some(allowance) = &mut ctx.accounts.delegated_data_credits.allowance {
+    require!(
+      allowance.try_burn(args.amount, current_epoch(Clock::get()?.unix_timestamp)),
+      DataCreditsErrors::AllowanceExceeded
+    );
+  }
+
   // burn the dc tokens
   token::burn(
     CpiContext::new_with_signer(
@@ -4,8 +4,9 @@ use anchor_spl::{
   token::{transfer, Mint, Token, TokenAccount, Transfer},
 };
 use helium_sub_daos::{DaoV0, SubDaoV0};
+use shared_utils::resize_to_fit;
 
-use crate::{hash_name, state::*};
+use crate::{errors::DataCreditsErrors, hash_name, state::*};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct ChangeDelegatedSubDaoArgsV0 {
@@ -20,6 +21,7 @@ pub struct ChangeDelegatedSubDaoV0<'info> {
   pub payer: Signer<'info>,
   pub authority: Signer<'info>,
   #[account(
+    mut,
     seeds = [
       "delegated_data_credits".as_bytes(),
       sub_dao.key().as_ref(),
@@ -88,16 +90,40 @@ pub fn handler(
   ctx: Context<ChangeDelegatedSubDaoV0>,
   args: ChangeDelegatedSubDaoArgsV0,
 ) -> Result<()> {
-  ctx
-    .accounts
-    .destination_delegated_data_credits
-    .set_inner(DelegatedDataCreditsV0 {
-      data_credits: ctx.accounts.data_credits.key(),
-      router_key: args.router_key.clone(),
-      sub_dao: ctx.accounts.destination_sub_dao.key(),
-      escrow_account: ctx.accounts.destination_escrow_account.key(),
-      bump: ctx.bumps.destination_delegated_data_credits,
-    });
+  // Deposits cannot follow the DC, so moving it would take value from their owners
+  require!(
+    ctx.accounts.delegated_data_credits.deposit_shares == 0,
+    DataCreditsErrors::EscrowHasDeposits
+  );
+
+  let destination = &mut ctx.accounts.destination_delegated_data_credits;
+  // Spending caps follow the DC unless the destination has its own
+  let allowance = destination
+    .allowance
+    .take()
+    .or_else(|| ctx.accounts.delegated_data_credits.allowance.clone());
+  let total_shares = destination.total_shares;
+  let deposit_shares = destination.deposit_shares;
+  destination.set_inner(DelegatedDataCreditsV0 {
+    data_credits: ctx.accounts.data_credits.key(),
+    router_key: args.router_key.clone(),
+    sub_dao: ctx.accounts.destination_sub_dao.key(),
+    escrow_account: ctx.accounts.destination_escrow_account.key(),
+    bump: ctx.bumps.destination_delegated_data_credits,
+    allowance,
+    total_shares,
+    deposit_shares,
+  });
+  // The DC is not owned by any delegator of the destination
+  destination
+    .issue_shares(args.amount, ctx.accounts.destination_escrow_account.amount)
+    .ok_or(DataCreditsErrors::ArithmeticError)?;
+
+  resize_to_fit(
+    &ctx.accounts.payer,
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.destination_delegated_data_credits,
+  )?;
 
   transfer(
     CpiContext::new_with_signer(
@@ -117,5 +143,9 @@ pub fn handler(
     args.amount,
   )?;
 
+  // No deposit holds shares of the source, so what is left of it is owned by nobody, like DC
+  // delegated before any shares were issued
+  ctx.accounts.delegated_data_credits.total_shares = 0;
+
   Ok(())
 }
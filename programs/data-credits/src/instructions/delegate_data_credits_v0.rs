@@ -7,8 +7,9 @@ use anchor_spl::{
   },
 };
 use helium_sub_daos::{DaoV0, SubDaoV0};
+use shared_utils::resize_to_fit;
 
-use crate::{hash_name, state::*};
+use crate::{errors::DataCreditsErrors, hash_name, state::*};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct DelegateDataCreditsArgsV0 {
@@ -73,57 +74,86 @@ pub struct DelegateDataCreditsV0<'info> {
   pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<DelegateDataCreditsV0>, args: DelegateDataCreditsArgsV0) -> Result<()> {
-  ctx
-    .accounts
-    .delegated_data_credits
-    .set_inner(DelegatedDataCreditsV0 {
-      data_credits: ctx.accounts.data_credits.key(),
+impl DelegateDataCreditsV0<'_> {
+  // Moves the DC into the escrow, returning the escrow shares issued for it
+  pub fn delegate(
+    &mut self,
+    delegated_data_credits_bump: u8,
+    args: DelegateDataCreditsArgsV0,
+  ) -> Result<u128> {
+    let delegated_data_credits = &mut self.delegated_data_credits;
+    let allowance = delegated_data_credits.allowance.take();
+    let total_shares = delegated_data_credits.total_shares;
+    let deposit_shares = delegated_data_credits.deposit_shares;
+    delegated_data_credits.set_inner(DelegatedDataCreditsV0 {
+      data_credits: self.data_credits.key(),
       router_key: args.router_key,
-      sub_dao: ctx.accounts.sub_dao.key(),
-      escrow_account: ctx.accounts.escrow_account.key(),
-      bump: ctx.bumps.delegated_data_credits,
+      sub_dao: self.sub_dao.key(),
+      escrow_account: self.escrow_account.key(),
+      bump: delegated_data_credits_bump,
+      allowance,
+      total_shares,
+      deposit_shares,
     });
+    let shares = delegated_data_credits
+      .issue_shares(args.amount, self.escrow_account.amount)
+      .ok_or(DataCreditsErrors::ArithmeticError)?;
+
+    resize_to_fit(
+      &self.payer,
+      &self.system_program.to_account_info(),
+      &self.delegated_data_credits,
+    )?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+      "dc".as_bytes(),
+      self.dc_mint.to_account_info().key.as_ref(),
+      &[self.data_credits.data_credits_bump],
+    ]];
+
+    if self.from_account.is_frozen() {
+      thaw_account(CpiContext::new_with_signer(
+        self.token_program.to_account_info(),
+        ThawAccount {
+          account: self.from_account.to_account_info(),
+          mint: self.dc_mint.to_account_info(),
+          authority: self.data_credits.to_account_info(),
+        },
+        signer_seeds,
+      ))?;
+    }
+
+    transfer(
+      CpiContext::new(
+        self.token_program.to_account_info(),
+        Transfer {
+          from: self.from_account.to_account_info(),
+          to: self.escrow_account.to_account_info(),
+          authority: self.owner.to_account_info(),
+        },
+      ),
+      args.amount,
+    )?;
 
-  let signer_seeds: &[&[&[u8]]] = &[&[
-    "dc".as_bytes(),
-    ctx.accounts.dc_mint.to_account_info().key.as_ref(),
-    &[ctx.accounts.data_credits.data_credits_bump],
-  ]];
-
-  if ctx.accounts.from_account.is_frozen() {
-    thaw_account(CpiContext::new_with_signer(
-      ctx.accounts.token_program.to_account_info(),
-      ThawAccount {
-        account: ctx.accounts.from_account.to_account_info(),
-        mint: ctx.accounts.dc_mint.to_account_info(),
-        authority: ctx.accounts.data_credits.to_account_info(),
+    freeze_account(CpiContext::new_with_signer(
+      self.token_program.to_account_info(),
+      FreezeAccount {
+        account: self.from_account.to_account_info(),
+        mint: self.dc_mint.to_account_info(),
+        authority: self.data_credits.to_account_info(),
       },
       signer_seeds,
     ))?;
+
+    Ok(shares)
   }
+}
 
-  transfer(
-    CpiContext::new(
-      ctx.accounts.token_program.to_account_info(),
-      Transfer {
-        from: ctx.accounts.from_account.to_account_info(),
-        to: ctx.accounts.escrow_account.to_account_info(),
-        authority: ctx.accounts.owner.to_account_info(),
-      },
-    ),
-    args.amount,
-  )?;
-
-  freeze_account(CpiContext::new_with_signer(
-    ctx.accounts.token_program.to_account_info(),
-    FreezeAccount {
-      account: ctx.accounts.from_account.to_account_info(),
-      mint: ctx.accounts.dc_mint.to_account_info(),
-      authority: ctx.accounts.data_credits.to_account_info(),
-    },
-    signer_seeds,
-  ))?;
+/// DC delegated here is not tracked per delegator and cannot be withdrawn, use
+/// delegate_data_credits_v1 for that.
+pub fn handler(ctx: Context<DelegateDataCreditsV0>, args: DelegateDataCreditsArgsV0) -> Result<()> {
+  let bump = ctx.bumps.delegated_data_credits;
+  ctx.accounts.delegate(bump, args)?;
 
   Ok(())
 }
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use super::delegate_data_credits_v0::*;
+use crate::{errors::DataCreditsErrors, state::*};
+
+#[derive(Accounts)]
+#[instruction(args: DelegateDataCreditsArgsV0)]
+pub struct DelegateDataCreditsV1<'info> {
+  pub common: DelegateDataCreditsV0<'info>,
+  #[account(
+    init_if_needed,
+    payer = common.payer,
+    space = 8 + 60 + std::mem::size_of::<DelegatedDataCreditsDepositV0>(),
+    seeds = [
+      "delegated_data_credits_deposit".as_bytes(),
+      common.delegated_data_credits.key().as_ref(),
+      common.owner.key().as_ref(),
+    ],
+    bump,
+  )]
+  pub deposit: Box<Account<'info, DelegatedDataCreditsDepositV0>>,
+  pub system_program: Program<'info, System>,
+}
+
+/// Delegates DC while recording the owner's share of the escrow, so that the unburned part
+/// can later be withdrawn with request_delegated_withdrawal_v0.
+pub fn handler(ctx: Context<DelegateDataCreditsV1>, args: DelegateDataCreditsArgsV0) -> Result<()> {
+  let shares = ctx
+    .accounts
+    .common
+    .delegate(ctx.bumps.common.delegated_data_credits, args)?;
+
+  let deposit = &mut ctx.accounts.deposit;
+  if deposit.delegated_data_credits == Pubkey::default() {
+    deposit.delegated_data_credits = ctx.accounts.common.delegated_data_credits.key();
+    deposit.owner = ctx.accounts.common.owner.key();
+    deposit.refund_authority = ctx.accounts.common.owner.key();
+    deposit.bump_seed = ctx.bumps.deposit;
+  }
+  ctx
+    .accounts
+    .common
+    .delegated_data_credits
+    .credit_deposit(deposit, shares)
+    .ok_or(DataCreditsErrors::ArithmeticError)?;
+
+  Ok(())
+}
@@ -93,6 +93,9 @@ pub fn handler(
       sub_dao: ctx.accounts.sub_dao.key(),
       escrow_account: ctx.accounts.escrow_account.key(),
       bump: ctx.bumps.delegated_data_credits,
+      allowance: None,
+      total_shares: 0,
+      deposit_shares: 0,
     });
 
   let signer_seeds: &[&[&[u8]]] = &[&[
@@ -1,5 +1,3 @@
-use std::ops::Div;
-
 use anchor_lang::prelude::*;
 use anchor_spl::{
   associated_token::AssociatedToken,
@@ -11,7 +9,11 @@ use circuit_breaker::{
 };
 use pyth_solana_receiver_sdk::price_update::{PriceUpdateV2, VerificationLevel};
 
-use crate::{errors::*, DataCreditsV0};
+use crate::{
+  conversion::{dc_to_hnt, hnt_to_dc},
+  errors::*,
+  load_hnt_price, DataCreditsV0,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct MintDataCreditsArgsV0 {
@@ -42,9 +44,9 @@ pub struct MintDataCreditsV0<'info> {
 
   /// CHECK: Pinned to the address stored on `data_credits` by the has_one above
   /// (updatable via `UpdateDataCreditsV0`), then verified in the handler via
-  /// `load_hnt_price_oracle` — must be owned by the pro Pyth receiver, deserialize
-  /// as a PriceUpdateV2 for the HNT feed with Full verification, and be within the
-  /// freshness window.
+  /// `load_hnt_price` for the configured `price_source`. For Pyth it must be owned by
+  /// the pro Pyth receiver, deserialize as a PriceUpdateV2 for the HNT feed with Full
+  /// verification, and be within the freshness window.
   pub hnt_price_oracle: UncheckedAccount<'info>,
 
   // hnt tokens from this account are burned
@@ -159,61 +161,16 @@ pub fn handler(ctx: Context<MintDataCreditsV0>, args: MintDataCreditsArgsV0) ->
     token::thaw_account(ctx.accounts.thaw_ctx().with_signer(signer_seeds))?;
   }
 
-  let hnt_price_oracle = load_hnt_price_oracle(&ctx.accounts.hnt_price_oracle)?;
-  let message = hnt_price_oracle.price_message;
-
-  let current_time = Clock::get()?.unix_timestamp;
-  require_gte!(
-    message
-      .publish_time
-      .saturating_add(if TESTING { 6000000 } else { 10 * 60 }.into()),
-    current_time,
-    DataCreditsErrors::PythPriceNotFound
-  );
-  let hnt_price = message.ema_price;
-  require_gt!(hnt_price, 0);
-
-  // Remove the confidence from the price to use the most conservative price
-  // https://docs.pyth.network/price-feeds/solana-price-feeds/best-practices#confidence-intervals
-  let hnt_price_with_conf = hnt_price
-    .checked_sub(i64::try_from(message.ema_conf.checked_mul(2).unwrap()).unwrap())
-    .unwrap();
-
-  // dc_exponent = 5 since $1 = 10^5 DC
-  // expo is a negative number, i.e. normally -8 for 8 hnt decimals
-  // dc = (price * 10^expo) * (hnt_amount * 10^-hnt_decimals) * 10^dc_exponent
-  // dc = price * hnt_amount * 10^(expo - hnt_decimals + dc_exponent)
-  // dc = price * hnt_amount / 10^(hnt_decimals - expo - dc_exponent)
-  // hnt_amount = dc * 10^(hnt_decimals - expo - dc_exponent) / price
-  let exponent = i32::from(ctx.accounts.hnt_mint.decimals) - message.exponent - 5;
-  let decimals_factor = 10_u128
-    .checked_pow(u32::try_from(exponent).unwrap())
-    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?;
+  let hnt_price = load_hnt_price(
+    &ctx.accounts.data_credits.price_source,
+    &ctx.accounts.hnt_price_oracle,
+    Clock::get()?.unix_timestamp,
+  )?;
+  let hnt_decimals = ctx.accounts.hnt_mint.decimals;
 
   let (hnt_amount, dc_amount) = match (args.hnt_amount, args.dc_amount) {
-    (Some(hnt_amount), None) => {
-      let dc_amount = u64::try_from(
-        u128::from(hnt_amount)
-          .checked_mul(u128::try_from(hnt_price_with_conf).unwrap())
-          .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?
-          .div(decimals_factor),
-      )
-      .map_err(|_| error!(DataCreditsErrors::ArithmeticError))?;
-
-      (hnt_amount, dc_amount)
-    }
-    (None, Some(dc_amount)) => {
-      let hnt_amount = u64::try_from(
-        u128::from(dc_amount)
-          .checked_mul(decimals_factor)
-          .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?
-          .checked_div(u128::try_from(hnt_price_with_conf).unwrap())
-          .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?,
-      )
-      .map_err(|_| error!(DataCreditsErrors::ArithmeticError))?;
-
-      (hnt_amount, dc_amount)
-    }
+    (Some(hnt_amount), None) => (hnt_amount, hnt_to_dc(hnt_amount, hnt_decimals, &hnt_price)?),
+    (None, Some(dc_amount)) => (dc_to_hnt(dc_amount, hnt_decimals, &hnt_price)?, dc_amount),
     (None, None) => {
       return Err(error!(DataCreditsErrors::InvalidArgs));
     }
@@ -227,8 +184,8 @@ pub fn handler(ctx: Context<MintDataCreditsV0>, args: MintDataCreditsArgsV0) ->
 
   msg!(
     "HNT Price is {} * 10^{}, issuing {} data credits",
-    hnt_price_with_conf,
-    message.exponent,
+    hnt_price.price,
+    hnt_price.exponent,
     dc_amount
   );
 
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+  conversion::{dc_to_hnt, hnt_to_dc},
+  load_hnt_price, DataCreditsV0, HntPriceV0,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct QuoteMintDataCreditsArgsV0 {
+  pub hnt_amount: u64,
+  pub dc_amount: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct MintDataCreditsQuoteV0 {
+  // The conservative price mint_data_credits_v0 would use
+  pub hnt_price: HntPriceV0,
+  // DC minted when burning `hnt_amount`
+  pub dc_for_hnt_amount: u64,
+  // HNT burned when minting `dc_amount`
+  pub hnt_for_dc_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct QuoteMintDataCreditsV0<'info> {
+  #[account(
+    seeds = [
+      "dc".as_bytes(),
+      dc_mint.key().as_ref(),
+    ],
+    bump = data_credits.data_credits_bump,
+    has_one = hnt_mint,
+    has_one = hnt_price_oracle,
+  )]
+  pub data_credits: Box<Account<'info, DataCreditsV0>>,
+  /// CHECK: Checked by has_one on data_credits and verified by `load_hnt_price`
+  pub hnt_price_oracle: UncheckedAccount<'info>,
+  pub hnt_mint: Box<Account<'info, Mint>>,
+  pub dc_mint: Box<Account<'info, Mint>>,
+}
+
+pub fn handler(
+  ctx: Context<QuoteMintDataCreditsV0>,
+  args: QuoteMintDataCreditsArgsV0,
+) -> Result<MintDataCreditsQuoteV0> {
+  let hnt_price = load_hnt_price(
+    &ctx.accounts.data_credits.price_source,
+    &ctx.accounts.hnt_price_oracle,
+    Clock::get()?.unix_timestamp,
+  )?;
+  let hnt_decimals = ctx.accounts.hnt_mint.decimals;
+
+  Ok(MintDataCreditsQuoteV0 {
+    hnt_price,
+    dc_for_hnt_amount: hnt_to_dc(args.hnt_amount, hnt_decimals, &hnt_price)?,
+    hnt_for_dc_amount: dc_to_hnt(args.dc_amount, hnt_decimals, &hnt_price)?,
+  })
+}
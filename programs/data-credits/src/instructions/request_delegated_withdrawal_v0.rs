@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{errors::DataCreditsErrors, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct RequestDelegatedWithdrawalArgsV0 {
+  // 0 cancels any pending withdrawal
+  pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct RequestDelegatedWithdrawalV0<'info> {
+  pub refund_authority: Signer<'info>,
+  #[account(
+    seeds = ["dc".as_bytes(), dc_mint.key().as_ref()],
+    bump = data_credits.data_credits_bump,
+    has_one = dc_mint,
+  )]
+  pub data_credits: Box<Account<'info, DataCreditsV0>>,
+  pub dc_mint: Box<Account<'info, Mint>>,
+  #[account(
+    has_one = data_credits,
+  )]
+  pub delegated_data_credits: Box<Account<'info, DelegatedDataCreditsV0>>,
+  #[account(
+    mut,
+    has_one = delegated_data_credits,
+    constraint = deposit.refund_authority == refund_authority.key() @ DataCreditsErrors::InvalidRefundAuthority,
+  )]
+  pub deposit: Box<Account<'info, DelegatedDataCreditsDepositV0>>,
+  #[account(
+    token::mint = dc_mint,
+  )]
+  pub destination: Box<Account<'info, TokenAccount>>,
+}
+
+/// The amount is capped at the deposit's share of the escrow when the withdrawal executes.
+pub fn handler(
+  ctx: Context<RequestDelegatedWithdrawalV0>,
+  args: RequestDelegatedWithdrawalArgsV0,
+) -> Result<()> {
+  ctx.accounts.deposit.pending_withdrawal = if args.amount == 0 {
+    None
+  } else {
+    Some(PendingWithdrawalV0 {
+      amount: args.amount,
+      destination: ctx.accounts.destination.key(),
+      unlock_ts: Clock::get()?
+        .unix_timestamp
+        .checked_add(ctx.accounts.data_credits.withdrawal_timelock_seconds.into())
+        .unwrap(),
+    })
+  };
+
+  Ok(())
+}
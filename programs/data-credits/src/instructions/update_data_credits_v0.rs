@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 
-use crate::state::*;
+use crate::{errors::DataCreditsErrors, price::is_price_source_allowed, state::*};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct UpdateDataCreditsArgsV0 {
   new_authority: Option<Pubkey>,
   hnt_price_oracle: Option<Pubkey>,
+  price_source: Option<PriceSourceV0>,
+  withdrawal_timelock_seconds: Option<u32>,
 }
 
 #[derive(Accounts)]
@@ -32,5 +34,17 @@ pub fn handler(ctx: Context<UpdateDataCreditsV0>, args: UpdateDataCreditsArgsV0)
     ctx.accounts.data_credits.hnt_price_oracle = hnt_price_oracle;
   }
 
+  if let Some(price_source) = args.price_source {
+    require!(
+      is_price_source_allowed(&price_source),
+      DataCreditsErrors::InvalidPriceSource
+    );
+    ctx.accounts.data_credits.price_source = price_source;
+  }
+
+  if let Some(withdrawal_timelock_seconds) = args.withdrawal_timelock_seconds {
+    ctx.accounts.data_credits.withdrawal_timelock_seconds = withdrawal_timelock_seconds;
+  }
+
   Ok(())
 }
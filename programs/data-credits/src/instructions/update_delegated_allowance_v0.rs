@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use helium_sub_daos::current_epoch;
+use shared_utils::resize_to_fit;
+
+use crate::{errors::DataCreditsErrors, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateDelegatedAllowanceArgsV0 {
+  // Required when the delegation does not have an allowance yet
+  pub new_owner: Option<Pubkey>,
+  pub paused: Option<bool>,
+  // 0 removes the cap
+  pub max_burn_per_epoch: Option<u64>,
+  // 0 removes the cap
+  pub max_burn_lifetime: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDelegatedAllowanceV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  // The allowance owner. When assigning the first owner, the owner or refund authority of a
+  // deposit holding the majority of the escrow
+  pub authority: Signer<'info>,
+  pub data_credits: Box<Account<'info, DataCreditsV0>>,
+  #[account(
+    mut,
+    has_one = data_credits,
+  )]
+  pub delegated_data_credits: Box<Account<'info, DelegatedDataCreditsV0>>,
+  pub system_program: Program<'info, System>,
+  #[account(
+    has_one = delegated_data_credits,
+    constraint = deposit.owner == authority.key() || deposit.refund_authority == authority.key() @ DataCreditsErrors::InvalidAllowanceAuthority,
+  )]
+  pub deposit: Option<Box<Account<'info, DelegatedDataCreditsDepositV0>>>,
+}
+
+pub fn handler(
+  ctx: Context<UpdateDelegatedAllowanceV0>,
+  args: UpdateDelegatedAllowanceArgsV0,
+) -> Result<()> {
+  let authority = ctx.accounts.authority.key();
+  let mut allowance = match ctx.accounts.delegated_data_credits.allowance.take() {
+    Some(allowance) => {
+      require_keys_eq!(
+        allowance.owner,
+        authority,
+        DataCreditsErrors::InvalidAllowanceAuthority
+      );
+      allowance
+    }
+    None => {
+      let total_shares = ctx.accounts.delegated_data_credits.total_shares;
+      require!(
+        ctx
+          .accounts
+          .deposit
+          .as_ref()
+          .is_some_and(|deposit| deposit.shares > total_shares / 2),
+        DataCreditsErrors::InvalidAllowanceAuthority
+      );
+      DelegatedAllowanceV0 {
+        owner: args.new_owner.ok_or(DataCreditsErrors::InvalidArgs)?,
+        ..Default::default()
+      }
+    }
+  };
+
+  if let Some(new_owner) = args.new_owner {
+    allowance.owner = new_owner;
+  }
+  if let Some(paused) = args.paused {
+    allowance.paused = paused;
+  }
+  if let Some(max_burn_per_epoch) = args.max_burn_per_epoch {
+    allowance.max_burn_per_epoch = max_burn_per_epoch;
+  }
+  if let Some(max_burn_lifetime) = args.max_burn_lifetime {
+    allowance.max_burn_lifetime = max_burn_lifetime;
+  }
+  allowance.refresh(current_epoch(Clock::get()?.unix_timestamp));
+  ctx.accounts.delegated_data_credits.allowance = Some(allowance);
+
+  resize_to_fit(
+    &ctx.accounts.payer,
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.delegated_data_credits,
+  )?;
+
+  Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::DataCreditsErrors, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateRefundAuthorityArgsV0 {
+  pub new_refund_authority: Pubkey,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRefundAuthorityV0<'info> {
+  pub refund_authority: Signer<'info>,
+  #[account(
+    mut,
+    constraint = deposit.refund_authority == refund_authority.key() @ DataCreditsErrors::InvalidRefundAuthority,
+  )]
+  pub deposit: Box<Account<'info, DelegatedDataCreditsDepositV0>>,
+}
+
+pub fn handler(
+  ctx: Context<UpdateRefundAuthorityV0>,
+  args: UpdateRefundAuthorityArgsV0,
+) -> Result<()> {
+  ctx.accounts.deposit.refund_authority = args.new_refund_authority;
+
+  Ok(())
+}
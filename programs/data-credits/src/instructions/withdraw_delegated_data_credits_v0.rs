@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+  freeze_account, thaw_account, transfer, FreezeAccount, Mint, ThawAccount, Token, TokenAccount,
+  Transfer,
+};
+use helium_sub_daos::SubDaoV0;
+
+use crate::{errors::DataCreditsErrors, hash_name, state::*};
+
+// Permissionless once the timelock has passed, the destination was chosen by the deposit's
+// refund authority when requesting.
+#[derive(Accounts)]
+pub struct WithdrawDelegatedDataCreditsV0<'info> {
+  #[account(
+    seeds = ["dc".as_bytes(), dc_mint.key().as_ref()],
+    bump = data_credits.data_credits_bump,
+    has_one = dc_mint,
+  )]
+  pub data_credits: Box<Account<'info, DataCreditsV0>>,
+  pub dc_mint: Box<Account<'info, Mint>>,
+  pub sub_dao: Box<Account<'info, SubDaoV0>>,
+  #[account(
+    mut,
+    has_one = data_credits,
+    has_one = sub_dao,
+    has_one = escrow_account,
+  )]
+  pub delegated_data_credits: Box<Account<'info, DelegatedDataCreditsV0>>,
+  #[account(
+    mut,
+    has_one = delegated_data_credits,
+  )]
+  pub deposit: Box<Account<'info, DelegatedDataCreditsDepositV0>>,
+  #[account(mut)]
+  pub escrow_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    token::mint = dc_mint,
+    constraint = deposit.pending_withdrawal.as_ref().map(|w| w.destination) == Some(destination.key()) @ DataCreditsErrors::NoPendingWithdrawal,
+  )]
+  pub destination: Box<Account<'info, TokenAccount>>,
+  pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawDelegatedDataCreditsV0>) -> Result<()> {
+  let pending_withdrawal = ctx
+    .accounts
+    .deposit
+    .pending_withdrawal
+    .take()
+    .ok_or(DataCreditsErrors::NoPendingWithdrawal)?;
+  require_gte!(
+    Clock::get()?.unix_timestamp,
+    pending_withdrawal.unlock_ts,
+    DataCreditsErrors::WithdrawalLocked
+  );
+
+  // Burns are borne pro rata by every deposit, including those that settled during the timelock
+  let escrow_amount = ctx.accounts.escrow_account.amount;
+  let delegated_data_credits = &mut ctx.accounts.delegated_data_credits;
+  let deposit = &mut ctx.accounts.deposit;
+  let amount = std::cmp::min(
+    pending_withdrawal.amount,
+    delegated_data_credits
+      .shares_value(deposit.shares, escrow_amount)
+      .ok_or(DataCreditsErrors::ArithmeticError)?,
+  );
+  delegated_data_credits
+    .redeem_shares(deposit, amount, escrow_amount)
+    .ok_or(DataCreditsErrors::ArithmeticError)?;
+
+  let dc_seeds: &[&[&[u8]]] = &[&[
+    "dc".as_bytes(),
+    ctx.accounts.dc_mint.to_account_info().key.as_ref(),
+    &[ctx.accounts.data_credits.data_credits_bump],
+  ]];
+  let is_frozen = ctx.accounts.destination.is_frozen();
+  if is_frozen {
+    thaw_account(CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      ThawAccount {
+        account: ctx.accounts.destination.to_account_info(),
+        mint: ctx.accounts.dc_mint.to_account_info(),
+        authority: ctx.accounts.data_credits.to_account_info(),
+      },
+      dc_seeds,
+    ))?;
+  }
+
+  transfer(
+    CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      Transfer {
+        from: ctx.accounts.escrow_account.to_account_info(),
+        to: ctx.accounts.destination.to_account_info(),
+        authority: ctx.accounts.delegated_data_credits.to_account_info(),
+      },
+      &[&[
+        b"delegated_data_credits",
+        ctx.accounts.sub_dao.key().as_ref(),
+        &hash_name(&ctx.accounts.delegated_data_credits.router_key),
+        &[ctx.accounts.delegated_data_credits.bump],
+      ]],
+    ),
+    amount,
+  )?;
+
+  if is_frozen {
+    freeze_account(CpiContext::new_with_signer(
+      ctx.accounts.token_program.to_account_info(),
+      FreezeAccount {
+        account: ctx.accounts.destination.to_account_info(),
+        mint: ctx.accounts.dc_mint.to_account_info(),
+        authority: ctx.accounts.data_credits.to_account_info(),
+      },
+      dc_seeds,
+    ))?;
+  }
+
+  Ok(())
+}
@@ -2,11 +2,14 @@ use anchor_lang::prelude::*;
 #[cfg(not(feature = "no-entrypoint"))]
 use {default_env::default_env, solana_security_txt::security_txt};
 
+pub mod conversion;
 pub mod errors;
 pub mod instructions;
+pub mod price;
 pub mod state;
 
 pub use instructions::*;
+pub use price::*;
 pub use state::*;
 
 declare_id!("credMBJhYFzfn7NxBMdU4aUqFggAjgztaCcv2Fo6fPT");
@@ -80,6 +83,13 @@ pub mod data_credits {
     instructions::delegate_data_credits_v0::handler(ctx, args)
   }
 
+  pub fn delegate_data_credits_v1(
+    ctx: Context<DelegateDataCreditsV1>,
+    args: DelegateDataCreditsArgsV0,
+  ) -> Result<()> {
+    instructions::delegate_data_credits_v1::handler(ctx, args)
+  }
+
   pub fn update_data_credits_v0(
     ctx: Context<UpdateDataCreditsV0>,
     args: UpdateDataCreditsArgsV0,
@@ -93,4 +103,38 @@ pub mod data_credits {
   ) -> Result<()> {
     instructions::change_delegated_sub_dao_v0::handler(ctx, args)
   }
+
+  pub fn quote_mint_data_credits_v0(
+    ctx: Context<QuoteMintDataCreditsV0>,
+    args: QuoteMintDataCreditsArgsV0,
+  ) -> Result<MintDataCreditsQuoteV0> {
+    instructions::quote_mint_data_credits_v0::handler(ctx, args)
+  }
+
+  pub fn update_delegated_allowance_v0(
+    ctx: Context<UpdateDelegatedAllowanceV0>,
+    args: UpdateDelegatedAllowanceArgsV0,
+  ) -> Result<()> {
+    instructions::update_delegated_allowance_v0::handler(ctx, args)
+  }
+
+  pub fn update_refund_authority_v0(
+    ctx: Context<UpdateRefundAuthorityV0>,
+    args: UpdateRefundAuthorityArgsV0,
+  ) -> Result<()> {
+    instructions::update_refund_authority_v0::handler(ctx, args)
+  }
+
+  pub fn request_delegated_withdrawal_v0(
+    ctx: Context<RequestDelegatedWithdrawalV0>,
+    args: RequestDelegatedWithdrawalArgsV0,
+  ) -> Result<()> {
+    instructions::request_delegated_withdrawal_v0::handler(ctx, args)
+  }
+
+  pub fn withdraw_delegated_data_credits_v0(
+    ctx: Context<WithdrawDelegatedDataCreditsV0>,
+  ) -> Result<()> {
+    instructions::withdraw_delegated_data_credits_v0::handler(ctx)
+  }
 }
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use price_oracle::PriceOracleV0;
+
+use crate::{errors::*, load_hnt_price_oracle, state::PriceSourceV0, TESTING};
+
+pub const DEFAULT_MAX_AGE_SECONDS: u32 = 10 * 60;
+pub const DEFAULT_CONF_MULTIPLIER: u8 = 2;
+
+// HNT price in USD is price * 10^exponent
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq)]
+pub struct HntPriceV0 {
+  pub price: u64,
+  pub exponent: i32,
+}
+
+pub fn is_price_source_allowed(price_source: &PriceSourceV0) -> bool {
+  !matches!(price_source, PriceSourceV0::Fixed { .. }) || TESTING || cfg!(feature = "devnet")
+}
+
+// A configured max age always applies. Only the default is relaxed for localnet, where the
+// price accounts are cloned once and never updated.
+fn max_age(max_age_seconds: Option<u32>) -> i64 {
+  match max_age_seconds {
+    Some(max_age_seconds) => max_age_seconds.into(),
+    None if TESTING => 6000000,
+    None => DEFAULT_MAX_AGE_SECONDS.into(),
+  }
+}
+
+// Removes the confidence from the price to use the most conservative price
+// https://docs.pyth.network/price-feeds/solana-price-feeds/best-practices#confidence-intervals
+pub fn price_with_conf(ema_price: i64, ema_conf: u64, conf_multiplier: u8) -> Result<u64> {
+  let conf = ema_conf
+    .checked_mul(conf_multiplier.into())
+    .and_then(|conf| i64::try_from(conf).ok())
+    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?;
+  let price = ema_price
+    .checked_sub(conf)
+    .ok_or_else(|| error!(DataCreditsErrors::ArithmeticError))?;
+  require_gt!(price, 0, DataCreditsErrors::PriceNotAvailable);
+  Ok(price as u64)
+}
+
+/// Loads the HNT price from the configured source, checking ownership and freshness
+/// of the `hnt_price_oracle` account where the source uses one.
+pub fn load_hnt_price(
+  price_source: &PriceSourceV0,
+  hnt_price_oracle: &AccountInfo,
+  unix_timestamp: i64,
+) -> Result<HntPriceV0> {
+  match *price_source {
+    PriceSourceV0::Pyth {
+      max_age_seconds,
+      conf_multiplier,
+    } => {
+      let message = load_hnt_price_oracle(hnt_price_oracle)?.price_message;
+      require_gte!(
+        message
+          .publish_time
+          .saturating_add(max_age(max_age_seconds)),
+        unix_timestamp,
+        DataCreditsErrors::PythPriceNotFound
+      );
+      require_gt!(message.ema_price, 0);
+
+      Ok(HntPriceV0 {
+        price: price_with_conf(
+          message.ema_price,
+          message.ema_conf,
+          conf_multiplier.unwrap_or(DEFAULT_CONF_MULTIPLIER),
+        )?,
+        exponent: message.exponent,
+      })
+    }
+    PriceSourceV0::PriceOracle { max_age_seconds } => {
+      require!(
+        hnt_price_oracle.owner == &price_oracle::ID,
+        DataCreditsErrors::InvalidPriceOracleOwner
+      );
+      let data = hnt_price_oracle.try_borrow_data()?;
+      let price_oracle = PriceOracleV0::try_deserialize(&mut data.as_ref())?;
+      let (price, last_calculated_timestamp) = match (
+        price_oracle.current_price,
+        price_oracle.last_calculated_timestamp,
+      ) {
+        (Some(price), Some(ts)) if price > 0 => (price, ts),
+        _ => return Err(error!(DataCreditsErrors::PriceNotAvailable)),
+      };
+      require_gte!(
+        last_calculated_timestamp.saturating_add(max_age(max_age_seconds)),
+        unix_timestamp,
+        DataCreditsErrors::PriceNotAvailable
+      );
+
+      Ok(HntPriceV0 {
+        price,
+        exponent: -i32::from(price_oracle.decimals),
+      })
+    }
+    PriceSourceV0::Fixed { price, exponent } => {
+      require!(
+        is_price_source_allowed(price_source),
+        DataCreditsErrors::InvalidPriceSource
+      );
+      require_gt!(price, 0, DataCreditsErrors::PriceNotAvailable);
+
+      Ok(HntPriceV0 { price, exponent })
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use price_oracle::OracleV0;
+
+  use super::*;
+
+  fn price_oracle_data(
+    current_price: Option<u64>,
+    last_calculated_timestamp: Option<i64>,
+  ) -> Vec<u8> {
+    let price_oracle = PriceOracleV0 {
+      authority: Pubkey::new_unique(),
+      num_oracles: 1,
+      decimals: 6,
+      oracles: vec![OracleV0 {
+        authority: Pubkey::new_unique(),
+        last_submitted_timestamp: last_calculated_timestamp,
+        last_submitted_price: current_price,
+      }],
+      current_price,
+      last_calculated_timestamp,
+      ..Default::default()
+    };
+    let mut data = Vec::new();
+    price_oracle.try_serialize(&mut data).unwrap();
+    data
+  }
+
+  fn load(source: &PriceSourceV0, owner: &Pubkey, data: &mut [u8], ts: i64) -> Result<HntPriceV0> {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let account_info = AccountInfo::new(&key, false, false, &mut lamports, data, owner, false, 0);
+    load_hnt_price(source, &account_info, ts)
+  }
+
+  #[test]
+  fn test_price_with_conf() {
+    assert_eq!(price_with_conf(1000, 10, 2).unwrap(), 980);
+    assert_eq!(price_with_conf(1000, 10, 0).unwrap(), 1000);
+    assert!(price_with_conf(1000, 500, 2).is_err());
+  }
+
+  #[test]
+  fn loads_price_oracle_source() {
+    let source = PriceSourceV0::PriceOracle {
+      max_age_seconds: Some(60),
+    };
+    let mut data = price_oracle_data(Some(5_000_000), Some(100));
+    assert_eq!(
+      load(&source, &price_oracle::ID, &mut data, 150).unwrap(),
+      HntPriceV0 {
+        price: 5_000_000,
+        exponent: -6
+      }
+    );
+    assert_eq!(
+      load(&source, &Pubkey::new_unique(), &mut data, 150),
+      Err(error!(DataCreditsErrors::InvalidPriceOracleOwner))
+    );
+
+    let mut data = price_oracle_data(None, None);
+    assert_eq!(
+      load(&source, &price_oracle::ID, &mut data, 150),
+      Err(error!(DataCreditsErrors::PriceNotAvailable))
+    );
+  }
+
+  #[test]
+  fn rejects_stale_price_oracle_source() {
+    let source = PriceSourceV0::PriceOracle {
+      max_age_seconds: Some(60),
+    };
+    let mut data = price_oracle_data(Some(5_000_000), Some(100));
+    assert_eq!(
+      load(&source, &price_oracle::ID, &mut data, 161),
+      Err(error!(DataCreditsErrors::PriceNotAvailable))
+    );
+    assert_eq!(max_age(Some(60)), 60);
+    assert_eq!(
+      max_age(None),
+      if TESTING {
+        6000000
+      } else {
+        i64::from(DEFAULT_MAX_AGE_SECONDS)
+      }
+    );
+  }
+
+  #[test]
+  fn fixed_source_is_cluster_gated() {
+    let source = PriceSourceV0::Fixed {
+      price: 5,
+      exponent: 0,
+    };
+    assert_eq!(
+      is_price_source_allowed(&source),
+      TESTING || cfg!(feature = "devnet")
+    );
+    assert!(is_price_source_allowed(&PriceSourceV0::default()));
+  }
+}
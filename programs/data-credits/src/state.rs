@@ -10,6 +10,39 @@ pub struct DataCreditsV0 {
   pub data_credits_bump: u8,
   pub account_payer: Pubkey,
   pub account_payer_bump: u8,
+  pub price_source: PriceSourceV0,
+  // Delay between requesting and executing a withdrawal from a delegated escrow, so
+  // in-flight burns can settle first
+  pub withdrawal_timelock_seconds: u32,
+}
+
+// Where mint_data_credits_v0 reads the HNT price from. The account is always the
+// `hnt_price_oracle` stored above, which is ignored for `Fixed`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum PriceSourceV0 {
+  Pyth {
+    // None uses the default freshness window
+    max_age_seconds: Option<u32>,
+    // How many times the ema confidence is removed from the ema price. None uses the default
+    conf_multiplier: Option<u8>,
+  },
+  PriceOracle {
+    max_age_seconds: Option<u32>,
+  },
+  // Only allowed on devnet and localnet. The HNT price is price * 10^exponent USD
+  Fixed {
+    price: u64,
+    exponent: i32,
+  },
+}
+
+impl Default for PriceSourceV0 {
+  fn default() -> Self {
+    PriceSourceV0::Pyth {
+      max_age_seconds: None,
+      conf_multiplier: None,
+    }
+  }
 }
 
 #[account]
@@ -20,4 +53,237 @@ pub struct DelegatedDataCreditsV0 {
   pub escrow_account: Pubkey,
   pub router_key: String,
   pub bump: u8,
+  // Spending caps on the sub dao's burn authority, None when uncapped
+  pub allowance: Option<DelegatedAllowanceV0>,
+  // Shares of the escrow. Delegators hold theirs in a `DelegatedDataCreditsDepositV0`, so that
+  // burns from the shared escrow are borne pro rata. Shares not held by any deposit, such as
+  // DC delegated through delegate_data_credits_v0, cannot be withdrawn.
+  pub total_shares: u128,
+  // The part of `total_shares` held by deposits
+  pub deposit_shares: u128,
+}
+
+impl DelegatedDataCreditsV0 {
+  // Issues shares for `amount` DC added to an escrow that held `escrow_amount` before.
+  // Offsetting both sides by one keeps an escrow that burns drained to zero usable.
+  pub fn issue_shares(&mut self, amount: u64, escrow_amount: u64) -> Option<u128> {
+    if self.total_shares == 0 {
+      // DC in the escrow before any shares were issued is not owned by any delegator
+      self.total_shares = escrow_amount.into();
+    }
+    let shares = u128::from(amount)
+      .checked_mul(self.total_shares.checked_add(1)?)?
+      .checked_div(u128::from(escrow_amount).checked_add(1)?)?;
+    self.total_shares = self.total_shares.checked_add(shares)?;
+    Some(shares)
+  }
+
+  pub fn credit_deposit(
+    &mut self,
+    deposit: &mut DelegatedDataCreditsDepositV0,
+    shares: u128,
+  ) -> Option<()> {
+    deposit.shares = deposit.shares.checked_add(shares)?;
+    self.deposit_shares = self.deposit_shares.checked_add(shares)?;
+    Some(())
+  }
+
+  pub fn shares_value(&self, shares: u128, escrow_amount: u64) -> Option<u64> {
+    u64::try_from(
+      shares
+        .checked_mul(u128::from(escrow_amount).checked_add(1)?)?
+        .checked_div(self.total_shares.checked_add(1)?)?,
+    )
+    .ok()
+  }
+
+  // Burns the shares backing `amount` DC from `deposit`, rounding against the delegator
+  pub fn redeem_shares(
+    &mut self,
+    deposit: &mut DelegatedDataCreditsDepositV0,
+    amount: u64,
+    escrow_amount: u64,
+  ) -> Option<()> {
+    let total_shares = self.total_shares.checked_add(1)?;
+    let escrow_amount = u128::from(escrow_amount).checked_add(1)?;
+    let shares = u128::from(amount)
+      .checked_mul(total_shares)?
+      .checked_add(escrow_amount.checked_sub(1)?)?
+      .checked_div(escrow_amount)?
+      .min(deposit.shares);
+    deposit.shares = deposit.shares.checked_sub(shares)?;
+    self.deposit_shares = self.deposit_shares.checked_sub(shares)?;
+    self.total_shares = self.total_shares.checked_sub(shares)?;
+    Some(())
+  }
+}
+
+// DC a delegator put into a shared delegated escrow with delegate_data_credits_v1
+#[account]
+#[derive(Default)]
+pub struct DelegatedDataCreditsDepositV0 {
+  pub delegated_data_credits: Pubkey,
+  pub owner: Pubkey,
+  // Can request withdrawals of this deposit, the owner unless changed
+  pub refund_authority: Pubkey,
+  pub shares: u128,
+  pub pending_withdrawal: Option<PendingWithdrawalV0>,
+  pub bump_seed: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq)]
+pub struct PendingWithdrawalV0 {
+  pub amount: u64,
+  // DC token account the withdrawal is sent to
+  pub destination: Pubkey,
+  pub unlock_ts: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq)]
+pub struct DelegatedAllowanceV0 {
+  // Can pause burns and change the caps
+  pub owner: Pubkey,
+  pub paused: bool,
+  // 0 means no cap
+  pub max_burn_per_epoch: u64,
+  // 0 means no cap
+  pub max_burn_lifetime: u64,
+  // Epoch that `burned_in_epoch` and `remaining_allowance` were last updated in
+  pub epoch: u64,
+  pub burned_in_epoch: u64,
+  pub total_burned: u64,
+  // Amount that can still be burned in `epoch`, u64::MAX when uncapped
+  pub remaining_allowance: u64,
+}
+
+impl DelegatedAllowanceV0 {
+  pub fn remaining(&self, epoch: u64) -> u64 {
+    let burned_in_epoch = if epoch == self.epoch {
+      self.burned_in_epoch
+    } else {
+      0
+    };
+    let epoch_remaining = match self.max_burn_per_epoch {
+      0 => u64::MAX,
+      max => max.saturating_sub(burned_in_epoch),
+    };
+    let lifetime_remaining = match self.max_burn_lifetime {
+      0 => u64::MAX,
+      max => max.saturating_sub(self.total_burned),
+    };
+
+    std::cmp::min(epoch_remaining, lifetime_remaining)
+  }
+
+  // Moves the allowance to `epoch`, resetting the epoch counter when it has rolled over
+  pub fn refresh(&mut self, epoch: u64) {
+    if epoch != self.epoch {
+      self.epoch = epoch;
+      self.burned_in_epoch = 0;
+    }
+    self.remaining_allowance = self.remaining(epoch);
+  }
+
+  // Returns false without recording anything if the burn is not allowed
+  pub fn try_burn(&mut self, amount: u64, epoch: u64) -> bool {
+    if self.paused || amount > self.remaining(epoch) {
+      return false;
+    }
+
+    self.refresh(epoch);
+    self.burned_in_epoch = self.burned_in_epoch.saturating_add(amount);
+    self.total_burned = self.total_burned.saturating_add(amount);
+    self.remaining_allowance = self.remaining(epoch);
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_uncapped_allowance() {
+    let mut allowance = DelegatedAllowanceV0::default();
+    assert_eq!(allowance.remaining(1), u64::MAX);
+    assert!(allowance.try_burn(1_000_000, 1));
+    assert_eq!(allowance.total_burned, 1_000_000);
+    assert_eq!(allowance.remaining_allowance, u64::MAX);
+  }
+
+  #[test]
+  fn test_epoch_cap_resets() {
+    let mut allowance = DelegatedAllowanceV0 {
+      max_burn_per_epoch: 100,
+      ..Default::default()
+    };
+    assert!(allowance.try_burn(60, 1));
+    assert!(!allowance.try_burn(41, 1));
+    assert!(allowance.try_burn(40, 1));
+    assert_eq!(allowance.remaining_allowance, 0);
+
+    assert_eq!(allowance.remaining(2), 100);
+    assert!(allowance.try_burn(100, 2));
+    assert_eq!(allowance.burned_in_epoch, 100);
+    assert_eq!(allowance.total_burned, 200);
+  }
+
+  #[test]
+  fn test_lifetime_cap() {
+    let mut allowance = DelegatedAllowanceV0 {
+      max_burn_per_epoch: 100,
+      max_burn_lifetime: 150,
+      ..Default::default()
+    };
+    assert!(allowance.try_burn(100, 1));
+    assert_eq!(allowance.remaining(2), 50);
+    assert!(!allowance.try_burn(51, 2));
+    assert!(allowance.try_burn(50, 2));
+    assert_eq!(allowance.remaining(3), 0);
+  }
+
+  #[test]
+  fn test_shares_are_pro_rata() {
+    let mut delegated = DelegatedDataCreditsV0::default();
+    // 100 DC delegated before shares existed, owned by nobody
+    let mut alice = DelegatedDataCreditsDepositV0::default();
+    let alice_shares = delegated.issue_shares(100, 100).unwrap();
+    delegated.credit_deposit(&mut alice, alice_shares).unwrap();
+    assert_eq!(alice.shares, 100);
+    let mut escrow = 200;
+    let bob_shares = delegated.issue_shares(200, escrow).unwrap();
+    escrow += 200;
+    assert_eq!(delegated.total_shares, 400);
+
+    // Burning half of the escrow halves every share
+    escrow -= 200;
+    assert_eq!(delegated.shares_value(alice.shares, escrow), Some(50));
+    assert_eq!(delegated.shares_value(bob_shares, escrow), Some(100));
+
+    delegated.redeem_shares(&mut alice, 50, escrow).unwrap();
+    escrow -= 50;
+    assert_eq!(alice.shares, 0);
+    assert_eq!(delegated.deposit_shares, 0);
+    assert_eq!(delegated.shares_value(bob_shares, escrow), Some(100));
+  }
+
+  #[test]
+  fn test_shares_after_drained_escrow() {
+    let mut delegated = DelegatedDataCreditsV0::default();
+    let alice_shares = delegated.issue_shares(100, 0).unwrap();
+    // Burns drain the escrow, then bob delegates
+    let bob_shares = delegated.issue_shares(100, 0).unwrap();
+    assert_eq!(delegated.shares_value(alice_shares, 100), Some(0));
+    assert_eq!(delegated.shares_value(bob_shares, 100), Some(100));
+  }
+
+  #[test]
+  fn test_paused() {
+    let mut allowance = DelegatedAllowanceV0 {
+      paused: true,
+      ..Default::default()
+    };
+    assert!(!allowance.try_burn(1, 1));
+    assert_eq!(allowance.total_burned, 0);
+  }
 }
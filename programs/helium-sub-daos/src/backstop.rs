@@ -91,7 +91,11 @@ pub struct BackstopOutput {
 /// Convert a DC count to HNT lamports at the confidence-adjusted Pyth price, exactly
 /// as `mint_data_credits_v0` converts DC to the HNT it burns:
 /// `hnt = dc * decimals_factor / price`.
-fn scale_dc_to_hnt(dc_amount: u128, decimals_factor: u128, hnt_price_with_conf: u64) -> u64 {
+pub(crate) fn scale_dc_to_hnt(
+  dc_amount: u128,
+  decimals_factor: u128,
+  hnt_price_with_conf: u64,
+) -> u64 {
   dc_amount
     .saturating_mul(decimals_factor)
     .checked_div(hnt_price_with_conf as u128)
@@ -181,6 +185,22 @@ pub fn compute_backstop(input: &BackstopInput) -> BackstopOutput {
   }
 }
 
+/// The backstop when the price or the Mobile signals are unavailable for the epoch: the
+/// existing HIP 20 re-emit only, with no top-up and no earnings cap.
+pub fn dormant_backstop(
+  emission: u64,
+  smoothed_hnt_burned: u64,
+  net_emissions_cap: u64,
+) -> BackstopOutput {
+  let existing_re_emit = std::cmp::min(smoothed_hnt_burned, net_emissions_cap);
+  BackstopOutput {
+    existing_re_emit,
+    top_up: 0,
+    total_rewards: emission.checked_add(existing_re_emit).unwrap(),
+    deployer_cap_hnt: 0,
+  }
+}
+
 /// The HIP 149 earnings-cap overflow: the portion of the Mobile data bucket above the
 /// deployer ceiling, redirected from the rewards escrow to the shared delegator pool.
 ///
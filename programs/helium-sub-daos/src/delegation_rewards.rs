@@ -0,0 +1,461 @@
+//! Per-epoch delegation reward math shared by the claim instructions.
+//!
+//! A position earns `delegation_rewards_issued * vehnt_at_epoch / vehnt_at_epoch_start`
+//! for every epoch it was delegated at the epoch start. The share of that paid out depends
+//! on how many of the dao's recent proposals the position voted on, as configured by the
+//! epoch's `ParticipationPolicyV0`. The rest is burned.
+
+use std::collections::HashSet;
+
+use anchor_lang::prelude::*;
+use voter_stake_registry::state::{PositionV0, RecentProposal, VotingMintConfigV0};
+
+use crate::{
+  error::ErrorCode,
+  state::{DaoEpochInfoV0, DelegatedPositionV0},
+  TESTING,
+};
+
+// One epoch per bit of the delegated position's claimed epochs bitmap
+pub const MAX_CLAIM_RANGE_EPOCHS: usize = 128;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct EpochClaimV0 {
+  pub epoch: u64,
+  // Paid to the delegator
+  pub rewards: u64,
+  // Burned for falling short of the participation policy
+  pub ineligible_rewards: u64,
+  // Percent of the epoch's rewards earned through governance participation
+  pub reward_percent: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PendingRewardsV0 {
+  // Paid to the delegator on claim
+  pub claimable_rewards: u64,
+  // Burned on claim for falling short of the participation policy
+  pub ineligible_rewards: u64,
+  // Unclaimed epochs in the requested range
+  pub epochs: Vec<EpochClaimV0>,
+}
+
+pub fn delegated_vehnt_at_epoch(
+  position: &PositionV0,
+  voting_mint_config: &VotingMintConfigV0,
+  delegated_position: &DelegatedPositionV0,
+  dao_epoch_info: &DaoEpochInfoV0,
+) -> Result<u128> {
+  let epoch_start_ts = dao_epoch_info.start_ts();
+  if delegated_position.expiration_ts > epoch_start_ts {
+    voting_power_before_compounding(
+      position,
+      voting_mint_config,
+      delegated_position,
+      epoch_start_ts,
+    )
+  } else {
+    Ok(0)
+  }
+}
+
+// Voting power at `ts` of the position without any rewards compounded into it after `ts`
+pub fn voting_power_before_compounding(
+  position: &PositionV0,
+  voting_mint_config: &VotingMintConfigV0,
+  delegated_position: &DelegatedPositionV0,
+  ts: i64,
+) -> Result<u128> {
+  let compounded = delegated_position.compounded_after(ts);
+  if compounded == 0 {
+    return position.voting_power(voting_mint_config, ts);
+  }
+
+  let mut position = position.clone();
+  position.amount_deposited_native = position
+    .amount_deposited_native
+    .checked_sub(compounded)
+    .ok_or_else(|| error!(ErrorCode::ArithmeticError))?;
+  position.voting_power(voting_mint_config, ts)
+}
+
+// rewards = delegation_rewards_issued * delegated_vehnt_at_epoch / vehnt_at_epoch_start
+pub fn delegation_rewards(delegated_vehnt_at_epoch: u128, dao_epoch_info: &DaoEpochInfoV0) -> u64 {
+  u64::try_from(
+    delegated_vehnt_at_epoch
+      .checked_mul(dao_epoch_info.delegation_rewards_issued as u128)
+      .unwrap()
+      .checked_div(dao_epoch_info.vehnt_at_epoch_start as u128)
+      .unwrap_or(0),
+  )
+  .unwrap()
+}
+
+// Percent of an epoch's rewards the position earns under the epoch's participation policy
+pub fn participation_reward_percent(
+  position_recent_proposals: &[RecentProposal],
+  proxied_proposals: &[Pubkey],
+  dao_epoch_info: &DaoEpochInfoV0,
+) -> u8 {
+  let policy = dao_epoch_info
+    .participation_policy
+    .clone()
+    .unwrap_or_default();
+  let window = &dao_epoch_info.recent_proposals[..policy.window as usize];
+  // Until the dao has a full window of proposals every position is eligible
+  if window.iter().any(|p| p.proposal == Pubkey::default()) {
+    return 100;
+  }
+
+  let first_ts = window.last().unwrap().ts;
+  let last_ts = window.first().unwrap().ts;
+  let proposal_set = position_recent_proposals
+    .iter()
+    .filter(|p| p.ts <= last_ts && p.ts >= first_ts)
+    .filter(|p| policy.count_proxy_votes || !proxied_proposals.contains(&p.proposal))
+    .map(|rp| rp.proposal)
+    .collect::<HashSet<_>>();
+
+  let votes = window
+    .iter()
+    .filter(|&proposal| {
+      proposal_set.contains(&proposal.proposal)
+        || proposal.is_in_progress(dao_epoch_info.start_ts())
+    })
+    .count();
+
+  policy.reward_percent(u8::try_from(votes).unwrap())
+}
+
+pub fn calculate_epoch_claim(
+  position: &PositionV0,
+  voting_mint_config: &VotingMintConfigV0,
+  delegated_position: &DelegatedPositionV0,
+  dao_epoch_info: &DaoEpochInfoV0,
+) -> Result<EpochClaimV0> {
+  let delegated_vehnt = delegated_vehnt_at_epoch(
+    position,
+    voting_mint_config,
+    delegated_position,
+    dao_epoch_info,
+  )?;
+
+  msg!("Staked {} veHNT at start of epoch with {} total veHNT delegated to dao and {} total rewards to dao",
+    delegated_vehnt,
+    dao_epoch_info.vehnt_at_epoch_start,
+    dao_epoch_info.delegation_rewards_issued
+  );
+
+  let total_rewards = delegation_rewards(delegated_vehnt, dao_epoch_info);
+  // Proxy votes are flagged on the position, older ones were tracked by track_vote_v0
+  let proxied_proposals = position
+    .proxied_proposals
+    .iter()
+    .copied()
+    .chain(
+      delegated_position
+        .proxy_voted_proposals
+        .iter()
+        .map(|p| p.proposal),
+    )
+    .collect::<Vec<_>>();
+  let reward_percent = participation_reward_percent(
+    &position.recent_proposals,
+    &proxied_proposals,
+    dao_epoch_info,
+  );
+  let rewards = u64::try_from(total_rewards as u128 * reward_percent as u128 / 100).unwrap();
+
+  Ok(EpochClaimV0 {
+    epoch: dao_epoch_info.epoch,
+    rewards,
+    ineligible_rewards: total_rewards - rewards,
+    reward_percent,
+  })
+}
+
+// Sums the claims of every unclaimed epoch in `dao_epoch_infos`
+pub fn calculate_pending_rewards<'a>(
+  position: &PositionV0,
+  voting_mint_config: &VotingMintConfigV0,
+  delegated_position: &DelegatedPositionV0,
+  dao_epoch_infos: impl IntoIterator<Item = &'a DaoEpochInfoV0>,
+) -> Result<PendingRewardsV0> {
+  let mut pending = PendingRewardsV0::default();
+  for dao_epoch_info in dao_epoch_infos {
+    if !TESTING && delegated_position.is_claimed(dao_epoch_info.epoch)? {
+      continue;
+    }
+
+    let claim = calculate_epoch_claim(
+      position,
+      voting_mint_config,
+      delegated_position,
+      dao_epoch_info,
+    )?;
+    pending.claimable_rewards = pending
+      .claimable_rewards
+      .checked_add(claim.rewards)
+      .ok_or_else(|| error!(ErrorCode::ArithmeticError))?;
+    pending.ineligible_rewards = pending
+      .ineligible_rewards
+      .checked_add(claim.ineligible_rewards)
+      .ok_or_else(|| error!(ErrorCode::ArithmeticError))?;
+    pending.epochs.push(claim);
+  }
+
+  Ok(pending)
+}
+
+// Loads a contiguous, ascending range of closed `DaoEpochInfoV0`s for `dao`
+pub fn load_claim_range<'info>(
+  accounts: &'info [AccountInfo<'info>],
+  dao: &Pubkey,
+  curr_epoch: u64,
+) -> Result<Vec<Account<'info, DaoEpochInfoV0>>> {
+  require!(
+    !accounts.is_empty() && accounts.len() <= MAX_CLAIM_RANGE_EPOCHS,
+    ErrorCode::InvalidDaoEpochInfo
+  );
+
+  let mut dao_epoch_infos: Vec<Account<'info, DaoEpochInfoV0>> = Vec::with_capacity(accounts.len());
+  for account in accounts {
+    let dao_epoch_info = Account::<DaoEpochInfoV0>::try_from(account)?;
+    require_keys_eq!(dao_epoch_info.dao, *dao, ErrorCode::InvalidDaoEpochInfo);
+    if let Some(prev) = dao_epoch_infos.last() {
+      require_eq!(
+        dao_epoch_info.epoch,
+        prev.epoch + 1,
+        ErrorCode::InvalidDaoEpochInfo
+      );
+    }
+    // Ensure that a pre HIP-138 claim can't accidentally be done.
+    require_gt!(
+      dao_epoch_info.delegation_rewards_issued,
+      0,
+      ErrorCode::InvalidDaoEpochInfo
+    );
+    require!(
+      dao_epoch_info.done_issuing_rewards,
+      ErrorCode::EpochNotClosed
+    );
+    if !TESTING {
+      require_gt!(curr_epoch, dao_epoch_info.epoch, ErrorCode::EpochNotOver);
+    }
+    dao_epoch_infos.push(dao_epoch_info);
+  }
+
+  Ok(dao_epoch_infos)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::state::ParticipationPolicyV0;
+
+  fn proposal(seed: u8, ts: i64) -> RecentProposal {
+    RecentProposal {
+      proposal: Pubkey::new_from_array([seed; 32]),
+      ts,
+    }
+  }
+
+  fn epoch_info(recent_proposals: [RecentProposal; 4]) -> DaoEpochInfoV0 {
+    DaoEpochInfoV0 {
+      // Far enough past the proposals that none are in progress
+      epoch: 100,
+      recent_proposals,
+      delegation_rewards_issued: 1_000,
+      vehnt_at_epoch_start: 4_000,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_delegation_rewards() {
+    let info = epoch_info(Default::default());
+    assert_eq!(delegation_rewards(1_000, &info), 250);
+    assert_eq!(
+      delegation_rewards(
+        1_000,
+        &DaoEpochInfoV0 {
+          vehnt_at_epoch_start: 0,
+          ..info
+        }
+      ),
+      0
+    );
+  }
+
+  #[test]
+  fn test_participation_reward_percent() {
+    let proposals = [
+      proposal(4, 400),
+      proposal(3, 300),
+      proposal(2, 200),
+      proposal(1, 100),
+    ];
+    let info = epoch_info(proposals.clone());
+
+    assert_eq!(participation_reward_percent(&[], &[], &info), 0);
+    assert_eq!(
+      participation_reward_percent(&[proposals[0].clone()], &[], &info),
+      0
+    );
+    assert_eq!(
+      participation_reward_percent(&[proposals[0].clone(), proposals[3].clone()], &[], &info),
+      100
+    );
+
+    // Fewer than four proposals are always eligible
+    let partial_info = epoch_info([
+      proposal(4, 400),
+      proposal(3, 300),
+      proposal(2, 200),
+      RecentProposal::default(),
+    ]);
+    assert_eq!(participation_reward_percent(&[], &[], &partial_info), 100);
+  }
+
+  #[test]
+  fn test_participation_policy() {
+    let proposals = [
+      proposal(4, 400),
+      proposal(3, 300),
+      proposal(2, 200),
+      proposal(1, 100),
+    ];
+    let with_policy = |policy: ParticipationPolicyV0| DaoEpochInfoV0 {
+      participation_policy: Some(policy),
+      ..epoch_info(proposals.clone())
+    };
+
+    // Only the last two proposals are considered
+    let info = with_policy(ParticipationPolicyV0 {
+      window: 2,
+      required_votes: 1,
+      ..Default::default()
+    });
+    assert_eq!(
+      participation_reward_percent(&[proposals[3].clone()], &[], &info),
+      0
+    );
+    assert_eq!(
+      participation_reward_percent(&[proposals[1].clone()], &[], &info),
+      100
+    );
+
+    // Partial rewards below the required votes
+    let info = with_policy(ParticipationPolicyV0 {
+      window: 4,
+      required_votes: 3,
+      partial_reward_percents: [0, 25, 50, 0],
+      ..Default::default()
+    });
+    assert_eq!(participation_reward_percent(&[], &[], &info), 0);
+    assert_eq!(
+      participation_reward_percent(&[proposals[0].clone()], &[], &info),
+      25
+    );
+    assert_eq!(
+      participation_reward_percent(&proposals[..2], &[], &info),
+      50
+    );
+    assert_eq!(
+      participation_reward_percent(&proposals[..3], &[], &info),
+      100
+    );
+
+    // Proxy votes only count when the policy allows it
+    let proxied = [proposals[0].proposal];
+    let info = with_policy(ParticipationPolicyV0 {
+      required_votes: 2,
+      count_proxy_votes: false,
+      ..Default::default()
+    });
+    assert_eq!(
+      participation_reward_percent(&proposals[..2], &proxied, &info),
+      0
+    );
+    let info = with_policy(ParticipationPolicyV0::default());
+    assert_eq!(
+      participation_reward_percent(&proposals[..2], &proxied, &info),
+      100
+    );
+  }
+
+  #[test]
+  fn test_proxy_votes_reduce_rewards() {
+    let proposals = [
+      proposal(4, 400),
+      proposal(3, 300),
+      proposal(2, 200),
+      proposal(1, 100),
+    ];
+    let info = DaoEpochInfoV0 {
+      participation_policy: Some(ParticipationPolicyV0 {
+        window: 2,
+        required_votes: 2,
+        count_proxy_votes: false,
+        partial_reward_percents: [0, 50, 0, 0],
+      }),
+      ..epoch_info(proposals.clone())
+    };
+    let voting_mint_config = VotingMintConfigV0 {
+      // 1 veHNT per HNT
+      baseline_vote_weight_scaled_factor: 1_000_000_000,
+      ..Default::default()
+    };
+    let delegated_position = DelegatedPositionV0 {
+      expiration_ts: i64::MAX,
+      ..Default::default()
+    };
+    let mut position = PositionV0 {
+      amount_deposited_native: 1_000,
+      ..Default::default()
+    };
+
+    // One vote cast by the owner, one by a proxy
+    position.add_recent_proposal(proposals[0].proposal, proposals[0].ts, false);
+    position.add_recent_proposal(proposals[1].proposal, proposals[1].ts, true);
+    let claim =
+      calculate_epoch_claim(&position, &voting_mint_config, &delegated_position, &info).unwrap();
+    assert_eq!(claim.reward_percent, 50);
+    assert_eq!(claim.rewards, 125);
+    assert_eq!(claim.ineligible_rewards, 125);
+
+    // The owner voting directly replaces the proxy vote
+    position.add_recent_proposal(proposals[1].proposal, proposals[1].ts, false);
+    let claim =
+      calculate_epoch_claim(&position, &voting_mint_config, &delegated_position, &info).unwrap();
+    assert_eq!(claim.reward_percent, 100);
+    assert_eq!(claim.rewards, 250);
+  }
+
+  #[test]
+  fn test_validate_participation_policy() {
+    assert!(ParticipationPolicyV0::default().validate().is_ok());
+    for policy in [
+      ParticipationPolicyV0 {
+        window: 0,
+        required_votes: 0,
+        ..Default::default()
+      },
+      ParticipationPolicyV0 {
+        window: 5,
+        ..Default::default()
+      },
+      ParticipationPolicyV0 {
+        window: 2,
+        required_votes: 3,
+        ..Default::default()
+      },
+      ParticipationPolicyV0 {
+        partial_reward_percents: [0, 101, 0, 0],
+        ..Default::default()
+      },
+    ] {
+      assert!(policy.validate().is_err());
+    }
+  }
+}
@@ -85,4 +85,24 @@ pub enum ErrorCode {
 
   #[msg("The Mobile sub-DAO epoch-info accounts required by the backstop were not supplied")]
   MobileEpochInfoMissing,
+
+  #[msg("Dao epoch infos must be a contiguous range of closed epochs for this dao")]
+  InvalidDaoEpochInfo,
+
+  #[msg("Participation policy window must be 1-4 proposals with at most window required votes")]
+  InvalidParticipationPolicy,
+
+  #[msg("Compounding rewards requires the compound escrow, position vault and sub dao epoch info accounts")]
+  CompoundingAccountsMissing,
+
+  #[msg("Cannot split or merge a delegated position that still has the genesis multiplier")]
+  GenesisPositionChange,
+
+  #[msg("Delegated positions must share the sub dao, lockup and expiration, and have claimed all past epochs, to be merged")]
+  IncompatibleMerge,
+
+  #[msg(
+    "Vesting positions cannot be delegated, their voting power does not decay at a constant rate"
+  )]
+  VestingPositionDelegation,
 }
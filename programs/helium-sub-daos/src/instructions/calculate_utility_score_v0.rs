@@ -7,7 +7,7 @@ use shared_utils::{precise_number::PreciseNumber, try_from};
 use voter_stake_registry::state::Registrar;
 
 use crate::{
-  backstop::{compute_backstop, BackstopInput, MOBILE_SUB_DAO},
+  backstop::{compute_backstop, dormant_backstop, BackstopInput, MOBILE_SUB_DAO},
   current_epoch,
   error::ErrorCode,
   state::*,
@@ -104,6 +104,30 @@ pub struct CalculateUtilityScoreV0<'info> {
 }
 
 const SMOOTHING_FACTOR: u64 = 7;
+pub const INITIAL_SMOOTHED_HNT_BURNED: u64 = 300;
+
+/// HIP 20 moving average of the HNT burned per epoch, over `SMOOTHING_FACTOR` epochs.
+pub fn next_smoothed_hnt_burned(prev_smoothed_hnt_burned: u64, total_hnt_burned: u64) -> u64 {
+  (SMOOTHING_FACTOR
+    .checked_sub(1)
+    .unwrap()
+    .checked_mul(prev_smoothed_hnt_burned)
+    .unwrap()
+    .checked_div(SMOOTHING_FACTOR)
+    .unwrap())
+  .checked_add(total_hnt_burned.checked_div(SMOOTHING_FACTOR).unwrap())
+  .unwrap()
+}
+
+/// Utility score of a sub dao from the veHNT delegated to it at the start of the epoch.
+pub fn utility_score_from_vehnt(vehnt_at_epoch_start: u64) -> u128 {
+  PreciseNumber::new(vehnt_at_epoch_start.into())
+    .unwrap()
+    .checked_mul(&PreciseNumber::new(10000_u128).unwrap()) // Multiply by 10^4 to convert from 8 to 12 decimals
+    .unwrap()
+    .to_imprecise()
+    .unwrap()
+}
 
 pub fn handler<'info>(
   ctx: Context<'_, '_, 'info, 'info, CalculateUtilityScoreV0<'info>>,
@@ -112,6 +136,7 @@ pub fn handler<'info>(
   let end_of_epoch_ts = i64::try_from(args.epoch + 1).unwrap() * EPOCH_LENGTH;
   let curr_epoch = current_epoch(Clock::get()?.unix_timestamp);
   ctx.accounts.dao_epoch_info.recent_proposals = ctx.accounts.dao.recent_proposals.clone();
+  ctx.accounts.dao_epoch_info.participation_policy = ctx.accounts.dao.participation_policy.clone();
 
   // Set total rewards, accounting for net emmissions by counting
   // burned hnt since last supply setting.
@@ -152,24 +177,17 @@ pub fn handler<'info>(
 
   // Set smoothed hnt burned to 300 if it's not already set
   if ctx.accounts.dao_epoch_info.smoothed_hnt_burned == 0 {
-    ctx.accounts.dao_epoch_info.smoothed_hnt_burned = 300;
+    ctx.accounts.dao_epoch_info.smoothed_hnt_burned = INITIAL_SMOOTHED_HNT_BURNED;
   }
   if prev_smoothed_hnt_burned == 0 {
-    prev_smoothed_hnt_burned = 300;
+    prev_smoothed_hnt_burned = INITIAL_SMOOTHED_HNT_BURNED;
   }
 
   let total_hnt_burned = prev_supply
     .saturating_sub(curr_supply)
     .saturating_sub(not_emitted);
-  ctx.accounts.dao_epoch_info.smoothed_hnt_burned = (SMOOTHING_FACTOR
-    .checked_sub(1)
-    .unwrap()
-    .checked_mul(prev_smoothed_hnt_burned)
-    .unwrap()
-    .checked_div(SMOOTHING_FACTOR)
-    .unwrap())
-  .checked_add(total_hnt_burned.checked_div(SMOOTHING_FACTOR).unwrap())
-  .unwrap();
+  ctx.accounts.dao_epoch_info.smoothed_hnt_burned =
+    next_smoothed_hnt_burned(prev_smoothed_hnt_burned, total_hnt_burned);
 
   if ctx.accounts.dao_epoch_info.not_emitted == 0 {
     ctx.accounts.dao_epoch_info.not_emitted = not_emitted;
@@ -234,15 +252,7 @@ pub fn handler<'info>(
       hnt_price_floor,
       hnt_price_cap,
     }),
-    _ => {
-      let existing_re_emit = std::cmp::min(smoothed, net_emissions_cap);
-      crate::backstop::BackstopOutput {
-        existing_re_emit,
-        top_up: 0,
-        total_rewards: emission_ts.checked_add(existing_re_emit).unwrap(),
-        deployer_cap_hnt: 0,
-      }
-    }
+    _ => dormant_backstop(emission_ts, smoothed, net_emissions_cap),
   };
 
   ctx.accounts.dao_epoch_info.total_rewards = backstop.total_rewards;
@@ -328,12 +338,7 @@ pub fn handler<'info>(
   // 2. We want 12 decimals in the final utility score
   // 3. Therefore multiply by 10^4 (since 10^12/10^8 = 10^4)
   // This is equivalent to dividing by 10^8 and multiplying by 10^12, but no lost precision
-  let vehnt_staked = PreciseNumber::new(epoch_info.vehnt_at_epoch_start.into())
-    .unwrap()
-    .checked_mul(&PreciseNumber::new(10000_u128).unwrap()) // Multiply by 10^4 to convert from 8 to 12 decimals
-    .unwrap();
-
-  let utility_score = vehnt_staked.to_imprecise().unwrap();
+  let utility_score = utility_score_from_vehnt(epoch_info.vehnt_at_epoch_start);
 
   // Store utility scores for this epoch
   epoch_info.utility_score = Some(utility_score);
@@ -434,15 +439,31 @@ fn read_hnt_price<'info>(
   // still caps total re-emission at recent destruction. The cap uses the EMA point price
   // (no confidence adjustment) so it binds at exactly 3.0x the payer rate rather than
   // biasing the ceiling in either direction by the confidence width (I-01).
-  let conf_2 = i64::try_from(message.ema_conf.checked_mul(2)?).ok()?;
-  let price_floor = message.ema_price.checked_sub(conf_2)?;
-  let price_cap = message.ema_price;
+  backstop_prices(
+    ctx.accounts.hnt_mint.decimals,
+    message.exponent,
+    message.ema_price,
+    message.ema_conf,
+  )
+}
+
+/// The price math of `read_hnt_price` on an already trusted Pyth EMA price and confidence.
+/// Returns `(decimals_factor, price_floor, price_cap)`, or `None` for a non-positive price.
+pub fn backstop_prices(
+  hnt_decimals: u8,
+  exponent: i32,
+  ema_price: i64,
+  ema_conf: u64,
+) -> Option<(u128, u64, u64)> {
+  let conf_2 = i64::try_from(ema_conf.checked_mul(2)?).ok()?;
+  let price_floor = ema_price.checked_sub(conf_2)?;
+  let price_cap = ema_price;
   if price_floor <= 0 || price_cap <= 0 {
     return None;
   }
 
   // decimals_factor = 10^(hnt_decimals - expo - 5); converts a DC count to HNT lamports.
-  let exponent = i32::from(ctx.accounts.hnt_mint.decimals) - message.exponent - 5;
+  let exponent = i32::from(hnt_decimals) - exponent - 5;
   let decimals_factor = 10_u128.checked_pow(u32::try_from(exponent).ok()?)?;
 
   Some((
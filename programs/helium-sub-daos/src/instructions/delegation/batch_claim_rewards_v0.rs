@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{Mint, Token, TokenAccount},
+};
+use circuit_breaker::{AccountWindowedCircuitBreakerV0, CircuitBreaker};
+use voter_stake_registry::{
+  cpi::{accounts::ClearRecentProposalsV0, clear_recent_proposals_v0},
+  state::{PositionV0, Registrar},
+  ClearRecentProposalsArgsV0, VoterStakeRegistry,
+};
+
+use super::{
+  claim_rewards_v1::{pay_out_rewards, TUKTUK_SIGNER_KEY},
+  close_delegation_v0::get_closing_epoch_bytes,
+  PayoutAccounts,
+};
+use crate::{
+  calculate_pending_rewards, current_epoch, dao_seeds, get_sub_dao_epoch_info_seed,
+  load_claim_range, state::*,
+};
+
+// Claims a contiguous range of epochs. Remaining accounts are the `DaoEpochInfoV0`s of the
+// epochs to claim, in ascending order.
+#[derive(Accounts)]
+pub struct BatchClaimRewardsV0<'info> {
+  #[account(
+    mut,
+    seeds = [b"position".as_ref(), mint.key().as_ref()],
+    seeds::program = vsr_program.key(),
+    bump = position.bump_seed,
+    has_one = mint,
+    has_one = registrar,
+  )]
+  pub position: Box<Account<'info, PositionV0>>,
+  #[account(mut)]
+  pub mint: Box<Account<'info, Mint>>,
+  #[account(
+    token::mint = mint,
+    token::authority = position_authority,
+    constraint = position_token_account.amount > 0
+  )]
+  pub position_token_account: Box<Account<'info, TokenAccount>>,
+  /// CHECK: By constraint
+  #[account(
+    constraint = (position_authority.is_signer && position_authority.key() == payer.key()) || payer.key() == TUKTUK_SIGNER_KEY
+  )]
+  pub position_authority: AccountInfo<'info>,
+  #[account(mut)]
+  pub registrar: Box<Account<'info, Registrar>>,
+  #[account(
+    mut,
+    has_one = registrar,
+    has_one = hnt_mint,
+    has_one = delegator_pool,
+  )]
+  pub dao: Box<Account<'info, DaoV0>>,
+
+  #[account(
+    mut,
+    has_one = dao,
+  )]
+  pub sub_dao: Account<'info, SubDaoV0>,
+  #[account(
+    mut,
+    has_one = sub_dao,
+    seeds = ["delegated_position".as_bytes(), position.key().as_ref()],
+    bump = delegated_position.bump_seed,
+  )]
+  pub delegated_position: Account<'info, DelegatedPositionV0>,
+
+  #[account(mut)]
+  pub hnt_mint: Box<Account<'info, Mint>>,
+
+  #[account(mut)]
+  pub delegator_pool: Box<Account<'info, TokenAccount>>,
+  #[account(
+    init_if_needed,
+    payer = payer,
+    associated_token::mint = hnt_mint,
+    associated_token::authority = position_authority,
+  )]
+  pub delegator_ata: Box<Account<'info, TokenAccount>>,
+
+  /// CHECK: checked via cpi
+  #[account(
+    mut,
+    seeds = ["account_windowed_breaker".as_bytes(), delegator_pool.key().as_ref()],
+    seeds::program = circuit_breaker_program.key(),
+    bump = delegator_pool_circuit_breaker.bump_seed,
+  )]
+  pub delegator_pool_circuit_breaker: Box<Account<'info, AccountWindowedCircuitBreakerV0>>,
+
+  pub vsr_program: Program<'info, VoterStakeRegistry>,
+  pub system_program: Program<'info, System>,
+  pub circuit_breaker_program: Program<'info, CircuitBreaker>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_program: Program<'info, Token>,
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  // Same as claim_rewards_v1, only needed when the delegated position compounds rewards
+  #[account(
+    init_if_needed,
+    payer = payer,
+    associated_token::mint = hnt_mint,
+    associated_token::authority = dao,
+  )]
+  pub compound_escrow: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    mut,
+    associated_token::mint = hnt_mint,
+    associated_token::authority = position,
+  )]
+  pub position_vault: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    init_if_needed,
+    payer = payer,
+    space = SubDaoEpochInfoV0::SIZE,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_sub_dao_epoch_info_seed(&registrar)],
+    bump,
+  )]
+  pub compound_sub_dao_epoch_info: Option<Box<Account<'info, SubDaoEpochInfoV0>>>,
+  #[account(
+    mut,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_closing_epoch_bytes(&position, &delegated_position)],
+    bump = closing_time_sub_dao_epoch_info.bump_seed,
+  )]
+  pub closing_time_sub_dao_epoch_info: Option<Box<Account<'info, SubDaoEpochInfoV0>>>,
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, 'info, 'info, BatchClaimRewardsV0<'info>>,
+) -> Result<()> {
+  let position = &ctx.accounts.position;
+  let registrar = &ctx.accounts.registrar;
+  let voting_mint_config = &registrar.voting_mints[position.voting_mint_config_idx as usize];
+  let epoch = current_epoch(registrar.clock_unix_timestamp());
+
+  let dao_epoch_infos = load_claim_range(ctx.remaining_accounts, &ctx.accounts.dao.key(), epoch)?;
+  let pending = calculate_pending_rewards(
+    position,
+    voting_mint_config,
+    &ctx.accounts.delegated_position,
+    dao_epoch_infos.iter().map(|info| &**info),
+  )?;
+
+  for claim in pending.epochs.iter() {
+    ctx.accounts.delegated_position.set_claimed(claim.epoch)?;
+  }
+  let first_ts = pending.epochs.last().map(|claim| {
+    dao_epoch_infos
+      .iter()
+      .find(|info| info.epoch == claim.epoch)
+      .unwrap()
+      .recent_proposals
+      .last()
+      .unwrap()
+      .ts
+  });
+
+  // Same as claim_rewards_v1, only clear proposals once the position is caught up, using the
+  // most recent claimed epoch.
+  if let Some(first_ts) = first_ts {
+    if epoch == ctx.accounts.delegated_position.last_claimed_epoch + 1
+      && ctx
+        .accounts
+        .position
+        .recent_proposals
+        .iter()
+        .any(|p| p.ts < first_ts)
+    {
+      clear_recent_proposals_v0(
+        CpiContext::new_with_signer(
+          ctx.accounts.vsr_program.to_account_info(),
+          ClearRecentProposalsV0 {
+            position: ctx.accounts.position.to_account_info(),
+            registrar: ctx.accounts.registrar.to_account_info(),
+            dao: ctx.accounts.dao.to_account_info(),
+          },
+          &[dao_seeds!(ctx.accounts.dao)],
+        ),
+        ClearRecentProposalsArgsV0 {
+          ts: first_ts,
+          dao_bump: ctx.accounts.dao.bump_seed,
+        },
+      )?;
+    }
+  }
+
+  pay_out_rewards(
+    PayoutAccounts {
+      payer: &ctx.accounts.payer,
+      dao: &ctx.accounts.dao,
+      registrar: &ctx.accounts.registrar,
+      position: &mut ctx.accounts.position,
+      hnt_mint: &ctx.accounts.hnt_mint,
+      sub_dao: &mut ctx.accounts.sub_dao,
+      delegated_position: &mut ctx.accounts.delegated_position,
+      delegator_pool: &ctx.accounts.delegator_pool,
+      delegator_ata: &ctx.accounts.delegator_ata,
+      delegator_pool_circuit_breaker: &ctx.accounts.delegator_pool_circuit_breaker,
+      compound_escrow: ctx.accounts.compound_escrow.as_deref(),
+      position_vault: ctx.accounts.position_vault.as_deref(),
+      compound_sub_dao_epoch_info: ctx.accounts.compound_sub_dao_epoch_info.as_deref_mut(),
+      compound_sub_dao_epoch_info_bump: ctx.bumps.compound_sub_dao_epoch_info,
+      closing_time_sub_dao_epoch_info: ctx.accounts.closing_time_sub_dao_epoch_info.as_deref_mut(),
+      vsr_program: &ctx.accounts.vsr_program,
+      circuit_breaker_program: &ctx.accounts.circuit_breaker_program,
+      token_program: &ctx.accounts.token_program,
+      system_program: &ctx.accounts.system_program,
+    },
+    pending.claimable_rewards,
+    pending.ineligible_rewards,
+  )?;
+
+  Ok(())
+}
@@ -1,5 +1,3 @@
-use std::collections::HashSet;
-
 use anchor_lang::{prelude::*, solana_program::pubkey};
 use anchor_spl::{
   associated_token::AssociatedToken,
@@ -12,20 +10,28 @@ use circuit_breaker::{
   },
   AccountWindowedCircuitBreakerV0, BurnArgsV0, CircuitBreaker, TransferArgsV0,
 };
+use shared_utils::resize_to_fit;
 use voter_stake_registry::{
-  cpi::{accounts::ClearRecentProposalsV0, clear_recent_proposals_v0},
+  cpi::{
+    accounts::{ClearRecentProposalsV0, DepositV0},
+    clear_recent_proposals_v0, deposit_v0,
+  },
   state::{PositionV0, Registrar},
-  ClearRecentProposalsArgsV0, VoterStakeRegistry,
+  ClearRecentProposalsArgsV0, DepositArgsV0, VoterStakeRegistry,
 };
 
-use crate::{current_epoch, dao_seeds, error::ErrorCode, state::*, TESTING};
+use super::{close_delegation_v0::get_closing_epoch_bytes, CompoundAccounts, PayoutAccounts};
+use crate::{
+  calculate_epoch_claim, current_epoch, dao_seeds, error::ErrorCode, get_sub_dao_epoch_info_seed,
+  replace_delegated_vehnt, state::*, DelegatedVehnt, EPOCH_LENGTH, TESTING,
+};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct ClaimRewardsArgsV0 {
   pub epoch: u64,
 }
 
-const TUKTUK_SIGNER_KEY: Pubkey = pubkey!("8m6iyXwcu8obaXdqKwzBqHE5HM2tRZZfSXV5qNALiPk4");
+pub const TUKTUK_SIGNER_KEY: Pubkey = pubkey!("8m6iyXwcu8obaXdqKwzBqHE5HM2tRZZfSXV5qNALiPk4");
 
 #[derive(Accounts)]
 #[instruction(args: ClaimRewardsArgsV0)]
@@ -112,39 +118,41 @@ pub struct ClaimRewardsV1<'info> {
   pub token_program: Program<'info, Token>,
   #[account(mut)]
   pub payer: Signer<'info>,
-}
-
-impl<'info> ClaimRewardsV1<'info> {
-  fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, TransferV0<'info>> {
-    let cpi_accounts = TransferV0 {
-      from: self.delegator_pool.to_account_info(),
-      to: self.delegator_ata.to_account_info(),
-      owner: self.dao.to_account_info(),
-      circuit_breaker: self.delegator_pool_circuit_breaker.to_account_info(),
-      token_program: self.token_program.to_account_info(),
-    };
-
-    CpiContext::new(self.circuit_breaker_program.to_account_info(), cpi_accounts)
-  }
-
-  fn burn_ctx(&self) -> CpiContext<'_, '_, '_, 'info, BurnV0<'info>> {
-    let cpi_accounts = BurnV0 {
-      mint: self.hnt_mint.to_account_info(),
-      from: self.delegator_pool.to_account_info(),
-      circuit_breaker: self.delegator_pool_circuit_breaker.to_account_info(),
-      token_program: self.token_program.to_account_info(),
-      owner: self.dao.to_account_info(),
-    };
-
-    CpiContext::new(self.circuit_breaker_program.to_account_info(), cpi_accounts)
-  }
+  // The following are only needed when the delegated position compounds rewards.
+  // Rewards pass through the dao's escrow on their way into the position's vault.
+  #[account(
+    init_if_needed,
+    payer = payer,
+    associated_token::mint = hnt_mint,
+    associated_token::authority = dao,
+  )]
+  pub compound_escrow: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    mut,
+    associated_token::mint = hnt_mint,
+    associated_token::authority = position,
+  )]
+  pub position_vault: Option<Box<Account<'info, TokenAccount>>>,
+  #[account(
+    init_if_needed,
+    payer = payer,
+    space = SubDaoEpochInfoV0::SIZE,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_sub_dao_epoch_info_seed(&registrar)],
+    bump,
+  )]
+  pub compound_sub_dao_epoch_info: Option<Box<Account<'info, SubDaoEpochInfoV0>>>,
+  #[account(
+    mut,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_closing_epoch_bytes(&position, &delegated_position)],
+    bump = closing_time_sub_dao_epoch_info.bump_seed,
+  )]
+  pub closing_time_sub_dao_epoch_info: Option<Box<Account<'info, SubDaoEpochInfoV0>>>,
 }
 
 pub fn handler(ctx: Context<ClaimRewardsV1>, args: ClaimRewardsArgsV0) -> Result<()> {
   // load the vehnt information
   let position = &mut ctx.accounts.position;
   let registrar = &ctx.accounts.registrar;
-  let dao_epoch_info_ts = ctx.accounts.dao_epoch_info.start_ts();
   let voting_mint_config = &registrar.voting_mints[position.voting_mint_config_idx as usize];
 
   let delegated_position = &mut ctx.accounts.delegated_position;
@@ -159,29 +167,12 @@ pub fn handler(ctx: Context<ClaimRewardsV1>, args: ClaimRewardsArgsV0) -> Result
     }
   }
 
-  let epoch_start_ts = ctx.accounts.dao_epoch_info.start_ts();
-  let delegated_vehnt_at_epoch = if delegated_position.expiration_ts > epoch_start_ts {
-    position.voting_power(voting_mint_config, epoch_start_ts)?
-  } else {
-    0
-  };
-
-  msg!("Staked {} veHNT at start of epoch with {} total veHNT delegated to dao and {} total rewards to dao",
-    delegated_vehnt_at_epoch,
-    ctx.accounts.dao_epoch_info.vehnt_at_epoch_start,
-    ctx.accounts.dao_epoch_info.delegation_rewards_issued
-  );
-
-  // calculate the position's share of that epoch's rewards
-  // rewards = staking_rewards_issued * staked_vehnt_at_epoch / total_vehnt
-  let rewards = u64::try_from(
-    delegated_vehnt_at_epoch
-      .checked_mul(ctx.accounts.dao_epoch_info.delegation_rewards_issued as u128)
-      .unwrap()
-      .checked_div(ctx.accounts.dao_epoch_info.vehnt_at_epoch_start as u128)
-      .unwrap_or(0),
-  )
-  .unwrap();
+  let claim = calculate_epoch_claim(
+    position,
+    voting_mint_config,
+    delegated_position,
+    &ctx.accounts.dao_epoch_info,
+  )?;
 
   delegated_position.set_claimed(args.epoch)?;
 
@@ -222,64 +213,197 @@ pub fn handler(ctx: Context<ClaimRewardsV1>, args: ClaimRewardsArgsV0) -> Result
     }
   }
 
-  let last_ts = ctx
-    .accounts
-    .dao_epoch_info
-    .recent_proposals
-    .first()
-    .unwrap()
-    .ts;
-  let proposal_set = ctx
-    .accounts
-    .position
-    .recent_proposals
-    .iter()
-    .filter(|p| p.ts <= last_ts && p.ts >= first_ts)
-    .map(|rp| rp.proposal)
-    .collect::<HashSet<_>>();
+  pay_out_rewards(
+    PayoutAccounts {
+      payer: &ctx.accounts.payer,
+      dao: &ctx.accounts.dao,
+      registrar: &ctx.accounts.registrar,
+      position: &mut ctx.accounts.position,
+      hnt_mint: &ctx.accounts.hnt_mint,
+      sub_dao: &mut ctx.accounts.sub_dao,
+      delegated_position: &mut ctx.accounts.delegated_position,
+      delegator_pool: &ctx.accounts.delegator_pool,
+      delegator_ata: &ctx.accounts.delegator_ata,
+      delegator_pool_circuit_breaker: &ctx.accounts.delegator_pool_circuit_breaker,
+      compound_escrow: ctx.accounts.compound_escrow.as_deref(),
+      position_vault: ctx.accounts.position_vault.as_deref(),
+      compound_sub_dao_epoch_info: ctx.accounts.compound_sub_dao_epoch_info.as_deref_mut(),
+      compound_sub_dao_epoch_info_bump: ctx.bumps.compound_sub_dao_epoch_info,
+      closing_time_sub_dao_epoch_info: ctx.accounts.closing_time_sub_dao_epoch_info.as_deref_mut(),
+      vsr_program: &ctx.accounts.vsr_program,
+      circuit_breaker_program: &ctx.accounts.circuit_breaker_program,
+      token_program: &ctx.accounts.token_program,
+      system_program: &ctx.accounts.system_program,
+    },
+    claim.rewards,
+    claim.ineligible_rewards,
+  )?;
 
-  // Check eligibility based on recent proposals
-  let eligible_count = ctx
-    .accounts
-    .dao_epoch_info
-    .recent_proposals
-    .iter()
-    .filter(|&proposal| {
-      proposal_set.contains(&proposal.proposal) || proposal.is_in_progress(dao_epoch_info_ts)
-    })
-    .count();
-  let not_four_proposals = ctx.accounts.dao_epoch_info.recent_proposals.len() < 4
-    || ctx
-      .accounts
-      .dao_epoch_info
-      .recent_proposals
-      .iter()
-      .filter(|p| p.proposal != Pubkey::default())
-      .count()
-      < 4;
+  Ok(())
+}
 
-  let amount_left = ctx.accounts.delegator_pool.amount;
-  let amount = std::cmp::min(rewards, amount_left);
+/// Pays `rewards` from the delegator pool to the delegator, or into the position when it
+/// compounds, and burns `ineligible_rewards`.
+pub fn pay_out_rewards<'info>(
+  accounts: PayoutAccounts<'_, 'info>,
+  rewards: u64,
+  ineligible_rewards: u64,
+) -> Result<()> {
+  // Due to rounding down of vehnt fall rates it's possible the vehnt on the dao does not exactly match the
+  // vehnt remaining. It could be off by a little bit of dust.
+  let amount_left = accounts.delegator_pool.amount;
+  let transfer_amount = std::cmp::min(rewards, amount_left);
+  let burn_amount = std::cmp::min(ineligible_rewards, amount_left - transfer_amount);
 
-  if !not_four_proposals && eligible_count < 2 {
-    burn_v0(
-      ctx
-        .accounts
-        .burn_ctx()
-        .with_signer(&[dao_seeds!(ctx.accounts.dao)]),
-      BurnArgsV0 { amount },
-    )?;
-  } else {
+  let compound = accounts.delegated_position.compound_rewards
+    && accounts.position.genesis_end <= accounts.registrar.clock_unix_timestamp();
+  if transfer_amount > 0 {
+    // Compounded rewards pass through the dao's escrow on their way into the position's vault
+    let to = if compound {
+      accounts
+        .compound_escrow
+        .ok_or(ErrorCode::CompoundingAccountsMissing)?
+        .to_account_info()
+    } else {
+      accounts.delegator_ata.to_account_info()
+    };
     transfer_v0(
-      ctx
-        .accounts
-        .transfer_ctx()
-        .with_signer(&[dao_seeds!(ctx.accounts.dao)]),
-      // Due to rounding down of vehnt fall rates it's possible the vehnt on the dao does not exactly match the
-      // vehnt remaining. It could be off by a little bit of dust.
-      TransferArgsV0 { amount },
+      CpiContext::new_with_signer(
+        accounts.circuit_breaker_program.to_account_info(),
+        TransferV0 {
+          from: accounts.delegator_pool.to_account_info(),
+          to,
+          owner: accounts.dao.to_account_info(),
+          circuit_breaker: accounts.delegator_pool_circuit_breaker.to_account_info(),
+          token_program: accounts.token_program.to_account_info(),
+        },
+        &[dao_seeds!(accounts.dao)],
+      ),
+      TransferArgsV0 {
+        amount: transfer_amount,
+      },
+    )?;
+  }
+
+  if transfer_amount > 0 && compound {
+    compound_rewards(
+      CompoundAccounts {
+        dao: accounts.dao,
+        registrar: accounts.registrar,
+        position: accounts.position,
+        hnt_mint: accounts.hnt_mint,
+        sub_dao: accounts.sub_dao,
+        delegated_position: &mut *accounts.delegated_position,
+        sub_dao_epoch_info: accounts
+          .compound_sub_dao_epoch_info
+          .ok_or(ErrorCode::CompoundingAccountsMissing)?,
+        sub_dao_epoch_info_bump: accounts.compound_sub_dao_epoch_info_bump.unwrap(),
+        closing_time_sub_dao_epoch_info: accounts
+          .closing_time_sub_dao_epoch_info
+          .ok_or(ErrorCode::CompoundingAccountsMissing)?,
+        compound_escrow: accounts
+          .compound_escrow
+          .ok_or(ErrorCode::CompoundingAccountsMissing)?,
+        position_vault: accounts
+          .position_vault
+          .ok_or(ErrorCode::CompoundingAccountsMissing)?,
+        vsr_program: accounts.vsr_program,
+        token_program: accounts.token_program,
+      },
+      transfer_amount,
+    )?;
+    resize_to_fit(
+      &accounts.payer.to_account_info(),
+      &accounts.system_program.to_account_info(),
+      accounts.delegated_position,
+    )?;
+  }
+
+  if burn_amount > 0 {
+    burn_v0(
+      CpiContext::new_with_signer(
+        accounts.circuit_breaker_program.to_account_info(),
+        BurnV0 {
+          mint: accounts.hnt_mint.to_account_info(),
+          from: accounts.delegator_pool.to_account_info(),
+          circuit_breaker: accounts.delegator_pool_circuit_breaker.to_account_info(),
+          token_program: accounts.token_program.to_account_info(),
+          owner: accounts.dao.to_account_info(),
+        },
+        &[dao_seeds!(accounts.dao)],
+      ),
+      BurnArgsV0 {
+        amount: burn_amount,
+      },
     )?;
   }
 
   Ok(())
 }
+
+/// Deposits `amount` of already escrowed rewards into the delegated position, adding the
+/// extra veHNT to the sub dao exactly as close_delegation_v0 will later remove it.
+pub fn compound_rewards(accounts: CompoundAccounts, amount: u64) -> Result<()> {
+  let registrar = accounts.registrar;
+  let voting_mint_config =
+    &registrar.voting_mints[accounts.position.voting_mint_config_idx as usize];
+  let curr_ts = registrar.clock_unix_timestamp();
+  let curr_epoch = current_epoch(curr_ts);
+  let expiration_ts = accounts.delegated_position.expiration_ts;
+
+  let before = DelegatedVehnt::new(
+    accounts.position,
+    voting_mint_config,
+    accounts.delegated_position,
+    curr_ts,
+  )?;
+
+  deposit_v0(
+    CpiContext::new_with_signer(
+      accounts.vsr_program.to_account_info(),
+      DepositV0 {
+        registrar: registrar.to_account_info(),
+        position: accounts.position.to_account_info(),
+        vault: accounts.position_vault.to_account_info(),
+        mint: accounts.hnt_mint.to_account_info(),
+        deposit_token: accounts.compound_escrow.to_account_info(),
+        deposit_authority: accounts.dao.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+      },
+      &[dao_seeds!(accounts.dao)],
+    ),
+    DepositArgsV0 { amount },
+  )?;
+  accounts.position.reload()?;
+
+  let after = DelegatedVehnt::new(
+    accounts.position,
+    voting_mint_config,
+    accounts.delegated_position,
+    curr_ts,
+  )?;
+
+  let delegated_position = accounts.delegated_position;
+  delegated_position.hnt_amount = accounts.position.amount_deposited_native;
+  delegated_position.record_compounded_deposit(curr_ts, amount);
+
+  let sub_dao_epoch_info = accounts.sub_dao_epoch_info;
+  sub_dao_epoch_info.epoch = curr_epoch;
+  sub_dao_epoch_info.sub_dao = accounts.sub_dao.key();
+  sub_dao_epoch_info.bump_seed = accounts.sub_dao_epoch_info_bump;
+  sub_dao_epoch_info.initialized = true;
+
+  // Positions expiring this epoch were already purged from the sub dao
+  if expiration_ts < i64::try_from(curr_epoch + 1).unwrap() * EPOCH_LENGTH {
+    return Ok(());
+  }
+
+  replace_delegated_vehnt(
+    accounts.sub_dao,
+    sub_dao_epoch_info,
+    accounts.closing_time_sub_dao_epoch_info,
+    curr_ts,
+    before,
+    after,
+  )
+}
@@ -10,7 +10,7 @@ use voter_stake_registry::{
 use super::CloseDelegationAccounts;
 use crate::{
   caclulate_vhnt_info, current_epoch, get_sub_dao_epoch_info_seed, id, state::*,
-  update_subdao_vehnt, PrecisePosition, VehntInfo, TESTING,
+  update_subdao_vehnt, voting_power_before_compounding, PrecisePosition, VehntInfo, TESTING,
 };
 
 pub fn get_genesis_end_epoch_bytes(
@@ -271,9 +271,12 @@ pub fn raw_handler(accounts: &mut CloseDelegationAccounts, sde_bump: u8) -> Resu
   }
   // If the position was staked before this epoch, remove it.
   if current_epoch(delegated_position.start_ts) < curr_epoch {
-    let vehnt_at_start = u64::try_from(
-      position.voting_power(voting_mint_config, accounts.sub_dao_epoch_info.start_ts())?,
-    )
+    let vehnt_at_start = u64::try_from(voting_power_before_compounding(
+      position,
+      voting_mint_config,
+      delegated_position,
+      accounts.sub_dao_epoch_info.start_ts(),
+    )?)
     .unwrap();
     msg!(
       "Removing {} vehnt from this epoch for this subdao, which currently has {} vehnt",
@@ -68,7 +68,8 @@ pub struct DelegateV0<'info> {
     bump = position.bump_seed,
     has_one = mint,
     has_one = registrar,
-    constraint = position.lockup.kind == LockupKind::Constant || position.lockup.end_ts > registrar.clock_unix_timestamp()
+    constraint = position.lockup.kind == LockupKind::Constant || position.lockup.end_ts > registrar.clock_unix_timestamp(),
+    constraint = !position.lockup.kind.is_vesting() @ ErrorCode::VestingPositionDelegation
   )]
   pub position: Box<Account<'info, PositionV0>>,
   pub mint: Box<Account<'info, Mint>>,
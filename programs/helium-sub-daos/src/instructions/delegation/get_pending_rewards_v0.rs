@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use voter_stake_registry::state::{PositionV0, Registrar};
+
+use crate::{
+  calculate_pending_rewards, current_epoch, load_claim_range, state::*, PendingRewardsV0,
+};
+
+// Remaining accounts are the `DaoEpochInfoV0`s of the epochs to check, in ascending order,
+// exactly as they would be passed to batch_claim_rewards_v0.
+#[derive(Accounts)]
+pub struct GetPendingRewardsV0<'info> {
+  #[account(
+    seeds = [b"position".as_ref(), position.mint.as_ref()],
+    seeds::program = voter_stake_registry::ID,
+    bump = position.bump_seed,
+    has_one = registrar,
+  )]
+  pub position: Box<Account<'info, PositionV0>>,
+  pub registrar: Box<Account<'info, Registrar>>,
+  #[account(
+    has_one = registrar,
+  )]
+  pub dao: Box<Account<'info, DaoV0>>,
+  #[account(
+    has_one = dao,
+  )]
+  pub sub_dao: Box<Account<'info, SubDaoV0>>,
+  #[account(
+    has_one = sub_dao,
+    seeds = ["delegated_position".as_bytes(), position.key().as_ref()],
+    bump = delegated_position.bump_seed,
+  )]
+  pub delegated_position: Box<Account<'info, DelegatedPositionV0>>,
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, 'info, 'info, GetPendingRewardsV0<'info>>,
+) -> Result<PendingRewardsV0> {
+  let position = &ctx.accounts.position;
+  let registrar = &ctx.accounts.registrar;
+  let voting_mint_config = &registrar.voting_mints[position.voting_mint_config_idx as usize];
+  let epoch = current_epoch(registrar.clock_unix_timestamp());
+
+  let dao_epoch_infos = load_claim_range(ctx.remaining_accounts, &ctx.accounts.dao.key(), epoch)?;
+  calculate_pending_rewards(
+    position,
+    voting_mint_config,
+    &ctx.accounts.delegated_position,
+    dao_epoch_infos.iter().map(|info| &**info),
+  )
+}
@@ -0,0 +1,223 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{Mint, Token, TokenAccount},
+};
+use shared_utils::resize_to_fit;
+use voter_stake_registry::{
+  cpi::{accounts::TransferV0 as VsrTransferV0, transfer_v0},
+  state::{PositionV0, Registrar},
+  TransferArgsV0 as VsrTransferArgsV0, VoterStakeRegistry,
+};
+
+use super::close_delegation_v0::get_closing_epoch_bytes;
+use crate::{
+  current_epoch, dao_seeds, error::ErrorCode, get_sub_dao_epoch_info_seed, replace_delegated_vehnt,
+  state::*, DelegatedVehnt, EPOCH_LENGTH,
+};
+
+#[derive(Accounts)]
+pub struct MergeDelegatedPositionsV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub registrar: Box<Account<'info, Registrar>>,
+  #[account(
+    has_one = registrar,
+  )]
+  pub dao: Box<Account<'info, DaoV0>>,
+  #[account(
+    mut,
+    has_one = dao,
+  )]
+  pub sub_dao: Box<Account<'info, SubDaoV0>>,
+  #[account(
+    mut,
+    seeds = [b"position".as_ref(), mint.key().as_ref()],
+    seeds::program = vsr_program.key(),
+    bump = source_position.bump_seed,
+    has_one = mint,
+    has_one = registrar,
+  )]
+  pub source_position: Box<Account<'info, PositionV0>>,
+  pub mint: Box<Account<'info, Mint>>,
+  #[account(
+    token::mint = mint,
+    token::authority = position_authority,
+    constraint = position_token_account.amount > 0
+  )]
+  pub position_token_account: Box<Account<'info, TokenAccount>>,
+  #[account(mut)]
+  pub position_authority: Signer<'info>,
+  #[account(
+    mut,
+    close = position_authority,
+    seeds = ["delegated_position".as_bytes(), source_position.key().as_ref()],
+    bump = source_delegated_position.bump_seed,
+    has_one = sub_dao,
+  )]
+  pub source_delegated_position: Box<Account<'info, DelegatedPositionV0>>,
+  #[account(
+    mut,
+    has_one = registrar,
+  )]
+  pub target_position: Box<Account<'info, PositionV0>>,
+  #[account(
+    mut,
+    seeds = ["delegated_position".as_bytes(), target_position.key().as_ref()],
+    bump = target_delegated_position.bump_seed,
+    has_one = sub_dao,
+  )]
+  pub target_delegated_position: Box<Account<'info, DelegatedPositionV0>>,
+  #[account(
+    init_if_needed,
+    payer = payer,
+    space = SubDaoEpochInfoV0::SIZE,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_sub_dao_epoch_info_seed(&registrar)],
+    bump,
+  )]
+  pub sub_dao_epoch_info: Box<Account<'info, SubDaoEpochInfoV0>>,
+  #[account(
+    mut,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_closing_epoch_bytes(&target_position, &target_delegated_position)],
+    bump = closing_time_sub_dao_epoch_info.bump_seed,
+  )]
+  pub closing_time_sub_dao_epoch_info: Box<Account<'info, SubDaoEpochInfoV0>>,
+  pub deposit_mint: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    associated_token::authority = source_position,
+    associated_token::mint = deposit_mint,
+  )]
+  pub source_vault: Box<Account<'info, TokenAccount>>,
+  #[account(
+    mut,
+    associated_token::authority = target_position,
+    associated_token::mint = deposit_mint,
+  )]
+  pub target_vault: Box<Account<'info, TokenAccount>>,
+  pub vsr_program: Program<'info, VoterStakeRegistry>,
+  pub system_program: Program<'info, System>,
+  pub token_program: Program<'info, Token>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Merges all of the tokens in a delegated source position into a delegated target
+/// position with the same lockup and sub dao, closing the source delegation.
+///
+/// The emptied source position can then be closed with the voter stake registry.
+pub fn handler(ctx: Context<MergeDelegatedPositionsV0>) -> Result<()> {
+  let registrar = &ctx.accounts.registrar;
+  let source_position = &ctx.accounts.source_position;
+  let target_position = &ctx.accounts.target_position;
+  let source_delegated_position = &ctx.accounts.source_delegated_position;
+  let target_delegated_position = &ctx.accounts.target_delegated_position;
+  let voting_mint_config = &registrar.voting_mints[target_position.voting_mint_config_idx as usize];
+  let curr_ts = registrar.clock_unix_timestamp();
+  let curr_epoch = current_epoch(curr_ts);
+
+  // Genesis positions also have corrections at genesis end, which would need to be merged as well
+  require_gte!(
+    curr_ts,
+    source_position.genesis_end,
+    ErrorCode::GenesisPositionChange
+  );
+  require_gte!(
+    curr_ts,
+    target_position.genesis_end,
+    ErrorCode::GenesisPositionChange
+  );
+  source_position.check_transfer_to(target_position, voting_mint_config, curr_ts)?;
+  // Both delegations must look identical to the sub dao for the merged position to be
+  // accounted for like a single one
+  require!(
+    source_position.lockup.kind == target_position.lockup.kind
+      && source_position.lockup.seconds_left(curr_ts)
+        == target_position.lockup.seconds_left(curr_ts)
+      && source_delegated_position.expiration_ts == target_delegated_position.expiration_ts
+      && source_delegated_position.last_claimed_epoch + 1 >= curr_epoch
+      && source_delegated_position.last_claimed_epoch
+        == target_delegated_position.last_claimed_epoch
+      && source_delegated_position.claimed_epochs_bitmap
+        == target_delegated_position.claimed_epochs_bitmap
+      && (current_epoch(source_delegated_position.start_ts) < curr_epoch)
+        == (current_epoch(target_delegated_position.start_ts) < curr_epoch),
+    ErrorCode::IncompatibleMerge
+  );
+
+  let before = DelegatedVehnt::new(
+    source_position,
+    voting_mint_config,
+    source_delegated_position,
+    curr_ts,
+  )?
+  .checked_add(&DelegatedVehnt::new(
+    target_position,
+    voting_mint_config,
+    target_delegated_position,
+    curr_ts,
+  )?)
+  .ok_or_else(|| error!(ErrorCode::ArithmeticError))?;
+
+  transfer_v0(
+    CpiContext::new_with_signer(
+      ctx.accounts.vsr_program.to_account_info(),
+      VsrTransferV0 {
+        registrar: ctx.accounts.registrar.to_account_info(),
+        position_update_authority: ctx.accounts.dao.to_account_info(),
+        source_position: ctx.accounts.source_position.to_account_info(),
+        mint: ctx.accounts.mint.to_account_info(),
+        position_token_account: ctx.accounts.position_token_account.to_account_info(),
+        position_authority: ctx.accounts.position_authority.to_account_info(),
+        target_position: ctx.accounts.target_position.to_account_info(),
+        deposit_mint: ctx.accounts.deposit_mint.to_account_info(),
+        source_vault: ctx.accounts.source_vault.to_account_info(),
+        target_vault: ctx.accounts.target_vault.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+      },
+      &[dao_seeds!(ctx.accounts.dao)],
+    ),
+    VsrTransferArgsV0 {
+      amount: ctx.accounts.source_position.amount_deposited_native,
+    },
+  )?;
+  ctx.accounts.source_position.reload()?;
+  ctx.accounts.target_position.reload()?;
+
+  let source_delegated_position = ctx.accounts.source_delegated_position.as_ref().clone();
+  let target_delegated_position = &mut ctx.accounts.target_delegated_position;
+  target_delegated_position.merge(&source_delegated_position);
+  let after = DelegatedVehnt::new(
+    &ctx.accounts.target_position,
+    voting_mint_config,
+    target_delegated_position,
+    curr_ts,
+  )?;
+  let expiration_ts = target_delegated_position.expiration_ts;
+
+  resize_to_fit(
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.target_delegated_position,
+  )?;
+
+  let sub_dao_epoch_info = &mut ctx.accounts.sub_dao_epoch_info;
+  sub_dao_epoch_info.epoch = curr_epoch;
+  sub_dao_epoch_info.sub_dao = ctx.accounts.sub_dao.key();
+  sub_dao_epoch_info.bump_seed = ctx.bumps.sub_dao_epoch_info;
+  sub_dao_epoch_info.initialized = true;
+
+  // Positions expiring this epoch were already purged from the sub dao
+  if expiration_ts < i64::try_from(curr_epoch + 1).unwrap() * EPOCH_LENGTH {
+    return Ok(());
+  }
+
+  replace_delegated_vehnt(
+    &mut ctx.accounts.sub_dao,
+    sub_dao_epoch_info,
+    &mut ctx.accounts.closing_time_sub_dao_epoch_info,
+    curr_ts,
+    before,
+    after,
+  )
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use voter_stake_registry::cpi::{
+  accounts::{MergePositionsV0 as VsrMergePositionsV0, TransferV0 as VsrTransferV0},
+  merge_positions_v0,
+};
+
+use super::transfer_v0::*;
+use crate::dao_seeds;
+
+#[derive(Accounts)]
+pub struct MergePositionsV0<'info> {
+  pub common: TransferV0<'info>,
+  pub target_position_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+/// Merges two undelegated positions, see the voter stake registry merge_positions_v0.
+///
+/// Delegated positions must either be undelegated first, or have identical lockups
+/// and be merged with merge_delegated_positions_v0.
+pub fn handler(ctx: Context<MergePositionsV0>) -> Result<()> {
+  let common = &ctx.accounts.common;
+  merge_positions_v0(CpiContext::new_with_signer(
+    common.vsr_program.to_account_info(),
+    VsrMergePositionsV0 {
+      common: VsrTransferV0 {
+        registrar: common.registrar.to_account_info(),
+        position_update_authority: common.dao.to_account_info(),
+        source_position: common.source_position.to_account_info(),
+        mint: common.mint.to_account_info(),
+        position_token_account: common.position_token_account.to_account_info(),
+        position_authority: common.position_authority.to_account_info(),
+        target_position: common.target_position.to_account_info(),
+        deposit_mint: common.deposit_mint.to_account_info(),
+        source_vault: common.source_vault.to_account_info(),
+        target_vault: common.target_vault.to_account_info(),
+        token_program: common.token_program.to_account_info(),
+        associated_token_program: common.associated_token_program.to_account_info(),
+      },
+      target_position_token_account: ctx.accounts.target_position_token_account.to_account_info(),
+    },
+    &[dao_seeds!(common.dao)],
+  ))?;
+
+  Ok(())
+}
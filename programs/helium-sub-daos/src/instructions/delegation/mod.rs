@@ -1,28 +1,44 @@
+pub mod batch_claim_rewards_v0;
 pub mod change_delegation_v0;
 pub mod claim_rewards_v0;
 pub mod claim_rewards_v1;
 pub mod close_delegation_v0;
 pub mod delegate_v0;
 pub mod extend_expiration_ts_v0;
+pub mod get_pending_rewards_v0;
+pub mod merge_delegated_positions_v0;
+pub mod merge_positions_v0;
 pub mod reset_lockup_v0;
+pub mod split_delegated_position_v0;
 pub mod temp_claim_buggy_rewards;
 pub mod track_vote_v0;
 pub mod transfer_v0;
+pub mod update_delegated_position_v0;
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+pub use batch_claim_rewards_v0::*;
 pub use change_delegation_v0::*;
+use circuit_breaker::{AccountWindowedCircuitBreakerV0, CircuitBreaker};
 pub use claim_rewards_v0::*;
 pub use claim_rewards_v1::*;
 pub use close_delegation_v0::*;
 pub use delegate_v0::*;
 pub use extend_expiration_ts_v0::*;
+pub use get_pending_rewards_v0::*;
+pub use merge_delegated_positions_v0::*;
+pub use merge_positions_v0::*;
 use modular_governance::nft_proxy::accounts::ProxyConfigV0;
 pub use reset_lockup_v0::*;
+pub use split_delegated_position_v0::*;
 pub use temp_claim_buggy_rewards::*;
 pub use track_vote_v0::*;
 pub use transfer_v0::*;
-use voter_stake_registry::state::{PositionV0, Registrar};
+pub use update_delegated_position_v0::*;
+use voter_stake_registry::{
+  state::{PositionV0, Registrar},
+  VoterStakeRegistry,
+};
 
 use crate::state::*;
 
@@ -57,3 +73,42 @@ pub struct CloseDelegationAccounts<'a, 'info> {
   pub closing_time_sub_dao_epoch_info: &'a mut Box<Account<'info, SubDaoEpochInfoV0>>,
   pub genesis_end_sub_dao_epoch_info: &'a mut Box<Account<'info, SubDaoEpochInfoV0>>,
 }
+
+pub struct CompoundAccounts<'a, 'info> {
+  pub dao: &'a Account<'info, DaoV0>,
+  pub registrar: &'a Account<'info, Registrar>,
+  pub position: &'a mut Account<'info, PositionV0>,
+  pub hnt_mint: &'a Account<'info, Mint>,
+  pub sub_dao: &'a mut Account<'info, SubDaoV0>,
+  pub delegated_position: &'a mut Account<'info, DelegatedPositionV0>,
+  pub sub_dao_epoch_info: &'a mut Account<'info, SubDaoEpochInfoV0>,
+  pub sub_dao_epoch_info_bump: u8,
+  pub closing_time_sub_dao_epoch_info: &'a mut Account<'info, SubDaoEpochInfoV0>,
+  pub compound_escrow: &'a Account<'info, TokenAccount>,
+  pub position_vault: &'a Account<'info, TokenAccount>,
+  pub vsr_program: &'a Program<'info, VoterStakeRegistry>,
+  pub token_program: &'a Program<'info, Token>,
+}
+
+pub struct PayoutAccounts<'a, 'info> {
+  pub payer: &'a Signer<'info>,
+  pub dao: &'a Account<'info, DaoV0>,
+  pub registrar: &'a Account<'info, Registrar>,
+  pub position: &'a mut Account<'info, PositionV0>,
+  pub hnt_mint: &'a Account<'info, Mint>,
+  pub sub_dao: &'a mut Account<'info, SubDaoV0>,
+  pub delegated_position: &'a mut Account<'info, DelegatedPositionV0>,
+  pub delegator_pool: &'a Account<'info, TokenAccount>,
+  pub delegator_ata: &'a Account<'info, TokenAccount>,
+  pub delegator_pool_circuit_breaker: &'a Account<'info, AccountWindowedCircuitBreakerV0>,
+  // Only needed when the delegated position compounds rewards
+  pub compound_escrow: Option<&'a Account<'info, TokenAccount>>,
+  pub position_vault: Option<&'a Account<'info, TokenAccount>>,
+  pub compound_sub_dao_epoch_info: Option<&'a mut Account<'info, SubDaoEpochInfoV0>>,
+  pub compound_sub_dao_epoch_info_bump: Option<u8>,
+  pub closing_time_sub_dao_epoch_info: Option<&'a mut Account<'info, SubDaoEpochInfoV0>>,
+  pub vsr_program: &'a Program<'info, VoterStakeRegistry>,
+  pub circuit_breaker_program: &'a Program<'info, CircuitBreaker>,
+  pub token_program: &'a Program<'info, Token>,
+  pub system_program: &'a Program<'info, System>,
+}
@@ -0,0 +1,257 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  metadata::Metadata,
+  token::{Mint, Token, TokenAccount},
+};
+use voter_stake_registry::{
+  cpi::{accounts::SplitPositionV0, split_position_v0},
+  state::{PositionV0, RecentProposal, Registrar},
+  SplitPositionArgsV0, VoterStakeRegistry,
+};
+
+use super::close_delegation_v0::get_closing_epoch_bytes;
+use crate::{
+  current_epoch, dao_seeds, error::ErrorCode, get_sub_dao_epoch_info_seed, replace_delegated_vehnt,
+  state::*, DelegatedVehnt, EPOCH_LENGTH,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SplitDelegatedPositionArgsV0 {
+  pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct SplitDelegatedPositionV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub registrar: Box<Account<'info, Registrar>>,
+  #[account(
+    has_one = registrar,
+  )]
+  pub dao: Box<Account<'info, DaoV0>>,
+  #[account(
+    mut,
+    has_one = dao,
+  )]
+  pub sub_dao: Box<Account<'info, SubDaoV0>>,
+  #[account(
+    mut,
+    seeds = [b"position".as_ref(), mint.key().as_ref()],
+    seeds::program = vsr_program.key(),
+    bump = position.bump_seed,
+    has_one = mint,
+    has_one = registrar,
+  )]
+  pub position: Box<Account<'info, PositionV0>>,
+  pub mint: Box<Account<'info, Mint>>,
+  #[account(
+    token::mint = mint,
+    token::authority = position_authority,
+    constraint = position_token_account.amount > 0
+  )]
+  pub position_token_account: Box<Account<'info, TokenAccount>>,
+  pub position_authority: Signer<'info>,
+  #[account(
+    mut,
+    seeds = ["delegated_position".as_bytes(), position.key().as_ref()],
+    bump = delegated_position.bump_seed,
+    has_one = sub_dao,
+  )]
+  pub delegated_position: Box<Account<'info, DelegatedPositionV0>>,
+  #[account(
+    init_if_needed,
+    payer = payer,
+    space = SubDaoEpochInfoV0::SIZE,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_sub_dao_epoch_info_seed(&registrar)],
+    bump,
+  )]
+  pub sub_dao_epoch_info: Box<Account<'info, SubDaoEpochInfoV0>>,
+  #[account(
+    mut,
+    seeds = ["sub_dao_epoch_info".as_bytes(), sub_dao.key().as_ref(), &get_closing_epoch_bytes(&position, &delegated_position)],
+    bump = closing_time_sub_dao_epoch_info.bump_seed,
+  )]
+  pub closing_time_sub_dao_epoch_info: Box<Account<'info, SubDaoEpochInfoV0>>,
+  #[account(
+    mut,
+    associated_token::authority = position,
+    associated_token::mint = deposit_mint,
+  )]
+  pub vault: Box<Account<'info, TokenAccount>>,
+  /// CHECK: Initialized by the voter stake registry
+  #[account(
+    mut,
+    seeds = [b"position".as_ref(), new_mint.key().as_ref()],
+    seeds::program = vsr_program.key(),
+    bump,
+  )]
+  pub new_position: UncheckedAccount<'info>,
+  /// CHECK: Checked by cpi
+  #[account(mut)]
+  pub new_mint: UncheckedAccount<'info>,
+  /// CHECK: Checked by cpi
+  #[account(
+    mut,
+    seeds = ["metadata".as_bytes(), token_metadata_program.key().as_ref(), new_mint.key().as_ref()],
+    seeds::program = token_metadata_program.key(),
+    bump,
+  )]
+  pub new_metadata: UncheckedAccount<'info>,
+  /// CHECK: Checked by cpi
+  #[account(mut)]
+  pub new_position_token_account: UncheckedAccount<'info>,
+  /// CHECK: Checked by cpi
+  #[account(mut)]
+  pub new_vault: UncheckedAccount<'info>,
+  #[account(
+    init,
+    payer = payer,
+    space = 60 + 8 + std::mem::size_of::<DelegatedPositionV0>()
+      + delegated_position.proxy_voted_proposals.len() * RecentProposal::INIT_SPACE
+      + delegated_position.compounded_deposits.len() * std::mem::size_of::<CompoundedDepositV0>(),
+    seeds = ["delegated_position".as_bytes(), new_position.key().as_ref()],
+    bump,
+  )]
+  pub new_delegated_position: Box<Account<'info, DelegatedPositionV0>>,
+  /// CHECK: Checked by cpi
+  #[account(
+    address = registrar.collection,
+  )]
+  pub collection: UncheckedAccount<'info>,
+  /// CHECK: Checked by cpi
+  #[account(
+    mut,
+    seeds = ["metadata".as_bytes(), token_metadata_program.key().as_ref(), collection.key().as_ref()],
+    seeds::program = token_metadata_program.key(),
+    bump,
+  )]
+  pub collection_metadata: UncheckedAccount<'info>,
+  /// CHECK: Checked by cpi
+  #[account(
+    seeds = ["metadata".as_bytes(), token_metadata_program.key().as_ref(), collection.key().as_ref(), "edition".as_bytes()],
+    seeds::program = token_metadata_program.key(),
+    bump,
+  )]
+  pub collection_master_edition: UncheckedAccount<'info>,
+  pub deposit_mint: Box<Account<'info, Mint>>,
+  pub vsr_program: Program<'info, VoterStakeRegistry>,
+  pub system_program: Program<'info, System>,
+  pub token_program: Program<'info, Token>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_metadata_program: Program<'info, Metadata>,
+}
+
+/// Splits `amount` off of a delegated position into a new position NFT with the same
+/// lockup, delegated to the same sub dao with the same claimed epochs.
+///
+/// The new piece can then be undelegated or moved to another sub dao with
+/// `change_delegation_v0` without touching the rest of the stake.
+pub fn handler(
+  ctx: Context<SplitDelegatedPositionV0>,
+  args: SplitDelegatedPositionArgsV0,
+) -> Result<()> {
+  let registrar = &ctx.accounts.registrar;
+  let voting_mint_config =
+    &registrar.voting_mints[ctx.accounts.position.voting_mint_config_idx as usize];
+  let curr_ts = registrar.clock_unix_timestamp();
+  let curr_epoch = current_epoch(curr_ts);
+
+  // Genesis positions also have corrections at genesis end, which would need to be split as well
+  require_gte!(
+    curr_ts,
+    ctx.accounts.position.genesis_end,
+    ErrorCode::GenesisPositionChange
+  );
+
+  let total = ctx.accounts.position.amount_deposited_native;
+  let before = DelegatedVehnt::new(
+    &ctx.accounts.position,
+    voting_mint_config,
+    &ctx.accounts.delegated_position,
+    curr_ts,
+  )?;
+
+  split_position_v0(
+    CpiContext::new_with_signer(
+      ctx.accounts.vsr_program.to_account_info(),
+      SplitPositionV0 {
+        registrar: ctx.accounts.registrar.to_account_info(),
+        position_update_authority: ctx.accounts.dao.to_account_info(),
+        collection: ctx.accounts.collection.to_account_info(),
+        collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+        collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+        source_position: ctx.accounts.position.to_account_info(),
+        source_mint: ctx.accounts.mint.to_account_info(),
+        source_position_token_account: ctx.accounts.position_token_account.to_account_info(),
+        position_authority: ctx.accounts.position_authority.to_account_info(),
+        source_vault: ctx.accounts.vault.to_account_info(),
+        position: ctx.accounts.new_position.to_account_info(),
+        mint: ctx.accounts.new_mint.to_account_info(),
+        metadata: ctx.accounts.new_metadata.to_account_info(),
+        position_token_account: ctx.accounts.new_position_token_account.to_account_info(),
+        recipient: ctx.accounts.position_authority.to_account_info(),
+        vault: ctx.accounts.new_vault.to_account_info(),
+        payer: ctx.accounts.payer.to_account_info(),
+        deposit_mint: ctx.accounts.deposit_mint.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+        token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+      },
+      &[dao_seeds!(ctx.accounts.dao)],
+    ),
+    SplitPositionArgsV0 {
+      amount: args.amount,
+    },
+  )?;
+  ctx.accounts.position.reload()?;
+  let new_position =
+    PositionV0::try_deserialize(&mut &ctx.accounts.new_position.try_borrow_data()?[..])?;
+
+  let new_delegated_position = ctx.accounts.delegated_position.split_off(
+    total,
+    args.amount,
+    ctx.accounts.new_mint.key(),
+    ctx.accounts.new_position.key(),
+    ctx.bumps.new_delegated_position,
+  );
+  let after = DelegatedVehnt::new(
+    &ctx.accounts.position,
+    voting_mint_config,
+    &ctx.accounts.delegated_position,
+    curr_ts,
+  )?
+  .checked_add(&DelegatedVehnt::new(
+    &new_position,
+    voting_mint_config,
+    &new_delegated_position,
+    curr_ts,
+  )?)
+  .ok_or_else(|| error!(ErrorCode::ArithmeticError))?;
+  let expiration_ts = new_delegated_position.expiration_ts;
+  ctx
+    .accounts
+    .new_delegated_position
+    .set_inner(new_delegated_position);
+
+  let sub_dao_epoch_info = &mut ctx.accounts.sub_dao_epoch_info;
+  sub_dao_epoch_info.epoch = curr_epoch;
+  sub_dao_epoch_info.sub_dao = ctx.accounts.sub_dao.key();
+  sub_dao_epoch_info.bump_seed = ctx.bumps.sub_dao_epoch_info;
+  sub_dao_epoch_info.initialized = true;
+
+  // Positions expiring this epoch were already purged from the sub dao
+  if expiration_ts < i64::try_from(curr_epoch + 1).unwrap() * EPOCH_LENGTH {
+    return Ok(());
+  }
+
+  replace_delegated_vehnt(
+    &mut ctx.accounts.sub_dao,
+    sub_dao_epoch_info,
+    &mut ctx.accounts.closing_time_sub_dao_epoch_info,
+    curr_ts,
+    before,
+    after,
+  )
+}
@@ -3,9 +3,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::Mint;
 use modular_governance::proposal::accounts::ProposalV0;
-use shared_utils::try_from;
+use shared_utils::{resize_to_fit, try_from};
 use voter_stake_registry::{
-  state::{PositionV0, Registrar, VoteMarkerV0},
+  state::{PositionV0, RecentProposal, Registrar, VoteMarkerV0},
   VoterStakeRegistry,
 };
 
@@ -74,6 +74,7 @@ pub fn handler(ctx: Context<TrackVoteV0>) -> Result<()> {
     ctx.accounts.proposal.created_at,
   );
   ctx.accounts.dao_epoch_info.recent_proposals = ctx.accounts.dao.recent_proposals.clone();
+  ctx.accounts.dao_epoch_info.participation_policy = ctx.accounts.dao.participation_policy.clone();
   let data = ctx.accounts.marker.data.try_borrow().unwrap();
   let has_data = !data.is_empty();
   drop(data);
@@ -84,6 +85,22 @@ pub fn handler(ctx: Context<TrackVoteV0>) -> Result<()> {
       ctx.accounts.position.registrar,
       ErrorCode::InvalidMarker
     );
+
+    // Remember proxy cast votes so participation policies that ignore them can
+    let oldest_ts = ctx.accounts.dao.recent_proposals.last().unwrap().ts;
+    ctx.accounts.delegated_position.track_proxy_vote(
+      RecentProposal {
+        proposal: ctx.accounts.proposal.key(),
+        ts: ctx.accounts.proposal.created_at,
+      },
+      marker.proxy_index > 0 && !marker.choices.is_empty(),
+      oldest_ts,
+    );
+    resize_to_fit(
+      &ctx.accounts.payer.to_account_info(),
+      &ctx.accounts.system_program.to_account_info(),
+      &ctx.accounts.delegated_position,
+    )?;
   }
 
   Ok(())
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use shared_utils::resize_to_fit;
+use voter_stake_registry::state::PositionV0;
+
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateDelegatedPositionArgsV0 {
+  pub compound_rewards: Option<bool>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateDelegatedPositionV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  #[account(
+    seeds = [b"position".as_ref(), mint.key().as_ref()],
+    seeds::program = voter_stake_registry::ID,
+    bump = position.bump_seed,
+    has_one = mint,
+  )]
+  pub position: Box<Account<'info, PositionV0>>,
+  pub mint: Box<Account<'info, Mint>>,
+  #[account(
+    token::mint = mint,
+    token::authority = position_authority,
+    constraint = position_token_account.amount > 0
+  )]
+  pub position_token_account: Box<Account<'info, TokenAccount>>,
+  pub position_authority: Signer<'info>,
+  #[account(
+    mut,
+    has_one = position,
+    seeds = ["delegated_position".as_bytes(), position.key().as_ref()],
+    bump = delegated_position.bump_seed,
+  )]
+  pub delegated_position: Box<Account<'info, DelegatedPositionV0>>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<UpdateDelegatedPositionV0>,
+  args: UpdateDelegatedPositionArgsV0,
+) -> Result<()> {
+  if let Some(compound_rewards) = args.compound_rewards {
+    ctx.accounts.delegated_position.compound_rewards = compound_rewards;
+  }
+
+  resize_to_fit(
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.delegated_position,
+  )?;
+
+  Ok(())
+}
@@ -108,6 +108,7 @@ pub fn handler(ctx: Context<InitializeDaoV0>, args: InitializeDaoArgsV0) -> Resu
     rewards_escrow: ctx.accounts.rewards_escrow.key(),
     recent_proposals: array::from_fn(|_| RecentProposal::default()),
     proposal_namespace: args.proposal_namespace,
+    participation_policy: None,
   });
   initialize_account_windowed_breaker_v0(
     CpiContext::new_with_signer(
@@ -127,30 +127,48 @@ impl<'info> IssueRewardsV0<'info> {
   }
 }
 
-pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result<()> {
-  let curr_ts = Clock::get()?.unix_timestamp;
-  let curr_ts_epoch = current_epoch(curr_ts);
-  let end_of_epoch_ts = i64::try_from(args.epoch + 1).unwrap() * EPOCH_LENGTH;
+/// Inputs to a sub dao's share of the epoch's rewards, as read by `issue_rewards_v0`.
+pub struct SubDaoRewardsInput {
+  pub utility_score: u128,
+  pub total_utility_score: u128,
+  /// Smoothed percent share set by the previous epoch, scaled by `u32::MAX`.
+  pub prev_percentage: u32,
+  /// DAO-level `total_rewards` of the epoch.
+  pub total_emissions: u64,
+  pub hst_percent: u8,
+  pub delegator_rewards_percent: u64,
+  pub deployer_cap_hnt: u64,
+  /// Whether the HIP 149 earnings cap applies to this sub dao.
+  pub is_mobile: bool,
+}
 
-  if !TESTING && args.epoch >= curr_ts_epoch {
-    return Err(error!(ErrorCode::EpochNotOver));
-  }
+/// A sub dao's share of the epoch's rewards and how it is split between the delegator
+/// pool and the rewards escrow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SubDaoRewards {
+  /// Smoothed percent share of this epoch, scaled by `u32::MAX`.
+  pub previous_percentage: u32,
+  pub rewards_amount: u64,
+  pub delegation_rewards_amount: u64,
+  /// HIP 149 earnings-cap overflow moved from the escrow to the delegator pool.
+  pub staker_overflow: u64,
+  pub delegation_pool_amount: u64,
+  pub escrow_amount: u64,
+}
 
-  let utility_score = to_prec(ctx.accounts.sub_dao_epoch_info.utility_score)
-    .ok_or_else(|| error!(ErrorCode::NoUtilityScore))?;
-  let total_utility_score = to_prec(Some(ctx.accounts.dao_epoch_info.total_utility_score))
-    .ok_or_else(|| error!(ErrorCode::NoUtilityScore))?;
+pub fn calculate_sub_dao_rewards(input: &SubDaoRewardsInput) -> Result<SubDaoRewards> {
+  let utility_score = to_prec(Some(input.utility_score)).or_arith_error()?;
+  let total_utility_score = to_prec(Some(input.total_utility_score)).or_arith_error()?;
 
   let percent_share_pre_smooth = utility_score
     .checked_div(&total_utility_score)
     .or_arith_error()?;
 
   // Convert previous percentage from u32 to PreciseNumber (divide by u32::MAX)
-  let prev_percentage =
-    PreciseNumber::new(ctx.accounts.prev_sub_dao_epoch_info.previous_percentage as u128)
-      .or_arith_error()?
-      .checked_div(&PreciseNumber::new(u32::MAX as u128).or_arith_error()?)
-      .or_arith_error()?;
+  let prev_percentage = PreciseNumber::new(input.prev_percentage as u128)
+    .or_arith_error()?
+    .checked_div(&PreciseNumber::new(u32::MAX as u128).or_arith_error()?)
+    .or_arith_error()?;
 
   let percent_share = prev_percentage
     .checked_mul(&PreciseNumber::new(SMOOTHING_FACTOR - 1).or_arith_error()?)
@@ -166,7 +184,7 @@ pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result
     )
     .or_arith_error()?;
 
-  ctx.accounts.sub_dao_epoch_info.previous_percentage = percent_share
+  let previous_percentage = percent_share
     .checked_mul(&PreciseNumber::new(u32::MAX as u128).or_arith_error()?)
     .or_arith_error()?
     .to_imprecise()
@@ -174,18 +192,11 @@ pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result
     .try_into()
     .unwrap();
 
-  let total_emissions = ctx.accounts.dao_epoch_info.total_rewards;
-  let hst_percent = ctx
-    .accounts
-    .dao
-    .hst_emission_schedule
-    .get_percent_at(end_of_epoch_ts)
-    .unwrap();
   // Subdaos get the remainder after hst
   let emissions = 100_u64
-    .checked_sub(hst_percent.into())
+    .checked_sub(input.hst_percent.into())
     .unwrap()
-    .checked_mul(total_emissions)
+    .checked_mul(input.total_emissions)
     .unwrap()
     .checked_div(100)
     .unwrap();
@@ -201,7 +212,7 @@ pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result
   let max_percent = 100_u64.checked_mul(10_0000000).unwrap();
 
   let delegation_rewards_amount: u64 = (rewards_amount as u128)
-    .checked_mul(u128::from(ctx.accounts.dao.delegator_rewards_percent))
+    .checked_mul(u128::from(input.delegator_rewards_percent))
     .unwrap()
     .checked_div(max_percent as u128) // 100% with 2 decimals accuracy
     .unwrap()
@@ -216,14 +227,13 @@ pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result
   // unchanged, the HNT is paid to veHNT delegators instead of deployers, and staker
   // claims already key off the DAO-level delegation_rewards_issued. A zero ceiling
   // (no carrier burn this epoch, or no price oracle) disables the redirect.
-  let is_mobile = TESTING || ctx.accounts.sub_dao.key() == crate::backstop::MOBILE_SUB_DAO;
   let escrow_before_overflow = rewards_amount
     .checked_sub(delegation_rewards_amount)
     .unwrap();
-  let staker_overflow: u64 = if is_mobile {
+  let staker_overflow: u64 = if input.is_mobile {
     crate::backstop::staker_overflow(
       rewards_amount,
-      ctx.accounts.dao_epoch_info.deployer_cap_hnt,
+      input.deployer_cap_hnt,
       escrow_before_overflow,
     )
   } else {
@@ -233,6 +243,55 @@ pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result
   let delegation_pool_amount = delegation_rewards_amount
     .checked_add(staker_overflow)
     .unwrap();
+  let escrow_amount = escrow_before_overflow.checked_sub(staker_overflow).unwrap();
+
+  Ok(SubDaoRewards {
+    previous_percentage,
+    rewards_amount,
+    delegation_rewards_amount,
+    staker_overflow,
+    delegation_pool_amount,
+    escrow_amount,
+  })
+}
+
+pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result<()> {
+  let curr_ts = Clock::get()?.unix_timestamp;
+  let curr_ts_epoch = current_epoch(curr_ts);
+  let end_of_epoch_ts = i64::try_from(args.epoch + 1).unwrap() * EPOCH_LENGTH;
+
+  if !TESTING && args.epoch >= curr_ts_epoch {
+    return Err(error!(ErrorCode::EpochNotOver));
+  }
+
+  let utility_score = ctx
+    .accounts
+    .sub_dao_epoch_info
+    .utility_score
+    .ok_or_else(|| error!(ErrorCode::NoUtilityScore))?;
+  let hst_percent = ctx
+    .accounts
+    .dao
+    .hst_emission_schedule
+    .get_percent_at(end_of_epoch_ts)
+    .unwrap();
+  let is_mobile = TESTING || ctx.accounts.sub_dao.key() == crate::backstop::MOBILE_SUB_DAO;
+  let SubDaoRewards {
+    previous_percentage,
+    delegation_pool_amount,
+    escrow_amount,
+    ..
+  } = calculate_sub_dao_rewards(&SubDaoRewardsInput {
+    utility_score,
+    total_utility_score: ctx.accounts.dao_epoch_info.total_utility_score,
+    prev_percentage: ctx.accounts.prev_sub_dao_epoch_info.previous_percentage,
+    total_emissions: ctx.accounts.dao_epoch_info.total_rewards,
+    hst_percent,
+    delegator_rewards_percent: ctx.accounts.dao.delegator_rewards_percent,
+    deployer_cap_hnt: ctx.accounts.dao_epoch_info.deployer_cap_hnt,
+    is_mobile,
+  })?;
+  ctx.accounts.sub_dao_epoch_info.previous_percentage = previous_percentage;
 
   if delegation_pool_amount > 0 {
     msg!("Minting {} delegation rewards", delegation_pool_amount);
@@ -247,7 +306,6 @@ pub fn handler(ctx: Context<IssueRewardsV0>, args: IssueRewardsArgsV0) -> Result
     )?;
   }
 
-  let escrow_amount = escrow_before_overflow.checked_sub(staker_overflow).unwrap();
   msg!("Minting {} to rewards escrow", escrow_amount);
   mint_v0(
     ctx
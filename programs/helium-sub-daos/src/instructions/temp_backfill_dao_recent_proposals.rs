@@ -19,5 +19,6 @@ pub struct TempBackfillDaoRecentProposals<'info> {
 
 pub fn handler(ctx: Context<TempBackfillDaoRecentProposals>) -> Result<()> {
   ctx.accounts.dao_epoch_info.recent_proposals = ctx.accounts.dao.recent_proposals.clone();
+  ctx.accounts.dao_epoch_info.participation_policy = ctx.accounts.dao.participation_policy.clone();
   Ok(())
 }
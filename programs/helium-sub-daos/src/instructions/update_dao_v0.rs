@@ -13,6 +13,7 @@ pub struct UpdateDaoArgsV0 {
   pub proposal_namespace: Option<Pubkey>,
   pub delegator_rewards_percent: Option<u64>,
   pub rewards_escrow: Option<Pubkey>,
+  pub participation_policy: Option<ParticipationPolicyV0>,
 }
 
 #[derive(Accounts)]
@@ -71,6 +72,12 @@ pub fn handler(ctx: Context<UpdateDaoV0>, args: UpdateDaoArgsV0) -> Result<()> {
     ctx.accounts.dao.delegator_rewards_percent = delegator_rewards_percent;
   }
 
+  if let Some(participation_policy) = args.participation_policy {
+    participation_policy.validate()?;
+    ctx.accounts.dao.participation_policy = Some(participation_policy);
+    should_resize = true;
+  }
+
   if should_resize {
     resize_to_fit(
       &ctx.accounts.payer.to_account_info(),
@@ -6,13 +6,16 @@ declare_id!("hdaoVTCqhfHHo75XdAMxBKdUqvq1i5bF23sisBqVgGR");
 
 pub mod backstop;
 pub mod create_account;
+pub mod delegation_rewards;
 pub mod error;
 pub mod instructions;
+pub mod simulator;
 pub mod state;
 pub mod supplement;
 pub mod utils;
 
 pub use backstop::*;
+pub use delegation_rewards::*;
 pub use instructions::*;
 pub use state::*;
 pub use supplement::*;
@@ -104,6 +107,40 @@ pub mod helium_sub_daos {
     claim_rewards_v1::handler(ctx, args)
   }
 
+  pub fn batch_claim_rewards_v0<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchClaimRewardsV0<'info>>,
+  ) -> Result<()> {
+    batch_claim_rewards_v0::handler(ctx)
+  }
+
+  pub fn get_pending_rewards_v0<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetPendingRewardsV0<'info>>,
+  ) -> Result<PendingRewardsV0> {
+    get_pending_rewards_v0::handler(ctx)
+  }
+
+  pub fn update_delegated_position_v0(
+    ctx: Context<UpdateDelegatedPositionV0>,
+    args: UpdateDelegatedPositionArgsV0,
+  ) -> Result<()> {
+    update_delegated_position_v0::handler(ctx, args)
+  }
+
+  pub fn split_delegated_position_v0(
+    ctx: Context<SplitDelegatedPositionV0>,
+    args: SplitDelegatedPositionArgsV0,
+  ) -> Result<()> {
+    split_delegated_position_v0::handler(ctx, args)
+  }
+
+  pub fn merge_delegated_positions_v0(ctx: Context<MergeDelegatedPositionsV0>) -> Result<()> {
+    merge_delegated_positions_v0::handler(ctx)
+  }
+
+  pub fn merge_positions_v0(ctx: Context<MergePositionsV0>) -> Result<()> {
+    merge_positions_v0::handler(ctx)
+  }
+
   pub fn transfer_v0(ctx: Context<TransferV0>, args: TransferArgsV0) -> Result<()> {
     transfer_v0::handler(ctx, args)
   }
@@ -0,0 +1,429 @@
+//! Offline projection of the per-epoch emission economics.
+//!
+//! Replays what `calculate_utility_score_v0` and `issue_rewards_v0` do at each epoch end,
+//! through the same pure functions the instructions call: the HIP 20 emission schedule and
+//! net-emissions re-emit, the HIP 149 backstop (`backstop.rs`) and the supplement
+//! (`supplement.rs`). Nothing here touches accounts, so token-economics proposals can be
+//! backed by the program's own math over any DC burn and price series.
+//!
+//! Two things the chain measures have to be assumed instead:
+//!
+//! - HNT burned in an epoch defaults to the DC burned that epoch converted at the
+//!   confidence-adjusted price, i.e. DC are assumed to be minted as they are burned.
+//! - The epoch end crank runs right at the epoch boundary, which is the timestamp the
+//!   supplement is sized from.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+  backstop::{compute_backstop, dormant_backstop, scale_dc_to_hnt, BackstopInput, BackstopOutput},
+  backstop_prices, calculate_sub_dao_rewards,
+  error::ErrorCode,
+  next_smoothed_hnt_burned,
+  state::*,
+  supplement::{council_cut, supplement_per_subdao},
+  utility_score_from_vehnt, SubDaoRewards, SubDaoRewardsInput, EPOCH_LENGTH,
+  INITIAL_SMOOTHED_HNT_BURNED,
+};
+
+/// The DAO parameters the emission math reads.
+#[derive(Clone, Default)]
+pub struct SimulationConfig {
+  pub emission_schedule: Vec<EmissionScheduleItem>,
+  pub hst_emission_schedule: Vec<PercentItem>,
+  pub net_emissions_cap: u64,
+  pub delegator_rewards_percent: u64,
+  pub hnt_decimals: u8,
+}
+
+impl SimulationConfig {
+  pub fn from_dao(dao: &DaoV0, hnt_decimals: u8) -> Self {
+    Self {
+      emission_schedule: dao.emission_schedule.clone(),
+      hst_emission_schedule: dao.hst_emission_schedule.clone(),
+      net_emissions_cap: dao.net_emissions_cap,
+      delegator_rewards_percent: dao.delegator_rewards_percent,
+      hnt_decimals,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SimulationSubDao {
+  /// Whether this is the Mobile sub dao, whose signals drive the HIP 149 backstop.
+  pub is_mobile: bool,
+  pub vehnt_at_epoch_start: u64,
+  /// Smoothed percent share set by the last issued epoch, scaled by `u32::MAX`.
+  pub previous_percentage: u32,
+}
+
+/// State carried from one epoch end to the next.
+#[derive(Debug, Clone, Default)]
+pub struct SimulationState {
+  /// Next epoch to simulate.
+  pub epoch: u64,
+  /// `current_hnt_supply` recorded by the previous epoch.
+  pub hnt_supply: u64,
+  pub smoothed_hnt_burned: u64,
+  pub sub_daos: Vec<SimulationSubDao>,
+}
+
+/// Pyth HNT/USD EMA price, as read by `calculate_utility_score_v0`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HntPrice {
+  pub ema_price: i64,
+  pub ema_conf: u64,
+  pub exponent: i32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SubDaoEpochInput {
+  pub dc_burned: u64,
+  /// Replaces the sub dao's delegated veHNT from this epoch on.
+  pub vehnt_at_epoch_start: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EpochInput {
+  /// One entry per sub dao, in the order of `SimulationState::sub_daos`.
+  pub sub_daos: Vec<SubDaoEpochInput>,
+  /// `None` leaves the backstop dormant, as a missing or untrusted price does on-chain.
+  pub hnt_price: Option<HntPrice>,
+  /// HNT burned during the epoch. Defaults to the epoch's DC burn at the price floor.
+  pub hnt_burned: Option<u64>,
+  /// HNT burned through the no-emit program, which does not count as burn.
+  pub not_emitted: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedSubDaoEpoch {
+  pub utility_score: u128,
+  pub rewards: SubDaoRewards,
+  /// Supplement minted to the Receiving Entity vault on this sub dao's pass.
+  pub supplement: u64,
+  /// Council compensation carved out of this sub dao's supplement.
+  pub council: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedEpoch {
+  pub epoch: u64,
+  pub emission: u64,
+  pub total_hnt_burned: u64,
+  pub smoothed_hnt_burned: u64,
+  pub backstop: BackstopOutput,
+  pub sub_daos: Vec<SimulatedSubDaoEpoch>,
+  /// Minted to the delegator pool, earnings-cap overflow included.
+  pub delegation_rewards_issued: u64,
+  /// Earnings-cap overflow moved from deployers to the delegator pool.
+  pub staker_overflow: u64,
+  /// Supplement minted across all passes, Council compensation included.
+  pub supplement_minted: u64,
+  pub current_hnt_supply: u64,
+}
+
+/// Simulates one epoch end and advances `state` to the next epoch.
+///
+/// Panics if `input` does not have one entry per sub dao of `state`.
+pub fn simulate_epoch(
+  config: &SimulationConfig,
+  state: &mut SimulationState,
+  input: &EpochInput,
+) -> Result<SimulatedEpoch> {
+  assert_eq!(
+    input.sub_daos.len(),
+    state.sub_daos.len(),
+    "one input per sub dao"
+  );
+  let epoch = state.epoch;
+  let end_of_epoch_ts = i64::try_from(epoch + 1).unwrap() * EPOCH_LENGTH;
+  let num_sub_daos = u64::try_from(state.sub_daos.len()).unwrap();
+
+  for (sub_dao, sub_dao_input) in state.sub_daos.iter_mut().zip(input.sub_daos.iter()) {
+    if let Some(vehnt) = sub_dao_input.vehnt_at_epoch_start {
+      sub_dao.vehnt_at_epoch_start = vehnt;
+    }
+  }
+
+  let prices = input.hnt_price.and_then(|price| {
+    backstop_prices(
+      config.hnt_decimals,
+      price.exponent,
+      price.ema_price,
+      price.ema_conf,
+    )
+  });
+
+  // HIP 20 net emissions, from the supply change as calculate_utility_score_v0 sees it
+  let hnt_burned = input.hnt_burned.unwrap_or_else(|| {
+    prices.map_or(0, |(decimals_factor, hnt_price_floor, _)| {
+      let dc_burned: u128 = input.sub_daos.iter().map(|s| s.dc_burned as u128).sum();
+      scale_dc_to_hnt(dc_burned, decimals_factor, hnt_price_floor)
+    })
+  });
+  let prev_supply = state.hnt_supply;
+  let curr_supply = prev_supply
+    .saturating_sub(hnt_burned)
+    .saturating_sub(input.not_emitted);
+  let total_hnt_burned = prev_supply
+    .saturating_sub(curr_supply)
+    .saturating_sub(input.not_emitted);
+  let prev_smoothed_hnt_burned = if state.smoothed_hnt_burned == 0 {
+    INITIAL_SMOOTHED_HNT_BURNED
+  } else {
+    state.smoothed_hnt_burned
+  };
+  let smoothed_hnt_burned = next_smoothed_hnt_burned(prev_smoothed_hnt_burned, total_hnt_burned);
+
+  let emission = config
+    .emission_schedule
+    .get_emissions_at(end_of_epoch_ts)
+    .ok_or_else(|| error!(ErrorCode::EpochTooEarly))?;
+  let mobile = state
+    .sub_daos
+    .iter()
+    .zip(input.sub_daos.iter())
+    .find(|(sub_dao, _)| sub_dao.is_mobile);
+  let backstop = match (prices, mobile) {
+    (Some((decimals_factor, hnt_price_floor, hnt_price_cap)), Some((mobile, mobile_input))) => {
+      compute_backstop(&BackstopInput {
+        emission,
+        smoothed_hnt_burned,
+        net_emissions_cap: config.net_emissions_cap,
+        mobile_dc_burned: mobile_input.dc_burned,
+        mobile_share: mobile.previous_percentage,
+        decimals_factor,
+        hnt_price_floor,
+        hnt_price_cap,
+      })
+    }
+    _ => dormant_backstop(emission, smoothed_hnt_burned, config.net_emissions_cap),
+  };
+
+  let supplement = supplement_per_subdao(end_of_epoch_ts);
+  let council = council_cut(supplement);
+  let supplement_minted = supplement.saturating_mul(num_sub_daos);
+  let current_hnt_supply = curr_supply
+    .checked_add(backstop.total_rewards)
+    .unwrap()
+    .checked_add(supplement_minted)
+    .unwrap();
+
+  let utility_scores = state
+    .sub_daos
+    .iter()
+    .map(|sub_dao| utility_score_from_vehnt(sub_dao.vehnt_at_epoch_start))
+    .collect::<Vec<_>>();
+  let total_utility_score: u128 = utility_scores.iter().sum();
+  let hst_percent = config
+    .hst_emission_schedule
+    .get_percent_at(end_of_epoch_ts)
+    .unwrap_or(0);
+
+  let mut sub_daos = Vec::with_capacity(state.sub_daos.len());
+  for (sub_dao, utility_score) in state.sub_daos.iter_mut().zip(utility_scores) {
+    let rewards = calculate_sub_dao_rewards(&SubDaoRewardsInput {
+      utility_score,
+      total_utility_score,
+      prev_percentage: sub_dao.previous_percentage,
+      total_emissions: backstop.total_rewards,
+      hst_percent,
+      delegator_rewards_percent: config.delegator_rewards_percent,
+      deployer_cap_hnt: backstop.deployer_cap_hnt,
+      is_mobile: sub_dao.is_mobile,
+    })?;
+    sub_dao.previous_percentage = rewards.previous_percentage;
+    sub_daos.push(SimulatedSubDaoEpoch {
+      utility_score,
+      rewards,
+      supplement: supplement.saturating_sub(council),
+      council,
+    });
+  }
+
+  state.epoch = epoch + 1;
+  state.hnt_supply = current_hnt_supply;
+  state.smoothed_hnt_burned = smoothed_hnt_burned;
+
+  Ok(SimulatedEpoch {
+    epoch,
+    emission,
+    total_hnt_burned,
+    smoothed_hnt_burned,
+    backstop,
+    delegation_rewards_issued: sub_daos
+      .iter()
+      .map(|s| s.rewards.delegation_pool_amount)
+      .sum(),
+    staker_overflow: sub_daos.iter().map(|s| s.rewards.staker_overflow).sum(),
+    sub_daos,
+    supplement_minted,
+    current_hnt_supply,
+  })
+}
+
+/// Simulates consecutive epochs starting at `state.epoch`, one per input.
+pub fn simulate(
+  config: &SimulationConfig,
+  mut state: SimulationState,
+  inputs: &[EpochInput],
+) -> Result<Vec<SimulatedEpoch>> {
+  inputs
+    .iter()
+    .map(|input| simulate_epoch(config, &mut state, input))
+    .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::inconsistent_digit_grouping)]
+mod tests {
+  use super::*;
+
+  // 2024-01-01, before the supplement window in every build
+  const START_EPOCH: u64 = 1_704_067_200 / EPOCH_LENGTH as u64;
+  const HNT: u64 = 100_000_000;
+
+  fn config() -> SimulationConfig {
+    SimulationConfig {
+      emission_schedule: vec![EmissionScheduleItem {
+        start_unix_time: 0,
+        emissions_per_epoch: 20_548 * HNT,
+      }],
+      hst_emission_schedule: vec![PercentItem {
+        start_unix_time: 0,
+        percent: 0,
+      }],
+      net_emissions_cap: 1_644 * HNT,
+      // 6% with 8 decimals
+      delegator_rewards_percent: 6_00000000,
+      hnt_decimals: 8,
+    }
+  }
+
+  fn state() -> SimulationState {
+    SimulationState {
+      epoch: START_EPOCH,
+      hnt_supply: 180_000_000 * HNT,
+      smoothed_hnt_burned: 9_100 * HNT,
+      sub_daos: vec![
+        SimulationSubDao {
+          is_mobile: false,
+          vehnt_at_epoch_start: 1_000 * HNT,
+          previous_percentage: u32::MAX / 2,
+        },
+        SimulationSubDao {
+          is_mobile: true,
+          vehnt_at_epoch_start: 1_000 * HNT,
+          previous_percentage: u32::MAX / 2,
+        },
+      ],
+    }
+  }
+
+  fn input(mobile_dc_burned: u64, price_dollars: Option<f64>) -> EpochInput {
+    EpochInput {
+      sub_daos: vec![
+        SubDaoEpochInput::default(),
+        SubDaoEpochInput {
+          dc_burned: mobile_dc_burned,
+          vehnt_at_epoch_start: None,
+        },
+      ],
+      hnt_price: price_dollars.map(|dollars| HntPrice {
+        ema_price: (dollars * 1e8) as i64,
+        ema_conf: 0,
+        exponent: -8,
+      }),
+      hnt_burned: None,
+      not_emitted: 0,
+    }
+  }
+
+  #[test]
+  fn test_dormant_backstop_without_price() {
+    let mut state = state();
+    let epoch = simulate_epoch(&config(), &mut state, &input(910_000_000, None)).unwrap();
+
+    assert_eq!(epoch.total_hnt_burned, 0);
+    assert_eq!(epoch.backstop.top_up, 0);
+    assert_eq!(epoch.backstop.deployer_cap_hnt, 0);
+    assert_eq!(epoch.staker_overflow, 0);
+    assert_eq!(
+      epoch.backstop.total_rewards,
+      epoch.emission + epoch.backstop.existing_re_emit
+    );
+    assert_eq!(epoch.supplement_minted, 0);
+    assert_eq!(state.epoch, START_EPOCH + 1);
+    assert_eq!(
+      state.hnt_supply,
+      180_000_000 * HNT + epoch.backstop.total_rewards
+    );
+  }
+
+  #[test]
+  fn test_rewards_never_exceed_total() {
+    let epochs = simulate(
+      &config(),
+      state(),
+      &(0..30)
+        .map(|_| input(910_000_000, Some(0.10)))
+        .collect::<Vec<_>>(),
+    )
+    .unwrap();
+
+    for epoch in epochs.iter() {
+      let issued: u64 = epoch
+        .sub_daos
+        .iter()
+        .map(|s| s.rewards.rewards_amount)
+        .sum();
+      assert!(issued <= epoch.backstop.total_rewards);
+      assert!(epoch.backstop.existing_re_emit + epoch.backstop.top_up <= epoch.smoothed_hnt_burned);
+      for sub_dao in epoch.sub_daos.iter() {
+        assert_eq!(
+          sub_dao.rewards.delegation_pool_amount + sub_dao.rewards.escrow_amount,
+          sub_dao.rewards.rewards_amount
+        );
+      }
+    }
+    // Carrier burn at a low price feeds the smoothed burn, and with it the top-up
+    assert!(epochs.last().unwrap().smoothed_hnt_burned > epochs[0].smoothed_hnt_burned);
+    assert!(epochs.last().unwrap().backstop.top_up > 0);
+  }
+
+  #[test]
+  fn test_earnings_cap_overflows_to_delegators() {
+    let mut state = state();
+    // Expensive HNT and little carrier burn puts the Mobile data bucket above the cap
+    let epoch = simulate_epoch(&config(), &mut state, &input(10_000_000, Some(5.0))).unwrap();
+
+    assert!(epoch.backstop.deployer_cap_hnt > 0);
+    assert_eq!(epoch.sub_daos[0].rewards.staker_overflow, 0);
+    assert!(epoch.sub_daos[1].rewards.staker_overflow > 0);
+    assert_eq!(
+      epoch.staker_overflow,
+      epoch.sub_daos[1].rewards.staker_overflow
+    );
+    assert_eq!(
+      epoch.delegation_rewards_issued,
+      epoch
+        .sub_daos
+        .iter()
+        .map(|s| s.rewards.delegation_rewards_amount)
+        .sum::<u64>()
+        + epoch.staker_overflow
+    );
+  }
+
+  #[test]
+  fn test_percent_share_follows_vehnt() {
+    let mut inputs = vec![input(0, None); 60];
+    inputs[0].sub_daos[1].vehnt_at_epoch_start = Some(3_000 * HNT);
+    let epochs = simulate(&config(), state(), &inputs).unwrap();
+
+    // The 30 epoch moving average approaches the 25/75 veHNT split
+    let last = epochs.last().unwrap();
+    assert!(last.sub_daos[1].rewards.previous_percentage > u32::MAX / 100 * 70);
+    assert!(last.sub_daos[1].rewards.rewards_amount > 2 * last.sub_daos[0].rewards.rewards_amount);
+  }
+}
@@ -106,6 +106,64 @@ pub struct DaoV0 {
   pub delegator_rewards_percent: u64, // number between 0 - (100_u64 * 100_000_000). The % of DNT rewards delegators receive with 8 decimal places of accuracy
   pub proposal_namespace: Pubkey,
   pub recent_proposals: [RecentProposal; 4],
+  // None applies ParticipationPolicyV0::default()
+  pub participation_policy: Option<ParticipationPolicyV0>,
+}
+
+// The most proposals a participation window can span, bounded by `recent_proposals`
+pub const MAX_PARTICIPATION_WINDOW: u8 = 4;
+
+/// Governance participation a delegated position needs to keep its delegation rewards.
+/// Rewards that are not paid out are burned.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ParticipationPolicyV0 {
+  // Number of the dao's most recent proposals that are considered
+  pub window: u8,
+  // Votes within the window needed for full rewards
+  pub required_votes: u8,
+  // Whether votes cast on the position's behalf by a proxy count towards participation
+  pub count_proxy_votes: bool,
+  // Percent of rewards paid out when the position cast `i` votes, for i < required_votes
+  pub partial_reward_percents: [u8; 4],
+}
+
+impl Default for ParticipationPolicyV0 {
+  // Voting on two of the last four proposals, all or nothing
+  fn default() -> Self {
+    Self {
+      window: 4,
+      required_votes: 2,
+      count_proxy_votes: true,
+      partial_reward_percents: [0; 4],
+    }
+  }
+}
+
+impl ParticipationPolicyV0 {
+  pub fn validate(&self) -> Result<()> {
+    require!(
+      self.window > 0 && self.window <= MAX_PARTICIPATION_WINDOW,
+      ErrorCode::InvalidParticipationPolicy
+    );
+    require_gte!(
+      self.window,
+      self.required_votes,
+      ErrorCode::InvalidParticipationPolicy
+    );
+    require!(
+      self.partial_reward_percents.iter().all(|p| *p <= 100),
+      ErrorCode::InvalidParticipationPolicy
+    );
+    Ok(())
+  }
+
+  pub fn reward_percent(&self, votes: u8) -> u8 {
+    if votes >= self.required_votes {
+      100
+    } else {
+      self.partial_reward_percents[votes as usize]
+    }
+  }
 }
 
 #[macro_export]
@@ -183,6 +241,8 @@ pub struct DaoEpochInfoV0 {
   // is not stored: it is recoverable as (dc_burned * 3 * decimals_factor) /
   // deployer_cap_hnt, or from the public Pyth feed at the epoch timestamp.
   pub deployer_cap_hnt: u64,
+  // Snapshot of the dao's participation policy alongside `recent_proposals`
+  pub participation_policy: Option<ParticipationPolicyV0>,
 }
 
 impl DaoEpochInfoV0 {
@@ -216,9 +276,106 @@ pub struct DelegatedPositionV0 {
   pub claimed_epochs_bitmap: u128,
   pub expiration_ts: i64,
   pub _deprecated_recent_proposals: Vec<RecentProposal>,
+  // Recent proposals this position only voted on through a proxy, recorded by track_vote_v0.
+  // Ignored for participation when the policy does not count proxy votes.
+  pub proxy_voted_proposals: Vec<RecentProposal>,
+  // When true, claims deposit rewards back into the position instead of the delegator's wallet
+  pub compound_rewards: bool,
+  // Rewards compounded into the position that epochs starting before them must not earn on
+  pub compounded_deposits: Vec<CompoundedDepositV0>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompoundedDepositV0 {
+  pub ts: i64,
+  pub amount: u64,
 }
 
 impl DelegatedPositionV0 {
+  // Amount compounded into the position after `ts`
+  pub fn compounded_after(&self, ts: i64) -> u64 {
+    self
+      .compounded_deposits
+      .iter()
+      .filter(|deposit| deposit.ts > ts)
+      .map(|deposit| deposit.amount)
+      .sum()
+  }
+
+  pub fn record_compounded_deposit(&mut self, ts: i64, amount: u64) {
+    // Every epoch still claimable starts after these deposits, so they no longer matter
+    let first_unclaimed_start_ts =
+      i64::try_from(self.last_claimed_epoch + 1).unwrap() * EPOCH_LENGTH;
+    self
+      .compounded_deposits
+      .retain(|deposit| deposit.ts > first_unclaimed_start_ts);
+    self
+      .compounded_deposits
+      .push(CompoundedDepositV0 { ts, amount });
+  }
+
+  // Splits `amount` out of the `total` hnt in this delegation into a new delegation with
+  // the same sub dao, expiration and claim state. Compounded deposits are split proportionally.
+  pub fn split_off(
+    &mut self,
+    total: u64,
+    amount: u64,
+    mint: Pubkey,
+    position: Pubkey,
+    bump_seed: u8,
+  ) -> DelegatedPositionV0 {
+    let mut piece_deposits = Vec::with_capacity(self.compounded_deposits.len());
+    for deposit in self.compounded_deposits.iter_mut() {
+      let piece_amount =
+        u64::try_from(u128::from(deposit.amount) * u128::from(amount) / u128::from(total)).unwrap();
+      deposit.amount -= piece_amount;
+      piece_deposits.push(CompoundedDepositV0 {
+        ts: deposit.ts,
+        amount: piece_amount,
+      });
+    }
+    self.hnt_amount = total - amount;
+
+    DelegatedPositionV0 {
+      mint,
+      position,
+      hnt_amount: amount,
+      bump_seed,
+      _deprecated_recent_proposals: vec![],
+      compounded_deposits: piece_deposits,
+      ..self.clone()
+    }
+  }
+
+  // Folds `other` into this delegation. Both must share the same claim state.
+  pub fn merge(&mut self, other: &DelegatedPositionV0) {
+    self.hnt_amount += other.hnt_amount;
+    self
+      .compounded_deposits
+      .extend(other.compounded_deposits.iter().cloned());
+    // A proxy vote from either side still counts against the merged position
+    for proposal in &other.proxy_voted_proposals {
+      if !self
+        .proxy_voted_proposals
+        .iter()
+        .any(|p| p.proposal == proposal.proposal)
+      {
+        self.proxy_voted_proposals.push(proposal.clone());
+      }
+    }
+  }
+
+  // Tracks whether the latest vote on `proposal` was cast by a proxy, forgetting
+  // proposals older than `oldest_ts`
+  pub fn track_proxy_vote(&mut self, proposal: RecentProposal, is_proxy: bool, oldest_ts: i64) {
+    self
+      .proxy_voted_proposals
+      .retain(|p| p.proposal != proposal.proposal && p.ts >= oldest_ts);
+    if is_proxy && proposal.ts >= oldest_ts {
+      self.proxy_voted_proposals.push(proposal);
+    }
+  }
+
   pub fn is_claimed(&self, epoch: u64) -> Result<bool> {
     if epoch <= self.last_claimed_epoch {
       Ok(true)
@@ -338,6 +495,71 @@ pub struct SubDaoV0 {
 mod tests {
   use super::*;
 
+  #[test]
+  fn test_compounded_deposits() {
+    let mut position = DelegatedPositionV0 {
+      last_claimed_epoch: 1,
+      ..Default::default()
+    };
+    position.record_compounded_deposit(EPOCH_LENGTH * 2 + 10, 100);
+    position.record_compounded_deposit(EPOCH_LENGTH * 3 + 10, 50);
+    assert_eq!(position.compounded_after(EPOCH_LENGTH * 2), 150);
+    assert_eq!(position.compounded_after(EPOCH_LENGTH * 3), 50);
+    assert_eq!(position.compounded_after(EPOCH_LENGTH * 4), 0);
+
+    // Deposits before the first unclaimed epoch are forgotten
+    position.last_claimed_epoch = 3;
+    position.record_compounded_deposit(EPOCH_LENGTH * 4 + 10, 25);
+    assert_eq!(position.compounded_deposits.len(), 1);
+    assert_eq!(position.compounded_after(0), 25);
+  }
+
+  #[test]
+  fn test_split_and_merge_delegated_position() {
+    let mut position = DelegatedPositionV0 {
+      hnt_amount: 1000,
+      last_claimed_epoch: 1,
+      claimed_epochs_bitmap: 1 << 127,
+      ..Default::default()
+    };
+    position.record_compounded_deposit(EPOCH_LENGTH * 2 + 10, 100);
+
+    let piece = position.split_off(1000, 250, Pubkey::new_unique(), Pubkey::new_unique(), 1);
+    assert_eq!(position.hnt_amount, 750);
+    assert_eq!(piece.hnt_amount, 250);
+    assert_eq!(piece.last_claimed_epoch, 1);
+    assert_eq!(piece.claimed_epochs_bitmap, 1 << 127);
+    assert_eq!(position.compounded_after(0), 75);
+    assert_eq!(piece.compounded_after(0), 25);
+
+    position.merge(&piece);
+    assert_eq!(position.hnt_amount, 1000);
+    assert_eq!(position.compounded_after(0), 100);
+  }
+
+  #[test]
+  fn test_merge_keeps_proxy_votes() {
+    let shared = Pubkey::new_unique();
+    let proxy_vote = |proposal: Pubkey, ts: i64| RecentProposal { proposal, ts };
+    let mut position = DelegatedPositionV0 {
+      proxy_voted_proposals: vec![proxy_vote(shared, 10)],
+      ..Default::default()
+    };
+    let other_only = Pubkey::new_unique();
+    let other = DelegatedPositionV0 {
+      proxy_voted_proposals: vec![proxy_vote(shared, 10), proxy_vote(other_only, 20)],
+      ..Default::default()
+    };
+
+    position.merge(&other);
+    let proposals: Vec<Pubkey> = position
+      .proxy_voted_proposals
+      .iter()
+      .map(|p| p.proposal)
+      .collect();
+    assert_eq!(proposals, vec![shared, other_only]);
+  }
+
   #[test]
   fn test_claimed() {
     let mut position = DelegatedPositionV0::default();
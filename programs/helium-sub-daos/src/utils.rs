@@ -238,6 +238,9 @@ impl PrecisePosition for PositionV0 {
         max_locked_vote_weight,
         lockup_saturation_secs,
       ),
+      // delegate_v0 refuses vesting positions, since the sub dao only tracks a single fall rate
+      // change when a position closes and vesting would need one per period
+      LockupKind::Daily | LockupKind::Monthly => Err(error!(ErrorCode::VestingPositionDelegation)),
     }
   }
 
@@ -407,6 +410,100 @@ pub fn caclulate_vhnt_info(
   })
 }
 
+/// The contribution of a delegated position to its sub dao, as added in `delegate_v0` and
+/// removed again in `close_delegation_v0`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DelegatedVehnt {
+  pub vehnt: u128,
+  pub fall_rate: u128,
+  pub end_fall_rate_correction: u128,
+  pub end_vehnt_correction: u128,
+}
+
+impl DelegatedVehnt {
+  pub fn new(
+    position: &PositionV0,
+    voting_mint_config: &VotingMintConfigV0,
+    delegated_position: &DelegatedPositionV0,
+    curr_ts: i64,
+  ) -> Result<Self> {
+    let info = caclulate_vhnt_info(
+      delegated_position.start_ts,
+      position,
+      voting_mint_config,
+      delegated_position.expiration_ts,
+    )?;
+    Ok(Self {
+      vehnt: position.voting_power_precise(voting_mint_config, curr_ts)?,
+      fall_rate: if current_epoch(curr_ts) >= current_epoch(position.genesis_end) {
+        info.post_genesis_end_fall_rate
+      } else {
+        info.pre_genesis_end_fall_rate
+      },
+      end_fall_rate_correction: info.end_fall_rate_correction,
+      end_vehnt_correction: info.end_vehnt_correction,
+    })
+  }
+
+  pub fn checked_add(&self, other: &Self) -> Option<Self> {
+    Some(Self {
+      vehnt: self.vehnt.checked_add(other.vehnt)?,
+      fall_rate: self.fall_rate.checked_add(other.fall_rate)?,
+      end_fall_rate_correction: self
+        .end_fall_rate_correction
+        .checked_add(other.end_fall_rate_correction)?,
+      end_vehnt_correction: self
+        .end_vehnt_correction
+        .checked_add(other.end_vehnt_correction)?,
+    })
+  }
+}
+
+/// Replaces the `before` contribution of delegated positions to the sub dao with `after`.
+///
+/// Used when the positions backing a delegation change (compounding, splitting, merging)
+/// while the lockup end and the closing epoch stay the same. Positions that still have
+/// the genesis multiplier also have corrections at genesis end, which are not handled here.
+pub fn replace_delegated_vehnt(
+  sub_dao: &mut SubDaoV0,
+  sub_dao_epoch_info: &mut Account<SubDaoEpochInfoV0>,
+  closing_time_sub_dao_epoch_info: &mut Account<SubDaoEpochInfoV0>,
+  curr_ts: i64,
+  before: DelegatedVehnt,
+  after: DelegatedVehnt,
+) -> Result<()> {
+  let curr_epoch = current_epoch(curr_ts);
+  sub_dao_epoch_info.epoch = curr_epoch;
+  update_subdao_vehnt(sub_dao, sub_dao_epoch_info, curr_ts)?;
+
+  sub_dao.vehnt_delegated = sub_dao
+    .vehnt_delegated
+    .checked_add(after.vehnt)
+    .ok_or_else(|| error!(ErrorCode::ArithmeticError))?
+    .saturating_sub(before.vehnt);
+  sub_dao.vehnt_fall_rate = sub_dao
+    .vehnt_fall_rate
+    .checked_add(after.fall_rate)
+    .and_then(|r| r.checked_sub(before.fall_rate))
+    .ok_or_else(|| error!(ErrorCode::ArithmeticError))?;
+
+  if closing_time_sub_dao_epoch_info.epoch > curr_epoch {
+    closing_time_sub_dao_epoch_info.fall_rates_from_closing_positions =
+      closing_time_sub_dao_epoch_info
+        .fall_rates_from_closing_positions
+        .checked_add(after.end_fall_rate_correction)
+        .and_then(|r| r.checked_sub(before.end_fall_rate_correction))
+        .ok_or_else(|| error!(ErrorCode::ArithmeticError))?;
+    closing_time_sub_dao_epoch_info.vehnt_in_closing_positions = closing_time_sub_dao_epoch_info
+      .vehnt_in_closing_positions
+      .checked_add(after.end_vehnt_correction)
+      .ok_or_else(|| error!(ErrorCode::ArithmeticError))?
+      .saturating_sub(before.end_vehnt_correction);
+  }
+
+  Ok(())
+}
+
 // Use bankers rounding
 pub fn apply_fall_rate_factor(item: u128) -> Option<u128> {
   let fall_rate_sub_one = FALL_RATE_FACTOR / 10;
@@ -43,4 +43,36 @@ pub enum ErrorCode {
 
   #[msg("Invalid approver")]
   InvalidApprover,
+
+  #[msg("Not enough oracles signed")]
+  InsufficientOracleSignatures,
+
+  #[msg("Rewards root period must be greater than the current one")]
+  InvalidRewardsPeriod,
+
+  #[msg("Lazy distributor has no rewards root")]
+  MissingRewardsRoot,
+
+  #[msg("Invalid rewards proof")]
+  InvalidRewardsProof,
+
+  #[msg("Oracles disagree on current rewards beyond the max spread")]
+  OracleDisagreement,
+
+  #[msg("Invalid quorum config")]
+  InvalidQuorumConfig,
+
+  #[msg(
+    "This recipient's rewards vest in its vesting escrow. Use distribute_vesting_destination_v0"
+  )]
+  VestingDestination,
+
+  #[msg("Vesting period must be positive")]
+  InvalidVestingPeriod,
+
+  #[msg("Rewards roots cannot be used while the quorum has a max spread")]
+  RewardsRootWithMaxSpread,
+
+  #[msg("Vesting escrow still has rewards vesting")]
+  VestingNotComplete,
 }
@@ -22,7 +22,7 @@ pub struct DistributeRewardsCommonV0<'info> {
   #[account(
     mut,
     has_one = lazy_distributor,
-    constraint = recipient.current_rewards.iter().flatten().count() >= lazy_distributor.oracles.len().div_ceil(2),
+    constraint = recipient.current_rewards.iter().flatten().count() >= lazy_distributor.min_signers(),
   )]
   pub recipient: Box<Account<'info, RecipientV0>>,
   pub rewards_mint: Box<Account<'info, Mint>>,
@@ -52,22 +52,17 @@ pub struct DistributeRewardsCommonV0<'info> {
   pub token_program: Program<'info, Token>,
 }
 
-pub fn distribute_impl(ctx: &mut DistributeRewardsCommonV0) -> Result<()> {
+/// Pays out the resolved current rewards of the recipient, returning the amount distributed
+pub fn distribute_impl(ctx: &mut DistributeRewardsCommonV0) -> Result<u64> {
   let seeds: &[&[&[u8]]] = &[&[
     b"lazy_distributor",
     ctx.lazy_distributor.rewards_mint.as_ref(),
     &[ctx.lazy_distributor.bump_seed],
   ]];
   let recipient = &mut ctx.recipient;
-  let mut filtered: Vec<u64> = recipient
-    .current_rewards
-    .iter()
-    .flatten()
-    .copied()
-    .collect();
-  filtered.sort_unstable();
-  let median_idx = filtered.len() / 2;
-  let median = filtered[median_idx];
+  let median = ctx
+    .lazy_distributor
+    .resolve_current_rewards(&recipient.current_rewards)?;
 
   recipient.current_rewards = vec![None; ctx.lazy_distributor.oracles.len()];
   let to_dist = median
@@ -96,5 +91,5 @@ pub fn distribute_impl(ctx: &mut DistributeRewardsCommonV0) -> Result<()> {
     )?;
   }
 
-  Ok(())
+  Ok(to_dist)
 }
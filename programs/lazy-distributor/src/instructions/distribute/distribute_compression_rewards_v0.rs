@@ -50,5 +50,7 @@ pub fn handler<'info>(
     ErrorCode::InvalidAsset
   );
 
-  distribute_impl(&mut ctx.accounts.common)
+  distribute_impl(&mut ctx.accounts.common)?;
+
+  Ok(())
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::error::ErrorCode;
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -12,6 +13,13 @@ pub fn handler(ctx: Context<DistributeCustomDestinationV0>) -> Result<()> {
     ctx.accounts.common.recipient.destination
   );
   require_neq!(ctx.accounts.common.recipient.destination, Pubkey::default());
+  // Vesting escrows are owned by this program and must account for what they receive
+  require!(
+    ctx.accounts.common.owner.owner != &crate::ID,
+    ErrorCode::VestingDestination
+  );
+
+  distribute_impl(&mut ctx.accounts.common)?;
 
-  distribute_impl(&mut ctx.accounts.common)
+  Ok(())
 }
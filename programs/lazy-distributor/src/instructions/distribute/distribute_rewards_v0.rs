@@ -20,5 +20,7 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, DistributeRewardsV0<'info>
     Pubkey::default(),
     ErrorCode::CustomDestination
   );
-  distribute_impl(&mut ctx.accounts.common)
+  distribute_impl(&mut ctx.accounts.common)?;
+
+  Ok(())
 }
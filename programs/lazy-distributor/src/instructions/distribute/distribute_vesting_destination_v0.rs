@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use super::common::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct DistributeVestingDestinationV0<'info> {
+  pub common: DistributeRewardsCommonV0<'info>,
+  #[account(
+    mut,
+    seeds = ["vesting_escrow".as_bytes(), common.recipient.key().as_ref()],
+    bump = vesting_escrow.bump_seed,
+    constraint = vesting_escrow.key() == common.owner.key(),
+    constraint = vesting_escrow.key() == common.recipient.destination,
+  )]
+  pub vesting_escrow: Box<Account<'info, VestingEscrowV0>>,
+}
+
+/// Distributes current rewards into the recipient's vesting escrow, adding them to its
+/// vesting schedule. The current owner of the asset claims them with claim_vesting_v0 as they vest.
+pub fn handler(ctx: Context<DistributeVestingDestinationV0>) -> Result<()> {
+  let amount = distribute_impl(&mut ctx.accounts.common)?;
+  ctx
+    .accounts
+    .vesting_escrow
+    .deposit(Clock::get()?.unix_timestamp, amount);
+
+  Ok(())
+}
@@ -2,8 +2,10 @@ pub mod common;
 pub mod distribute_compression_rewards_v0;
 pub mod distribute_custom_destination_v0;
 pub mod distribute_rewards_v0;
+pub mod distribute_vesting_destination_v0;
 
 pub use common::*;
 pub use distribute_compression_rewards_v0::*;
 pub use distribute_custom_destination_v0::*;
 pub use distribute_rewards_v0::*;
+pub use distribute_vesting_destination_v0::*;
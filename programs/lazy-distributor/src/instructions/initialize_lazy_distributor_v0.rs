@@ -91,6 +91,9 @@ pub fn handler(
     authority: args.authority,
     bump_seed: ctx.bumps.lazy_distributor,
     approver: args.approver,
+    rewards_root: None,
+    quorum: None,
+    last_rewards_period: 0,
   });
 
   Ok(())
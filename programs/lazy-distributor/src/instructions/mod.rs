@@ -2,16 +2,24 @@ pub mod distribute;
 pub mod initialize_compression_recipient_v0;
 pub mod initialize_lazy_distributor_v0;
 pub mod initialize_recipient_v0;
+pub mod prove_current_rewards_v0;
 pub mod set_current_rewards_v0;
 pub mod set_current_rewards_v1;
+pub mod set_rewards_root_v0;
 pub mod update_destination;
 pub mod update_lazy_distributor_v0;
+pub mod update_lazy_distributor_v1;
+pub mod vesting;
 
 pub use distribute::*;
 pub use initialize_compression_recipient_v0::*;
 pub use initialize_lazy_distributor_v0::*;
 pub use initialize_recipient_v0::*;
+pub use prove_current_rewards_v0::*;
 pub use set_current_rewards_v0::*;
 pub use set_current_rewards_v1::*;
+pub use set_rewards_root_v0::*;
 pub use update_destination::*;
 pub use update_lazy_distributor_v0::*;
+pub use update_lazy_distributor_v1::*;
+pub use vesting::*;
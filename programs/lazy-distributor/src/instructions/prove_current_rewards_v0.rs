@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use shared_utils::{merkle_proof::verify, resize_to_fit};
+
+use crate::{error::ErrorCode, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ProveCurrentRewardsArgsV0 {
+  pub current_rewards: u64,
+  pub index: u32,
+}
+
+#[derive(Accounts)]
+pub struct ProveCurrentRewardsV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub lazy_distributor: Box<Account<'info, LazyDistributorV0>>,
+  #[account(
+    mut,
+    has_one = lazy_distributor
+  )]
+  pub recipient: Box<Account<'info, RecipientV0>>,
+  pub system_program: Program<'info, System>,
+}
+
+/// Sets the current rewards of a recipient from the lazy distributor's rewards root, in
+/// place of every oracle. Pass the proof as remaining accounts, and follow with any of the
+/// distribute instructions to claim.
+pub fn handler(ctx: Context<ProveCurrentRewardsV0>, args: ProveCurrentRewardsArgsV0) -> Result<()> {
+  let lazy_distributor = &ctx.accounts.lazy_distributor;
+  // The max spread may have been configured after the root was set
+  lazy_distributor.require_rewards_root_allowed()?;
+  let rewards_root = lazy_distributor
+    .rewards_root
+    .as_ref()
+    .ok_or_else(|| error!(ErrorCode::MissingRewardsRoot))?;
+
+  let proof = ctx
+    .remaining_accounts
+    .iter()
+    .map(|account| account.key.to_bytes())
+    .collect::<Vec<_>>();
+  require!(
+    verify(
+      proof,
+      rewards_root.root,
+      RewardsRootV0::leaf(&ctx.accounts.recipient.asset, args.current_rewards),
+      args.index
+    ),
+    ErrorCode::InvalidRewardsProof
+  );
+  require_gte!(
+    args.current_rewards,
+    ctx.accounts.recipient.total_rewards,
+    ErrorCode::InvalidCurrentRewards
+  );
+
+  let recipient = &mut ctx.accounts.recipient;
+  recipient.current_config_version = lazy_distributor.version;
+  recipient.current_rewards = vec![Some(args.current_rewards); lazy_distributor.oracles.len()];
+
+  resize_to_fit(
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.recipient,
+  )?;
+
+  Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use shared_utils::resize_to_fit;
+
+use crate::{error::ErrorCode, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SetRewardsRootArgsV0 {
+  pub period: u64,
+  pub root: [u8; 32],
+}
+
+#[derive(Accounts)]
+pub struct SetRewardsRootV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  #[account(mut)]
+  pub lazy_distributor: Box<Account<'info, LazyDistributorV0>>,
+  pub system_program: Program<'info, System>,
+}
+
+/// Posts the cumulative rewards root for a new period.
+///
+/// The oracles (and the approver, if the lazy distributor has one) sign as remaining
+/// accounts. At least the quorum's minimum number of oracles must sign, and together they
+/// must hold more than half of the oracle weight. Roots are not available when the quorum
+/// has a max spread, since a single root cannot be checked against the other oracles.
+pub fn handler(ctx: Context<SetRewardsRootV0>, args: SetRewardsRootArgsV0) -> Result<()> {
+  let lazy_distributor = &mut ctx.accounts.lazy_distributor;
+  lazy_distributor.require_rewards_root_allowed()?;
+  let signers = ctx
+    .remaining_accounts
+    .iter()
+    .filter(|account| account.is_signer)
+    .map(|account| account.key())
+    .collect::<Vec<_>>();
+
+  if let Some(expected_approver) = lazy_distributor.approver {
+    require!(
+      signers.contains(&expected_approver),
+      ErrorCode::InvalidApproverSignature
+    );
+  }
+
+  lazy_distributor.require_signer_quorum(&signers)?;
+
+  // Distributors that had a root before `last_rewards_period` existed only track it there
+  let last_period = lazy_distributor
+    .rewards_root
+    .as_ref()
+    .map_or(0, |rewards_root| rewards_root.period)
+    .max(lazy_distributor.last_rewards_period);
+  require_gt!(args.period, last_period, ErrorCode::InvalidRewardsPeriod);
+  lazy_distributor.last_rewards_period = args.period;
+  lazy_distributor.rewards_root = Some(RewardsRootV0 {
+    period: args.period,
+    root: args.root,
+  });
+
+  resize_to_fit(
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.lazy_distributor,
+  )?;
+
+  Ok(())
+}
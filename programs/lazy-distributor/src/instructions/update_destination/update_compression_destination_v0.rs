@@ -47,6 +47,11 @@ pub fn handler<'info>(
     proof_accounts: ctx.remaining_accounts.to_vec(),
   })?;
 
+  require_neq!(
+    ctx.accounts.recipient.destination,
+    vesting_escrow_key(&ctx.accounts.recipient.key()),
+    ErrorCode::VestingDestination
+  );
   ctx.accounts.recipient.destination = ctx.accounts.destination.key();
 
   Ok(())
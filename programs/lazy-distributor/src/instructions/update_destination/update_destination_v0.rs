@@ -1,4 +1,4 @@
-use crate::state::*;
+use crate::{error::ErrorCode, state::*};
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
@@ -18,6 +18,11 @@ pub struct UpdateDestinationV0<'info> {
 }
 
 pub fn handler(ctx: Context<UpdateDestinationV0>) -> Result<()> {
+  require_neq!(
+    ctx.accounts.recipient.destination,
+    vesting_escrow_key(&ctx.accounts.recipient.key()),
+    ErrorCode::VestingDestination
+  );
   ctx.accounts.recipient.destination = ctx.accounts.destination.key();
 
   Ok(())
@@ -31,6 +31,8 @@ pub fn handler(
   let ld = &mut ctx.accounts.lazy_distributor;
   if let Some(oracles) = args.oracles {
     ld.oracles = oracles;
+    ld.validate_quorum()?;
+    ld.clear_rewards_root();
   }
   if let Some(authority) = args.authority {
     ld.authority = authority;
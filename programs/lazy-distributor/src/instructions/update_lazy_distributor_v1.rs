@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+use shared_utils::resize_to_fit;
+
+use crate::state::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateLazyDistributorArgsV1 {
+  pub oracles: Option<Vec<OracleConfigV0>>,
+  pub authority: Option<Pubkey>,
+  pub approver: Option<Option<Pubkey>>,
+  pub quorum: Option<Option<QuorumConfigV0>>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: UpdateLazyDistributorArgsV1)]
+pub struct UpdateLazyDistributorV1<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  #[account(
+    mut,
+    seeds = ["lazy_distributor".as_bytes(), rewards_mint.key().as_ref()],
+    bump,
+    has_one = authority,
+    has_one = rewards_mint
+  )]
+  pub lazy_distributor: Box<Account<'info, LazyDistributorV0>>,
+  pub rewards_mint: Box<Account<'info, Mint>>,
+  pub authority: Signer<'info>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+  ctx: Context<UpdateLazyDistributorV1>,
+  args: UpdateLazyDistributorArgsV1,
+) -> Result<()> {
+  let ld = &mut ctx.accounts.lazy_distributor;
+  if let Some(oracles) = args.oracles {
+    ld.oracles = oracles;
+    ld.clear_rewards_root();
+  }
+  if let Some(authority) = args.authority {
+    ld.authority = authority;
+  }
+  if let Some(approver) = args.approver {
+    ld.approver = approver;
+  }
+  if let Some(quorum) = args.quorum {
+    ld.quorum = quorum;
+    ld.clear_rewards_root();
+  }
+  ld.validate_quorum()?;
+
+  resize_to_fit(
+    &ctx.accounts.payer.to_account_info(),
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.lazy_distributor,
+  )?;
+
+  Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use super::common::*;
+
+#[derive(Accounts)]
+pub struct ClaimVestingV0<'info> {
+  pub common: ClaimVestingCommonV0<'info>,
+  #[account(
+    token::mint = common.recipient.asset,
+    constraint = recipient_mint_account.amount > 0,
+    constraint = recipient_mint_account.owner == common.owner.key()
+  )]
+  pub recipient_mint_account: Box<Account<'info, TokenAccount>>,
+}
+
+/// Transfers everything that has vested so far to the current owner of the asset
+pub fn handler(ctx: Context<ClaimVestingV0>) -> Result<()> {
+  claim_impl(&mut ctx.accounts.common)
+}
@@ -0,0 +1,48 @@
+use account_compression_cpi::account_compression::program::SplAccountCompression;
+use anchor_lang::prelude::*;
+use bubblegum_cpi::get_asset_id;
+use shared_utils::{verify_compressed_nft, VerifyCompressedNftArgs};
+
+use super::common::*;
+use crate::error::ErrorCode;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct CloseCompressionVestingEscrowArgsV0 {
+  pub data_hash: [u8; 32],
+  pub creator_hash: [u8; 32],
+  pub root: [u8; 32],
+  pub index: u32,
+}
+
+#[derive(Accounts)]
+pub struct CloseCompressionVestingEscrowV0<'info> {
+  pub common: ClaimVestingCommonV0<'info>,
+  /// CHECK: Checked via verify_compressed_nft
+  pub merkle_tree: UncheckedAccount<'info>,
+  pub compression_program: Program<'info, SplAccountCompression>,
+}
+
+/// Compressed asset version of close_vesting_escrow_v0
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, CloseCompressionVestingEscrowV0<'info>>,
+  args: CloseCompressionVestingEscrowArgsV0,
+) -> Result<()> {
+  require_eq!(
+    ctx.accounts.common.recipient.asset,
+    get_asset_id(&ctx.accounts.merkle_tree.key(), args.index.into()),
+    ErrorCode::InvalidAsset
+  );
+  verify_compressed_nft(VerifyCompressedNftArgs {
+    data_hash: args.data_hash,
+    creator_hash: args.creator_hash,
+    root: args.root,
+    index: args.index,
+    compression_program: ctx.accounts.compression_program.to_account_info(),
+    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+    owner: ctx.accounts.common.owner.key(),
+    delegate: ctx.accounts.common.owner.key(),
+    proof_accounts: ctx.remaining_accounts.to_vec(),
+  })?;
+
+  close_impl(&mut ctx.accounts.common)
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use super::common::*;
+
+#[derive(Accounts)]
+pub struct CloseVestingEscrowV0<'info> {
+  pub common: ClaimVestingCommonV0<'info>,
+  #[account(
+    token::mint = common.recipient.asset,
+    constraint = recipient_mint_account.amount > 0,
+    constraint = recipient_mint_account.owner == common.owner.key()
+  )]
+  pub recipient_mint_account: Box<Account<'info, TokenAccount>>,
+}
+
+/// Lets the current owner of the asset close a fully vested escrow, after which the
+/// destination of the recipient can be changed again
+pub fn handler(ctx: Context<CloseVestingEscrowV0>) -> Result<()> {
+  close_impl(&mut ctx.accounts.common)
+}
@@ -0,0 +1,111 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer},
+};
+
+use crate::{error::ErrorCode, state::*};
+
+#[derive(Accounts)]
+pub struct ClaimVestingCommonV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  // The current owner of the recipient's asset, checked by the instructions using this
+  #[account(mut)]
+  pub owner: Signer<'info>,
+  #[account(mut)]
+  pub recipient: Box<Account<'info, RecipientV0>>,
+  #[account(
+    mut,
+    seeds = ["vesting_escrow".as_bytes(), recipient.key().as_ref()],
+    bump = vesting_escrow.bump_seed,
+    has_one = recipient,
+    has_one = rewards_mint,
+  )]
+  pub vesting_escrow: Box<Account<'info, VestingEscrowV0>>,
+  pub rewards_mint: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    associated_token::mint = rewards_mint,
+    associated_token::authority = vesting_escrow,
+  )]
+  pub escrow_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+    init_if_needed,
+    payer = payer,
+    associated_token::mint = rewards_mint,
+    associated_token::authority = owner,
+  )]
+  pub destination_account: Box<Account<'info, TokenAccount>>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub system_program: Program<'info, System>,
+  pub token_program: Program<'info, Token>,
+}
+
+fn transfer_to_owner(ctx: &ClaimVestingCommonV0, amount: u64) -> Result<()> {
+  token::transfer(
+    CpiContext::new_with_signer(
+      ctx.token_program.to_account_info(),
+      Transfer {
+        from: ctx.escrow_account.to_account_info(),
+        to: ctx.destination_account.to_account_info(),
+        authority: ctx.vesting_escrow.to_account_info(),
+      },
+      &[&[
+        b"vesting_escrow",
+        ctx.vesting_escrow.recipient.as_ref(),
+        &[ctx.vesting_escrow.bump_seed],
+      ]],
+    ),
+    amount,
+  )
+}
+
+/// Transfers everything that has vested so far to the owner
+pub fn claim_impl(ctx: &mut ClaimVestingCommonV0) -> Result<()> {
+  let vesting_escrow = &mut ctx.vesting_escrow;
+  vesting_escrow.checkpoint(Clock::get()?.unix_timestamp);
+  let amount = vesting_escrow.unlocked_amount;
+  vesting_escrow.unlocked_amount = 0;
+
+  if amount > 0 {
+    transfer_to_owner(ctx, amount)?;
+  }
+
+  Ok(())
+}
+
+/// Claims the remaining rewards once everything has vested, then closes the escrow and
+/// clears the recipient's destination so rewards are distributed normally again
+pub fn close_impl(ctx: &mut ClaimVestingCommonV0) -> Result<()> {
+  claim_impl(ctx)?;
+  require_eq!(
+    ctx.vesting_escrow.locked_amount,
+    0,
+    ErrorCode::VestingNotComplete
+  );
+  // Anyone can send tokens to the escrow, which would otherwise keep it from closing
+  ctx.escrow_account.reload()?;
+  let remaining = ctx.escrow_account.amount;
+  if remaining > 0 {
+    transfer_to_owner(ctx, remaining)?;
+  }
+
+  token::close_account(CpiContext::new_with_signer(
+    ctx.token_program.to_account_info(),
+    CloseAccount {
+      account: ctx.escrow_account.to_account_info(),
+      destination: ctx.owner.to_account_info(),
+      authority: ctx.vesting_escrow.to_account_info(),
+    },
+    &[&[
+      b"vesting_escrow",
+      ctx.vesting_escrow.recipient.as_ref(),
+      &[ctx.vesting_escrow.bump_seed],
+    ]],
+  ))?;
+  ctx.vesting_escrow.close(ctx.owner.to_account_info())?;
+  ctx.recipient.destination = Pubkey::default();
+
+  Ok(())
+}
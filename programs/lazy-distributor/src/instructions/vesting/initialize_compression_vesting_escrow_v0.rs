@@ -0,0 +1,84 @@
+use account_compression_cpi::account_compression::program::SplAccountCompression;
+use anchor_lang::prelude::*;
+use bubblegum_cpi::get_asset_id;
+use shared_utils::{verify_compressed_nft, VerifyCompressedNftArgs};
+
+use crate::{error::ErrorCode, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct InitializeCompressionVestingEscrowArgsV0 {
+  pub vesting_period_secs: i64,
+  pub data_hash: [u8; 32],
+  pub creator_hash: [u8; 32],
+  pub root: [u8; 32],
+  pub index: u32,
+}
+
+#[derive(Accounts)]
+pub struct InitializeCompressionVestingEscrowV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  #[account(
+    has_one = rewards_mint,
+  )]
+  pub lazy_distributor: Box<Account<'info, LazyDistributorV0>>,
+  /// CHECK: Only used for the escrow's rewards mint
+  pub rewards_mint: UncheckedAccount<'info>,
+  #[account(
+    mut,
+    has_one = lazy_distributor,
+  )]
+  pub recipient: Box<Account<'info, RecipientV0>>,
+  pub owner: Signer<'info>,
+  /// CHECK: Checked via verify_compressed_nft
+  pub merkle_tree: UncheckedAccount<'info>,
+  pub compression_program: Program<'info, SplAccountCompression>,
+  #[account(
+    init,
+    payer = payer,
+    space = 8 + 60 + std::mem::size_of::<VestingEscrowV0>(),
+    seeds = ["vesting_escrow".as_bytes(), recipient.key().as_ref()],
+    bump,
+  )]
+  pub vesting_escrow: Box<Account<'info, VestingEscrowV0>>,
+  pub system_program: Program<'info, System>,
+}
+
+/// Compressed asset version of initialize_vesting_escrow_v0
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, InitializeCompressionVestingEscrowV0<'info>>,
+  args: InitializeCompressionVestingEscrowArgsV0,
+) -> Result<()> {
+  require_eq!(
+    ctx.accounts.recipient.asset,
+    get_asset_id(&ctx.accounts.merkle_tree.key(), args.index.into()),
+    ErrorCode::InvalidAsset
+  );
+  verify_compressed_nft(VerifyCompressedNftArgs {
+    data_hash: args.data_hash,
+    creator_hash: args.creator_hash,
+    root: args.root,
+    index: args.index,
+    compression_program: ctx.accounts.compression_program.to_account_info(),
+    merkle_tree: ctx.accounts.merkle_tree.to_account_info(),
+    owner: ctx.accounts.owner.key(),
+    delegate: ctx.accounts.owner.key(),
+    proof_accounts: ctx.remaining_accounts.to_vec(),
+  })?;
+  require_gt!(args.vesting_period_secs, 0, ErrorCode::InvalidVestingPeriod);
+  let curr_ts = Clock::get()?.unix_timestamp;
+
+  ctx.accounts.vesting_escrow.set_inner(VestingEscrowV0 {
+    recipient: ctx.accounts.recipient.key(),
+    rewards_mint: ctx.accounts.rewards_mint.key(),
+    vesting_period_secs: args.vesting_period_secs,
+    locked_amount: 0,
+    unlocked_amount: 0,
+    start_ts: curr_ts,
+    end_ts: curr_ts,
+    bump_seed: ctx.bumps.vesting_escrow,
+  });
+  ctx.accounts.recipient.destination = ctx.accounts.vesting_escrow.key();
+
+  Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use crate::{error::ErrorCode, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct InitializeVestingEscrowArgsV0 {
+  pub vesting_period_secs: i64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVestingEscrowV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  #[account(
+    has_one = rewards_mint,
+  )]
+  pub lazy_distributor: Box<Account<'info, LazyDistributorV0>>,
+  /// CHECK: Only used for the escrow's rewards mint
+  pub rewards_mint: UncheckedAccount<'info>,
+  #[account(
+    mut,
+    has_one = lazy_distributor,
+  )]
+  pub recipient: Box<Account<'info, RecipientV0>>,
+  pub owner: Signer<'info>,
+  #[account(
+    token::mint = recipient.asset,
+    constraint = recipient_mint_account.amount > 0,
+    constraint = recipient_mint_account.owner == owner.key()
+  )]
+  pub recipient_mint_account: Box<Account<'info, TokenAccount>>,
+  #[account(
+    init,
+    payer = payer,
+    space = 8 + 60 + std::mem::size_of::<VestingEscrowV0>(),
+    seeds = ["vesting_escrow".as_bytes(), recipient.key().as_ref()],
+    bump,
+  )]
+  pub vesting_escrow: Box<Account<'info, VestingEscrowV0>>,
+  pub system_program: Program<'info, System>,
+}
+
+/// Makes the recipient's vesting escrow its destination. From then on rewards can only be
+/// distributed with distribute_vesting_destination_v0, and whoever owns the asset claims them
+/// as they vest. The destination can only be changed again once the current owner closes the
+/// fully vested escrow.
+pub fn handler(
+  ctx: Context<InitializeVestingEscrowV0>,
+  args: InitializeVestingEscrowArgsV0,
+) -> Result<()> {
+  require_gt!(args.vesting_period_secs, 0, ErrorCode::InvalidVestingPeriod);
+  let curr_ts = Clock::get()?.unix_timestamp;
+
+  ctx.accounts.vesting_escrow.set_inner(VestingEscrowV0 {
+    recipient: ctx.accounts.recipient.key(),
+    rewards_mint: ctx.accounts.rewards_mint.key(),
+    vesting_period_secs: args.vesting_period_secs,
+    locked_amount: 0,
+    unlocked_amount: 0,
+    start_ts: curr_ts,
+    end_ts: curr_ts,
+    bump_seed: ctx.bumps.vesting_escrow,
+  });
+  ctx.accounts.recipient.destination = ctx.accounts.vesting_escrow.key();
+
+  Ok(())
+}
@@ -0,0 +1,15 @@
+pub mod claim_compression_vesting_v0;
+pub mod claim_vesting_v0;
+pub mod close_compression_vesting_escrow_v0;
+pub mod close_vesting_escrow_v0;
+pub mod common;
+pub mod initialize_compression_vesting_escrow_v0;
+pub mod initialize_vesting_escrow_v0;
+
+pub use claim_compression_vesting_v0::*;
+pub use claim_vesting_v0::*;
+pub use close_compression_vesting_escrow_v0::*;
+pub use close_vesting_escrow_v0::*;
+pub use common::*;
+pub use initialize_compression_vesting_escrow_v0::*;
+pub use initialize_vesting_escrow_v0::*;
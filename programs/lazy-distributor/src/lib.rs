@@ -101,6 +101,69 @@ pub mod lazy_distributor {
     set_current_rewards_v1::handler(ctx, args)
   }
 
+  pub fn set_rewards_root_v0(
+    ctx: Context<SetRewardsRootV0>,
+    args: SetRewardsRootArgsV0,
+  ) -> Result<()> {
+    set_rewards_root_v0::handler(ctx, args)
+  }
+
+  pub fn prove_current_rewards_v0(
+    ctx: Context<ProveCurrentRewardsV0>,
+    args: ProveCurrentRewardsArgsV0,
+  ) -> Result<()> {
+    prove_current_rewards_v0::handler(ctx, args)
+  }
+
+  pub fn update_lazy_distributor_v1(
+    ctx: Context<UpdateLazyDistributorV1>,
+    args: UpdateLazyDistributorArgsV1,
+  ) -> Result<()> {
+    update_lazy_distributor_v1::handler(ctx, args)
+  }
+
+  pub fn initialize_vesting_escrow_v0(
+    ctx: Context<InitializeVestingEscrowV0>,
+    args: InitializeVestingEscrowArgsV0,
+  ) -> Result<()> {
+    initialize_vesting_escrow_v0::handler(ctx, args)
+  }
+
+  pub fn initialize_compression_vesting_escrow_v0<'info>(
+    ctx: Context<'_, '_, '_, 'info, InitializeCompressionVestingEscrowV0<'info>>,
+    args: InitializeCompressionVestingEscrowArgsV0,
+  ) -> Result<()> {
+    initialize_compression_vesting_escrow_v0::handler(ctx, args)
+  }
+
+  pub fn distribute_vesting_destination_v0<'info>(
+    ctx: Context<'_, '_, '_, 'info, DistributeVestingDestinationV0<'info>>,
+  ) -> Result<()> {
+    distribute_vesting_destination_v0::handler(ctx)
+  }
+
+  pub fn claim_vesting_v0(ctx: Context<ClaimVestingV0>) -> Result<()> {
+    claim_vesting_v0::handler(ctx)
+  }
+
+  pub fn claim_compression_vesting_v0<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimCompressionVestingV0<'info>>,
+    args: ClaimCompressionVestingArgsV0,
+  ) -> Result<()> {
+    claim_compression_vesting_v0::handler(ctx, args)
+  }
+
+  pub fn close_vesting_escrow_v0(ctx: Context<CloseVestingEscrowV0>) -> Result<()> {
+    close_vesting_escrow_v0::handler(ctx)
+  }
+
+  pub fn close_compression_vesting_escrow_v0<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseCompressionVestingEscrowV0<'info>>,
+    args: CloseCompressionVestingEscrowArgsV0,
+  ) -> Result<()> {
+    close_compression_vesting_escrow_v0::handler(ctx, args)
+  }
+
   pub fn dummy_ix(_ctx: Context<DummyIx>) -> Result<()> {
     Err(error!(crate::error::ErrorCode::DummyInstruction))
   }
@@ -1,4 +1,6 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::keccak::hashv};
+
+use crate::error::ErrorCode;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct OracleConfigV0 {
@@ -6,6 +8,30 @@ pub struct OracleConfigV0 {
   pub url: String,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct RewardsRootV0 {
+  // Increases with every root, so an older root cannot be posted again
+  pub period: u64,
+  // Merkle root of keccak(asset, cumulative rewards) leaves
+  pub root: [u8; 32],
+}
+
+impl RewardsRootV0 {
+  pub fn leaf(asset: &Pubkey, current_rewards: u64) -> [u8; 32] {
+    hashv(&[asset.as_ref(), &current_rewards.to_le_bytes()]).0
+  }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct QuorumConfigV0 {
+  // Number of oracles that must have set current rewards before distribution
+  pub min_signers: u8,
+  // Weight of each oracle in the median, matching indexes in `oracles`. Empty for equal weights
+  pub oracle_weights: Vec<u16>,
+  // Largest allowed difference between the lowest and highest reported cumulative rewards
+  pub max_spread: Option<u64>,
+}
+
 #[account]
 #[derive(Default)]
 pub struct LazyDistributorV0 {
@@ -17,6 +43,135 @@ pub struct LazyDistributorV0 {
   pub bump_seed: u8,
   // Optional approver of every set current rewards tx. Use if you want to require calls to proxy throuh another contract
   pub approver: Option<Pubkey>,
+  // Latest cumulative rewards root signed by a quorum of oracles. Recipients can prove
+  // their current rewards against it instead of waiting for per-recipient oracle writes.
+  pub rewards_root: Option<RewardsRootV0>,
+  // Quorum rules for distribution. When not set, a majority of the oracles must report and
+  // their values are weighted equally.
+  pub quorum: Option<QuorumConfigV0>,
+  // Period of the latest rewards root, kept after the root is cleared so that it cannot be
+  // posted again
+  pub last_rewards_period: u64,
+}
+
+impl LazyDistributorV0 {
+  pub fn min_signers(&self) -> usize {
+    self
+      .quorum
+      .as_ref()
+      .map(|quorum| usize::from(quorum.min_signers))
+      .unwrap_or_else(|| self.oracles.len().div_ceil(2))
+      .max(1)
+  }
+
+  pub fn oracle_weight(&self, oracle_index: usize) -> u64 {
+    self
+      .quorum
+      .as_ref()
+      .and_then(|quorum| quorum.oracle_weights.get(oracle_index))
+      .map(|weight| u64::from(*weight))
+      .unwrap_or(1)
+  }
+
+  /// Roots signed by a previous set of oracles must not outlive them
+  pub fn clear_rewards_root(&mut self) {
+    if let Some(rewards_root) = self.rewards_root.take() {
+      self.last_rewards_period = self.last_rewards_period.max(rewards_root.period);
+    }
+  }
+
+  pub fn validate_quorum(&self) -> Result<()> {
+    if let Some(quorum) = &self.quorum {
+      require!(
+        quorum.min_signers > 0 && usize::from(quorum.min_signers) <= self.oracles.len(),
+        ErrorCode::InvalidQuorumConfig
+      );
+      require!(
+        quorum.oracle_weights.is_empty()
+          || (quorum.oracle_weights.len() == self.oracles.len()
+            && quorum.oracle_weights.iter().all(|weight| *weight > 0)),
+        ErrorCode::InvalidQuorumConfig
+      );
+    }
+
+    Ok(())
+  }
+
+  /// A single rewards root replaces every oracle's value, so there is no spread between
+  /// oracles to check.
+  pub fn require_rewards_root_allowed(&self) -> Result<()> {
+    require!(
+      self
+        .quorum
+        .as_ref()
+        .and_then(|quorum| quorum.max_spread)
+        .is_none(),
+      ErrorCode::RewardsRootWithMaxSpread
+    );
+
+    Ok(())
+  }
+
+  /// Requires the minimum number of oracles to have signed, together holding more than half
+  /// of the total oracle weight.
+  pub fn require_signer_quorum(&self, signers: &[Pubkey]) -> Result<()> {
+    let signed: Vec<u64> = self
+      .oracles
+      .iter()
+      .enumerate()
+      .filter(|(_, oracle)| signers.contains(&oracle.oracle))
+      .map(|(i, _)| self.oracle_weight(i))
+      .collect();
+    require_gte!(
+      signed.len(),
+      self.min_signers(),
+      ErrorCode::InsufficientOracleSignatures
+    );
+
+    let signed_weight: u64 = signed.iter().sum();
+    let total_weight: u64 = (0..self.oracles.len()).map(|i| self.oracle_weight(i)).sum();
+    require_gt!(
+      signed_weight * 2,
+      total_weight,
+      ErrorCode::InsufficientOracleSignatures
+    );
+
+    Ok(())
+  }
+
+  /// Resolves the oracle reported cumulative rewards of a recipient to the weighted median,
+  /// refusing when the reports are further apart than the quorum's max spread.
+  pub fn resolve_current_rewards(&self, current_rewards: &[Option<u64>]) -> Result<u64> {
+    let mut reported: Vec<(u64, u64)> = current_rewards
+      .iter()
+      .enumerate()
+      .filter_map(|(i, rewards)| rewards.map(|rewards| (rewards, self.oracle_weight(i))))
+      .collect();
+    require_gte!(
+      reported.len(),
+      self.min_signers(),
+      ErrorCode::InsufficientOracleSignatures
+    );
+    reported.sort_unstable();
+
+    let lowest = reported[0].0;
+    let highest = reported[reported.len() - 1].0;
+    if let Some(max_spread) = self.quorum.as_ref().and_then(|quorum| quorum.max_spread) {
+      require_gte!(max_spread, highest - lowest, ErrorCode::OracleDisagreement);
+    }
+
+    // With equal weights this is the upper median
+    let total_weight: u64 = reported.iter().map(|(_, weight)| weight).sum();
+    let mut cumulative_weight = 0;
+    for (rewards, weight) in reported {
+      cumulative_weight += weight;
+      if cumulative_weight * 2 > total_weight {
+        return Ok(rewards);
+      }
+    }
+
+    Ok(highest)
+  }
 }
 
 #[account]
@@ -35,3 +190,62 @@ pub struct RecipientV0 {
   /// Pubkey::Default if not being used.
   pub destination: Pubkey,
 }
+
+pub fn vesting_escrow_key(recipient: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(&[b"vesting_escrow", recipient.as_ref()], &crate::ID).0
+}
+
+#[account]
+#[derive(Default)]
+pub struct VestingEscrowV0 {
+  pub recipient: Pubkey,
+  pub rewards_mint: Pubkey,
+  // Every distribution vests linearly over this many seconds
+  pub vesting_period_secs: i64,
+  // Amount vesting linearly from start_ts to end_ts
+  pub locked_amount: u64,
+  // Amount vested but not yet claimed
+  pub unlocked_amount: u64,
+  pub start_ts: i64,
+  pub end_ts: i64,
+  pub bump_seed: u8,
+}
+
+impl VestingEscrowV0 {
+  /// Moves everything that vested up to curr_ts into the unlocked amount
+  pub fn checkpoint(&mut self, curr_ts: i64) {
+    if curr_ts <= self.start_ts {
+      return;
+    }
+    let vested = if curr_ts >= self.end_ts {
+      self.locked_amount
+    } else {
+      u64::try_from(
+        u128::from(self.locked_amount) * u128::try_from(curr_ts - self.start_ts).unwrap()
+          / u128::try_from(self.end_ts - self.start_ts).unwrap(),
+      )
+      .unwrap()
+    };
+    self.locked_amount -= vested;
+    self.unlocked_amount = self.unlocked_amount.checked_add(vested).unwrap();
+    self.start_ts = curr_ts.min(self.end_ts);
+  }
+
+  /// Adds a distribution to the schedule. The still locked amount and the new amount vest
+  /// together, ending at the amount weighted average of their end times.
+  pub fn deposit(&mut self, curr_ts: i64, amount: u64) {
+    self.checkpoint(curr_ts);
+    let total = u128::from(self.locked_amount) + u128::from(amount);
+    if total == 0 {
+      return;
+    }
+    let secs_left = u128::try_from((self.end_ts - curr_ts).max(0)).unwrap();
+    let period = u128::try_from(self.vesting_period_secs).unwrap();
+    let weighted_secs =
+      (u128::from(self.locked_amount) * secs_left + u128::from(amount) * period).div_ceil(total);
+
+    self.locked_amount = u64::try_from(total).unwrap();
+    self.start_ts = curr_ts;
+    self.end_ts = curr_ts + i64::try_from(weighted_secs).unwrap();
+  }
+}
@@ -16,4 +16,22 @@ pub enum ErrorCode {
 
   #[msg("Transaction has already been executed")]
   TransactionAlreadyExecuted,
+
+  #[msg("Transaction is not valid yet")]
+  TransactionNotYetValid,
+
+  #[msg("Transaction has expired")]
+  TransactionExpired,
+
+  #[msg("Transaction has been revoked")]
+  TransactionRevoked,
+
+  #[msg("Transaction index is out of bounds")]
+  InvalidIndex,
+
+  #[msg("Validity window must end after it starts")]
+  InvalidValidityWindow,
+
+  #[msg("Transactions have been revoked, use execute_transaction_v1")]
+  RevokedTransactionsRequired,
 }
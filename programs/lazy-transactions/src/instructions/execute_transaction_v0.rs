@@ -64,8 +64,44 @@ pub struct ExecuteTransactionV0<'info> {
   pub executed_transactions: AccountInfo<'info>,
 }
 
-pub fn handler(ctx: Context<ExecuteTransactionV0>, args: ExecuteTransactionArgsV0) -> Result<()> {
-  let slice = &mut ctx.accounts.executed_transactions.try_borrow_mut_data()?[1..];
+/// Executes a transaction of a lazy transactions instance without revoked transactions.
+/// Once any transaction has been revoked, use execute_transaction_v1.
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, ExecuteTransactionV0<'info>>,
+  args: ExecuteTransactionArgsV0,
+) -> Result<()> {
+  require_keys_eq!(
+    ctx.accounts.lazy_transactions.revoked_transactions,
+    Pubkey::default(),
+    ErrorCode::RevokedTransactionsRequired
+  );
+
+  execute_impl(
+    ctx.program_id,
+    ctx.accounts,
+    ctx.remaining_accounts,
+    ctx.bumps.lazy_signer,
+    args,
+  )
+}
+
+pub fn execute_impl<'info>(
+  program_id: &Pubkey,
+  common: &ExecuteTransactionV0<'info>,
+  remaining_accounts: &[AccountInfo<'info>],
+  lazy_signer_bump: u8,
+  args: ExecuteTransactionArgsV0,
+) -> Result<()> {
+  let curr_ts = Clock::get()?.unix_timestamp;
+  let lazy_transactions = &common.lazy_transactions;
+  if let Some(valid_from) = lazy_transactions.valid_from {
+    require_gte!(curr_ts, valid_from, ErrorCode::TransactionNotYetValid);
+  }
+  if let Some(valid_until) = lazy_transactions.valid_until {
+    require_gt!(valid_until, curr_ts, ErrorCode::TransactionExpired);
+  }
+
+  let slice = &mut common.executed_transactions.try_borrow_mut_data()?[1..];
   set_executed(slice, args.index);
 
   let largest_acct_idx: usize = (*args
@@ -76,19 +112,19 @@ pub fn handler(ctx: Context<ExecuteTransactionV0>, args: ExecuteTransactionArgsV
     .unwrap())
   .into();
 
-  let mut proof = ctx.remaining_accounts[(largest_acct_idx + 1)..]
+  let mut proof = remaining_accounts[(largest_acct_idx + 1)..]
     .iter()
     .map(|a| a.key.to_bytes())
     .collect::<Vec<_>>();
 
   fill_in_proof_from_canopy(
-    &ctx.accounts.canopy.try_borrow_data()?[1..],
-    ctx.accounts.lazy_transactions.max_depth,
+    &common.canopy.try_borrow_data()?[1..],
+    common.lazy_transactions.max_depth,
     args.index,
     &mut proof,
   )?;
 
-  let accts = ctx.remaining_accounts[..(largest_acct_idx + 1)]
+  let accts = remaining_accounts[..(largest_acct_idx + 1)]
     .iter()
     .map(|a| a.key.to_bytes().to_vec())
     .collect::<Vec<_>>();
@@ -116,17 +152,17 @@ pub fn handler(ctx: Context<ExecuteTransactionV0>, args: ExecuteTransactionArgsV
 
   let hash = solana_program::keccak::hashv(to_hash).0;
 
-  if !verify(proof, ctx.accounts.lazy_transactions.root, hash, args.index) {
+  if !verify(proof, common.lazy_transactions.root, hash, args.index) {
     return Err(error!(ErrorCode::InvalidData));
   };
 
   let lazy_signer_seeds: &[&[u8]] = &[
     b"lazy_signer",
-    ctx.accounts.lazy_transactions.name.as_bytes(),
-    &[ctx.bumps.lazy_signer],
+    common.lazy_transactions.name.as_bytes(),
+    &[lazy_signer_bump],
   ];
 
-  let prefix: Vec<&[u8]> = vec![b"user", ctx.accounts.lazy_transactions.name.as_bytes()];
+  let prefix: Vec<&[u8]> = vec![b"user", common.lazy_transactions.name.as_bytes()];
   // Need to convert to &[&[u8]] because invoke_signed expects that
   let signers_inner_u8: Vec<Vec<&[u8]>> = args
     .signer_seeds
@@ -147,17 +183,17 @@ pub fn handler(ctx: Context<ExecuteTransactionV0>, args: ExecuteTransactionArgsV
 
   let signer_addresses = signers
     .iter()
-    .map(|s| Pubkey::create_program_address(s, ctx.program_id).unwrap())
+    .map(|s| Pubkey::create_program_address(s, program_id).unwrap())
     .collect::<std::collections::HashSet<Pubkey>>();
   for ix in args.instructions {
     let mut accounts = Vec::new();
     let mut account_infos = Vec::new();
     for i in ix.accounts {
-      let acct = ctx.remaining_accounts[i as usize].clone();
+      let acct = remaining_accounts[i as usize].clone();
       accounts.push(acct.clone());
       account_infos.push(AccountMeta {
         pubkey: acct.key(),
-        is_signer: acct.key() == ctx.accounts.lazy_signer.key()
+        is_signer: acct.key() == common.lazy_signer.key()
           || acct.is_signer
           || signer_addresses.contains(&acct.key()),
         is_writable: acct.is_writable,
@@ -165,7 +201,7 @@ pub fn handler(ctx: Context<ExecuteTransactionV0>, args: ExecuteTransactionArgsV
     }
     solana_program::program::invoke_signed(
       &Instruction {
-        program_id: *ctx.remaining_accounts[ix.program_id_index as usize].key,
+        program_id: *remaining_accounts[ix.program_id_index as usize].key,
         accounts: account_infos,
         data: ix.data,
       },
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use super::execute_transaction_v0::*;
+use crate::{error::ErrorCode, util::is_revoked};
+
+#[derive(Accounts)]
+#[instruction(args: ExecuteTransactionArgsV0)]
+pub struct ExecuteTransactionV1<'info> {
+  pub common: ExecuteTransactionV0<'info>,
+  /// CHECK: Checked against the lazy transactions. The system program when no transactions have been revoked
+  #[account(
+    address = common.lazy_transactions.revoked_transactions,
+    constraint = revoked_transactions.key() == Pubkey::default() || !is_revoked(&revoked_transactions.try_borrow_data()?[1..], args.index) @ ErrorCode::TransactionRevoked,
+  )]
+  pub revoked_transactions: AccountInfo<'info>,
+}
+
+/// Executes a transaction like execute_transaction_v0, refusing transactions that have been
+/// revoked with revoke_transactions_v0
+pub fn handler<'info>(
+  ctx: Context<'_, '_, '_, 'info, ExecuteTransactionV1<'info>>,
+  args: ExecuteTransactionArgsV0,
+) -> Result<()> {
+  execute_impl(
+    ctx.program_id,
+    &ctx.accounts.common,
+    ctx.remaining_accounts,
+    ctx.bumps.common.lazy_signer,
+    args,
+  )
+}
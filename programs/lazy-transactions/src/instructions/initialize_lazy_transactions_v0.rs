@@ -63,6 +63,9 @@ pub fn handler(
       max_depth: args.max_depth,
       bump_seed: ctx.bumps.lazy_transactions,
       executed_transactions: ctx.accounts.executed_transactions.key(),
+      valid_from: None,
+      valid_until: None,
+      revoked_transactions: Pubkey::default(),
     });
 
   Ok(())
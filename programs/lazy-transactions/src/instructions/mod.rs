@@ -1,13 +1,19 @@
 pub mod close_canopy_v0;
 pub mod close_marker_v0;
 pub mod execute_transaction_v0;
+pub mod execute_transaction_v1;
 pub mod initialize_lazy_transactions_v0;
+pub mod revoke_transactions_v0;
 pub mod set_canopy_v0;
+pub mod set_validity_window_v0;
 pub mod update_lazy_transactions_v0;
 
 pub use close_canopy_v0::*;
 pub use close_marker_v0::*;
 pub use execute_transaction_v0::*;
+pub use execute_transaction_v1::*;
 pub use initialize_lazy_transactions_v0::*;
+pub use revoke_transactions_v0::*;
 pub use set_canopy_v0::*;
+pub use set_validity_window_v0::*;
 pub use update_lazy_transactions_v0::*;
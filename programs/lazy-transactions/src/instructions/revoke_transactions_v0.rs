@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+  error::ErrorCode,
+  id,
+  state::*,
+  util::{get_bitmap_len, set_revoked},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct RevokeTransactionsArgsV0 {
+  pub indices: Vec<u32>,
+}
+
+#[derive(Accounts)]
+#[instruction(args: RevokeTransactionsArgsV0)]
+pub struct RevokeTransactionsV0<'info> {
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    has_one = authority
+  )]
+  pub lazy_transactions: Account<'info, LazyTransactionsV0>,
+  /// CHECK: Account to store the bitmap of revoked txns, the size will determine the size of the bitmap
+  #[account(
+    mut,
+    owner = id(),
+    constraint = revoked_transactions.key() == lazy_transactions.revoked_transactions || (lazy_transactions.revoked_transactions == Pubkey::default() && revoked_transactions.data.borrow()[0] == 0),
+    constraint = revoked_transactions.data.borrow().len() == 1 + get_bitmap_len(lazy_transactions.max_depth),
+  )]
+  pub revoked_transactions: AccountInfo<'info>,
+}
+
+/// Permanently blocks the given leaves from being executed, without replacing the root.
+/// The first call sets up the revoked transactions bitmap, later calls must pass the same account.
+pub fn handler(ctx: Context<RevokeTransactionsV0>, args: RevokeTransactionsArgsV0) -> Result<()> {
+  let num_txns = 1_u64 << ctx.accounts.lazy_transactions.max_depth;
+  let mut data = ctx.accounts.revoked_transactions.try_borrow_mut_data()?;
+  data[0] = 1;
+  for index in args.indices {
+    require_gt!(num_txns, u64::from(index), ErrorCode::InvalidIndex);
+    set_revoked(&mut data[1..], index);
+  }
+
+  ctx.accounts.lazy_transactions.revoked_transactions = ctx.accounts.revoked_transactions.key();
+
+  Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SetValidityWindowArgsV0 {
+  pub valid_from: Option<i64>,
+  pub valid_until: Option<i64>,
+}
+
+#[derive(Accounts)]
+pub struct SetValidityWindowV0<'info> {
+  pub authority: Signer<'info>,
+  #[account(
+    mut,
+    has_one = authority
+  )]
+  pub lazy_transactions: Account<'info, LazyTransactionsV0>,
+}
+
+/// Sets the window in which transactions can be executed. Passing None leaves that side unbounded.
+pub fn handler(ctx: Context<SetValidityWindowV0>, args: SetValidityWindowArgsV0) -> Result<()> {
+  if let (Some(valid_from), Some(valid_until)) = (args.valid_from, args.valid_until) {
+    require_gt!(valid_until, valid_from, ErrorCode::InvalidValidityWindow);
+  }
+
+  ctx.accounts.lazy_transactions.valid_from = args.valid_from;
+  ctx.accounts.lazy_transactions.valid_until = args.valid_until;
+
+  Ok(())
+}
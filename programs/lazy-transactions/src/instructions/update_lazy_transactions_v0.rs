@@ -35,6 +35,7 @@ pub struct UpdateLazyTransactionsV0<'info> {
 }
 
 /// NOTE: This is a dangerous operation, as index markers will be preserved.
+/// Revocations refer to leaves of the old root, so replacing the root clears them.
 pub fn handler(
   ctx: Context<UpdateLazyTransactionsV0>,
   args: UpdateLazyTransactionsArgsV0,
@@ -53,6 +54,7 @@ pub fn handler(
 
   if let Some(root) = args.root {
     ctx.accounts.lazy_transactions.root = root;
+    ctx.accounts.lazy_transactions.revoked_transactions = Pubkey::default();
   }
 
   Ok(())
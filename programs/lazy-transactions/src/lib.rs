@@ -7,11 +7,11 @@ declare_id!("1atrmQs3eq1N2FEYWu6tyTXbCjP4uQwExpjtnhXtS8h");
 pub mod canopy;
 pub mod error;
 pub mod instructions;
-pub mod merkle_proof;
 pub mod state;
 pub mod util;
 
 pub use instructions::*;
+pub use shared_utils::merkle_proof;
 pub use state::*;
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -41,13 +41,20 @@ pub mod lazy_transactions {
     initialize_lazy_transactions_v0::handler(ctx, args)
   }
 
-  pub fn execute_transaction_v0(
-    ctx: Context<ExecuteTransactionV0>,
+  pub fn execute_transaction_v0<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteTransactionV0<'info>>,
     args: ExecuteTransactionArgsV0,
   ) -> Result<()> {
     execute_transaction_v0::handler(ctx, args)
   }
 
+  pub fn execute_transaction_v1<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteTransactionV1<'info>>,
+    args: ExecuteTransactionArgsV0,
+  ) -> Result<()> {
+    execute_transaction_v1::handler(ctx, args)
+  }
+
   pub fn close_marker_v0(ctx: Context<CloseMarkerV0>, args: CloseMarkerArgsV0) -> Result<()> {
     close_marker_v0::handler(ctx, args)
   }
@@ -66,4 +73,18 @@ pub mod lazy_transactions {
   pub fn set_canopy_v0(ctx: Context<SetCanopyV0>, args: SetCanopyArgsV0) -> Result<()> {
     set_canopy_v0::handler(ctx, args)
   }
+
+  pub fn set_validity_window_v0(
+    ctx: Context<SetValidityWindowV0>,
+    args: SetValidityWindowArgsV0,
+  ) -> Result<()> {
+    set_validity_window_v0::handler(ctx, args)
+  }
+
+  pub fn revoke_transactions_v0(
+    ctx: Context<RevokeTransactionsV0>,
+    args: RevokeTransactionsArgsV0,
+  ) -> Result<()> {
+    revoke_transactions_v0::handler(ctx, args)
+  }
 }
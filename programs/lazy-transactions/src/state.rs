@@ -11,6 +11,11 @@ pub struct LazyTransactionsV0 {
   pub bump_seed: u8,
   // Bitmap of executed transactions
   pub executed_transactions: Pubkey,
+  // Transactions can only be executed within this window when set
+  pub valid_from: Option<i64>,
+  pub valid_until: Option<i64>,
+  // Bitmap of revoked transactions, the system program (default pubkey) when none are revoked
+  pub revoked_transactions: Pubkey,
 }
 
 #[account]
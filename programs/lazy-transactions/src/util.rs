@@ -15,3 +15,11 @@ pub fn set_executed(bitmap: &mut [u8], index: u32) {
   let bit = 1 << (index % 8);
   *byte |= bit;
 }
+
+pub fn is_revoked(bitmap: &[u8], index: u32) -> bool {
+  is_executed(bitmap, index)
+}
+
+pub fn set_revoked(bitmap: &mut [u8], index: u32) {
+  set_executed(bitmap, index)
+}
@@ -13,4 +13,13 @@ pub enum ErrorCode {
 
   #[msg("Invalid argument")]
   InvalidArgs,
+
+  #[msg("Oracle index is out of range")]
+  InvalidOracleIndex,
+
+  #[msg("Remaining accounts do not match the submitted prices")]
+  InvalidRemainingAccounts,
+
+  #[msg("Price history must be passed once it is initialized")]
+  InvalidPriceHistory,
 }
@@ -0,0 +1,42 @@
+use crate::state::*;
+use anchor_lang::prelude::*;
+use shared_utils::resize_to_fit;
+
+#[derive(Accounts)]
+pub struct InitializePriceHistoryV0<'info> {
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  #[account(mut)]
+  pub price_oracle: Box<Account<'info, PriceOracleV0>>,
+  #[account(
+    init,
+    payer = payer,
+    space = 8 + std::mem::size_of::<PriceHistoryV0>(),
+    seeds = ["price_history".as_bytes(), price_oracle.key().as_ref()],
+    bump,
+  )]
+  pub price_history: AccountLoader<'info, PriceHistoryV0>,
+  pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializePriceHistoryV0>) -> Result<()> {
+  let mut price_history = ctx.accounts.price_history.load_init()?;
+  price_history.price_oracle = ctx.accounts.price_oracle.key();
+  price_history.bump_seed = ctx.bumps.price_history;
+  if let (Some(price), Some(unix_timestamp)) = (
+    ctx.accounts.price_oracle.current_price,
+    ctx.accounts.price_oracle.last_calculated_timestamp,
+  ) {
+    price_history.record(unix_timestamp, price);
+  }
+  drop(price_history);
+
+  ctx.accounts.price_oracle.price_history = Some(ctx.accounts.price_history.key());
+  resize_to_fit(
+    &ctx.accounts.payer,
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.price_oracle,
+  )?;
+
+  Ok(())
+}
@@ -7,6 +7,9 @@ pub struct InitializePriceOracleArgsV0 {
   pub oracles: Vec<OracleV0>,
   pub decimals: u8,
   pub authority: Pubkey,
+  pub config: Option<PriceOracleConfigV0>,
+  // Parallel to `oracles`. Defaults to equal weights
+  pub oracle_weights: Option<Vec<u16>>,
 }
 
 #[derive(Accounts)]
@@ -14,7 +17,7 @@ pub struct InitializePriceOracleArgsV0 {
 pub struct InitializePriceOracleV0<'info> {
   #[account(
     init,
-    space =  60 + 8 + std::mem::size_of::<PriceOracleV0>() + (std::mem::size_of::<OracleV0>() * args.oracles.len()),
+    space =  60 + 8 + std::mem::size_of::<PriceOracleV0>() + ((std::mem::size_of::<OracleV0>() + std::mem::size_of::<OracleInfoV0>()) * args.oracles.len()),
     payer = payer
   )]
   pub price_oracle: Box<Account<'info, PriceOracleV0>>,
@@ -42,6 +45,15 @@ pub fn handler(
       ErrorCode::InvalidArgs
     );
   }
+  let oracle_weights = args
+    .oracle_weights
+    .unwrap_or_else(|| vec![1; args.oracles.len()]);
+  require_eq!(
+    oracle_weights.len(),
+    args.oracles.len(),
+    ErrorCode::InvalidArgs
+  );
+
   ctx.accounts.price_oracle.set_inner(PriceOracleV0 {
     authority: args.authority,
     num_oracles: args.oracles.len().try_into().unwrap(),
@@ -49,6 +61,15 @@ pub fn handler(
     decimals: args.decimals,
     current_price: None,
     last_calculated_timestamp: None,
+    config: args.config.unwrap_or_default(),
+    oracle_infos: oracle_weights
+      .into_iter()
+      .map(|weight| OracleInfoV0 {
+        weight,
+        flagged: false,
+      })
+      .collect(),
+    price_history: None,
   });
   Ok(())
 }
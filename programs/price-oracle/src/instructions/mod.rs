@@ -1,9 +1,15 @@
+pub mod initialize_price_history_v0;
 pub mod initialize_price_oracle_v0;
 pub mod submit_price_v0;
+pub mod submit_price_v1;
+pub mod submit_prices_v0;
 pub mod update_price_oracle_v0;
 pub mod update_price_v0;
 
+pub use initialize_price_history_v0::*;
 pub use initialize_price_oracle_v0::*;
 pub use submit_price_v0::*;
+pub use submit_price_v1::*;
+pub use submit_prices_v0::*;
 pub use update_price_oracle_v0::*;
 pub use update_price_v0::*;
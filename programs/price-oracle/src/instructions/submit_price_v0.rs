@@ -16,28 +16,72 @@ pub struct SubmitPriceV0<'info> {
   pub price_oracle: Box<Account<'info, PriceOracleV0>>,
 
   pub oracle: Signer<'info>,
+  #[account(
+    mut,
+    has_one = price_oracle,
+  )]
+  pub price_history: Option<AccountLoader<'info, PriceHistoryV0>>,
 }
 
-pub fn handler(ctx: Context<SubmitPriceV0>, args: SubmitPriceArgsV0) -> Result<()> {
-  let oracle = ctx
-    .accounts
-    .price_oracle
-    .oracles
-    .get_mut(args.oracle_index as usize)
-    .unwrap();
-  require!(
-    oracle.authority == ctx.accounts.oracle.key(),
-    ErrorCode::UnauthorisedOracle
-  );
-
-  let curr_ts = Clock::get()?.unix_timestamp;
+// Resolves the submitting oracle, either by the given index or by searching for the signer
+pub fn find_oracle_index(
+  price_oracle: &PriceOracleV0,
+  oracle: &Pubkey,
+  oracle_index: Option<u8>,
+) -> Result<usize> {
+  match oracle_index {
+    Some(index) => {
+      let info = price_oracle
+        .oracles
+        .get(index as usize)
+        .ok_or_else(|| error!(ErrorCode::InvalidOracleIndex))?;
+      require_keys_eq!(info.authority, *oracle, ErrorCode::UnauthorisedOracle);
+      Ok(index as usize)
+    }
+    None => price_oracle
+      .oracles
+      .iter()
+      .position(|info| info.authority == *oracle)
+      .ok_or_else(|| error!(ErrorCode::UnauthorisedOracle)),
+  }
+}
+
+pub fn record_submission(
+  price_oracle: &mut PriceOracleV0,
+  price_history: Option<&AccountLoader<PriceHistoryV0>>,
+  oracle_index: usize,
+  price: u64,
+  curr_ts: i64,
+) -> Result<()> {
+  price_oracle.check_price_history(price_history.map(|price_history| price_history.key()))?;
+  let oracle = &mut price_oracle.oracles[oracle_index];
   oracle.last_submitted_timestamp = Some(curr_ts);
-  oracle.last_submitted_price = Some(args.price);
+  oracle.last_submitted_price = Some(price);
 
-  let curr_price_opt = calculate_current_price(&ctx.accounts.price_oracle.oracles, curr_ts);
-  if let Some(curr_price) = curr_price_opt {
-    ctx.accounts.price_oracle.current_price = Some(curr_price);
-    ctx.accounts.price_oracle.last_calculated_timestamp = Some(curr_ts);
+  let current_price = calculate_current_price(price_oracle, curr_ts);
+  price_oracle.set_flagged(&current_price.flagged);
+  if let Some(curr_price) = current_price.price {
+    price_oracle.current_price = Some(curr_price);
+    price_oracle.last_calculated_timestamp = Some(curr_ts);
+    if let Some(price_history) = price_history {
+      price_history.load_mut()?.record(curr_ts, curr_price);
+    }
   }
   Ok(())
 }
+
+pub fn handler(ctx: Context<SubmitPriceV0>, args: SubmitPriceArgsV0) -> Result<()> {
+  let oracle_index = find_oracle_index(
+    &ctx.accounts.price_oracle,
+    &ctx.accounts.oracle.key(),
+    Some(args.oracle_index),
+  )?;
+
+  record_submission(
+    &mut ctx.accounts.price_oracle,
+    ctx.accounts.price_history.as_ref(),
+    oracle_index,
+    args.price,
+    Clock::get()?.unix_timestamp,
+  )
+}
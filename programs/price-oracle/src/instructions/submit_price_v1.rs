@@ -0,0 +1,40 @@
+use crate::state::*;
+use crate::submit_price_v0::{find_oracle_index, record_submission};
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SubmitPriceArgsV1 {
+  // When not provided, the oracle is looked up by the signer
+  pub oracle_index: Option<u8>,
+  pub price: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(args: SubmitPriceArgsV1)]
+pub struct SubmitPriceV1<'info> {
+  #[account(mut)]
+  pub price_oracle: Box<Account<'info, PriceOracleV0>>,
+
+  pub oracle: Signer<'info>,
+  #[account(
+    mut,
+    has_one = price_oracle,
+  )]
+  pub price_history: Option<AccountLoader<'info, PriceHistoryV0>>,
+}
+
+pub fn handler(ctx: Context<SubmitPriceV1>, args: SubmitPriceArgsV1) -> Result<()> {
+  let oracle_index = find_oracle_index(
+    &ctx.accounts.price_oracle,
+    &ctx.accounts.oracle.key(),
+    args.oracle_index,
+  )?;
+
+  record_submission(
+    &mut ctx.accounts.price_oracle,
+    ctx.accounts.price_history.as_ref(),
+    oracle_index,
+    args.price,
+    Clock::get()?.unix_timestamp,
+  )
+}
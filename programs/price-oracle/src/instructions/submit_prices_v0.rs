@@ -0,0 +1,87 @@
+use crate::error::ErrorCode;
+use crate::state::*;
+use crate::submit_price_v0::{find_oracle_index, record_submission};
+use anchor_lang::prelude::*;
+
+pub const MAX_BATCH_SUBMISSIONS: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct BatchPriceArgsV0 {
+  // When not provided, the oracle is looked up by the signer
+  pub oracle_index: Option<u8>,
+  pub price: u64,
+  // Whether the price oracle account is followed by its price history in remaining accounts
+  pub has_price_history: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SubmitPricesArgsV0 {
+  pub prices: Vec<BatchPriceArgsV0>,
+}
+
+// Remaining accounts are, for each entry in args.prices, the writable price oracle followed
+// by its writable price history if `has_price_history` is set.
+#[derive(Accounts)]
+pub struct SubmitPricesV0<'info> {
+  pub oracle: Signer<'info>,
+}
+
+pub fn handler<'info>(
+  ctx: Context<'_, '_, 'info, 'info, SubmitPricesV0<'info>>,
+  args: SubmitPricesArgsV0,
+) -> Result<()> {
+  require!(
+    !args.prices.is_empty() && args.prices.len() <= MAX_BATCH_SUBMISSIONS,
+    ErrorCode::InvalidArgs
+  );
+
+  let curr_ts = Clock::get()?.unix_timestamp;
+  let oracle_key = ctx.accounts.oracle.key();
+  let mut remaining_accounts = ctx.remaining_accounts.iter();
+  for price_args in args.prices {
+    let price_oracle_info = remaining_accounts
+      .next()
+      .ok_or_else(|| error!(ErrorCode::InvalidRemainingAccounts))?;
+    require!(
+      price_oracle_info.is_writable,
+      ErrorCode::InvalidRemainingAccounts
+    );
+    let mut price_oracle = Account::<PriceOracleV0>::try_from(price_oracle_info)?;
+
+    let price_history = if price_args.has_price_history {
+      let price_history_info = remaining_accounts
+        .next()
+        .ok_or_else(|| error!(ErrorCode::InvalidRemainingAccounts))?;
+      require!(
+        price_history_info.is_writable,
+        ErrorCode::InvalidRemainingAccounts
+      );
+      let price_history = AccountLoader::<PriceHistoryV0>::try_from(price_history_info)?;
+      require_keys_eq!(
+        price_history.load()?.price_oracle,
+        price_oracle.key(),
+        ErrorCode::InvalidRemainingAccounts
+      );
+      Some(price_history)
+    } else {
+      None
+    };
+
+    let oracle_index = find_oracle_index(&price_oracle, &oracle_key, price_args.oracle_index)?;
+    record_submission(
+      &mut price_oracle,
+      price_history.as_ref(),
+      oracle_index,
+      price_args.price,
+      curr_ts,
+    )?;
+    price_oracle.exit(&crate::ID)?;
+  }
+
+  require!(
+    remaining_accounts.next().is_none(),
+    ErrorCode::InvalidRemainingAccounts
+  );
+
+  Ok(())
+}
@@ -3,11 +3,15 @@ use std::collections::HashMap;
 use crate::error::ErrorCode;
 use crate::state::*;
 use anchor_lang::prelude::*;
+use shared_utils::resize_to_fit;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
 pub struct UpdatePriceOracleArgsV0 {
   pub oracles: Option<Vec<OracleV0>>,
   pub authority: Option<Pubkey>,
+  pub config: Option<PriceOracleConfigV0>,
+  // Parallel to the oracles after this update
+  pub oracle_weights: Option<Vec<u16>>,
 }
 
 #[derive(Accounts)]
@@ -36,27 +40,33 @@ pub fn handler(ctx: Context<UpdatePriceOracleV0>, args: UpdatePriceOracleArgsV0)
       );
     }
 
-    let authorities: HashMap<String, &OracleV0> = ctx
-      .accounts
-      .price_oracle
+    let price_oracle = &ctx.accounts.price_oracle;
+    let authorities: HashMap<String, (&OracleV0, OracleInfoV0)> = price_oracle
       .oracles
       .iter()
-      .map(|oracle| (oracle.authority.to_string(), oracle))
+      .enumerate()
+      .map(|(index, oracle)| {
+        (
+          oracle.authority.to_string(),
+          (oracle, price_oracle.oracle_info(index)),
+        )
+      })
       .collect::<HashMap<_, _>>();
-    // If keeping an existing oracle, keep their price
-    let new_oracles = oracles
+    // If keeping an existing oracle, keep their price and weight
+    let (new_oracles, new_infos): (Vec<_>, Vec<_>) = oracles
       .into_iter()
       .map(|oracle| {
-        if let Some(existing) = authorities.get(&oracle.authority.to_string()) {
-          (*existing).clone()
+        if let Some((existing, info)) = authorities.get(&oracle.authority.to_string()) {
+          ((*existing).clone(), info.clone())
         } else {
-          oracle
+          (oracle, OracleInfoV0::default())
         }
       })
-      .collect::<Vec<_>>();
+      .unzip();
 
     ctx.accounts.price_oracle.num_oracles = new_oracles.len().try_into().unwrap();
     ctx.accounts.price_oracle.oracles = new_oracles;
+    ctx.accounts.price_oracle.oracle_infos = new_infos;
     ctx.accounts.price_oracle.current_price = None;
     ctx.accounts.price_oracle.last_calculated_timestamp = None;
   }
@@ -65,5 +75,28 @@ pub fn handler(ctx: Context<UpdatePriceOracleV0>, args: UpdatePriceOracleArgsV0)
     ctx.accounts.price_oracle.authority = authority;
   }
 
+  if let Some(config) = args.config {
+    ctx.accounts.price_oracle.config = config;
+  }
+
+  let price_oracle = &mut ctx.accounts.price_oracle;
+  // Accounts created before weights existed have no oracle infos
+  let num_oracles = price_oracle.oracles.len();
+  price_oracle
+    .oracle_infos
+    .resize(num_oracles, OracleInfoV0::default());
+  if let Some(oracle_weights) = args.oracle_weights {
+    require_eq!(oracle_weights.len(), num_oracles, ErrorCode::InvalidArgs);
+    for (info, weight) in price_oracle.oracle_infos.iter_mut().zip(oracle_weights) {
+      info.weight = weight;
+    }
+  }
+
+  resize_to_fit(
+    &ctx.accounts.authority,
+    &ctx.accounts.system_program.to_account_info(),
+    &ctx.accounts.price_oracle,
+  )?;
+
   Ok(())
 }
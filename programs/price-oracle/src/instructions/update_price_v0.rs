@@ -7,14 +7,33 @@ use anchor_lang::prelude::*;
 pub struct UpdatePriceV0<'info> {
   #[account(mut)]
   pub price_oracle: Box<Account<'info, PriceOracleV0>>,
+  #[account(
+    mut,
+    has_one = price_oracle,
+  )]
+  pub price_history: Option<AccountLoader<'info, PriceHistoryV0>>,
 }
 
 pub fn handler(ctx: Context<UpdatePriceV0>) -> Result<()> {
+  ctx.accounts.price_oracle.check_price_history(
+    ctx
+      .accounts
+      .price_history
+      .as_ref()
+      .map(|price_history| price_history.key()),
+  )?;
   let curr_ts = Clock::get()?.unix_timestamp;
-  let curr_price_opt = calculate_current_price(&ctx.accounts.price_oracle.oracles, curr_ts);
-  if let Some(curr_price) = curr_price_opt {
+  let current_price = calculate_current_price(&ctx.accounts.price_oracle, curr_ts);
+  ctx
+    .accounts
+    .price_oracle
+    .set_flagged(&current_price.flagged);
+  if let Some(curr_price) = current_price.price {
     ctx.accounts.price_oracle.current_price = Some(curr_price);
     ctx.accounts.price_oracle.last_calculated_timestamp = Some(curr_ts);
+    if let Some(price_history) = &ctx.accounts.price_history {
+      price_history.load_mut()?.record(curr_ts, curr_price);
+    }
     Ok(())
   } else {
     Err(error!(ErrorCode::InvalidPriceUpdate))
@@ -53,6 +53,21 @@ pub mod price_oracle {
   pub fn update_price_v0(ctx: Context<UpdatePriceV0>) -> Result<()> {
     update_price_v0::handler(ctx)
   }
+
+  pub fn initialize_price_history_v0(ctx: Context<InitializePriceHistoryV0>) -> Result<()> {
+    initialize_price_history_v0::handler(ctx)
+  }
+
+  pub fn submit_price_v1(ctx: Context<SubmitPriceV1>, args: SubmitPriceArgsV1) -> Result<()> {
+    submit_price_v1::handler(ctx, args)
+  }
+
+  pub fn submit_prices_v0<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitPricesV0<'info>>,
+    args: SubmitPricesArgsV0,
+  ) -> Result<()> {
+    submit_prices_v0::handler(ctx, args)
+  }
 }
 
 #[derive(Accounts)]
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+
 #[account]
 #[derive(Default)]
 pub struct PriceOracleV0 {
@@ -9,6 +11,37 @@ pub struct PriceOracleV0 {
   pub oracles: Vec<OracleV0>,
   pub current_price: Option<u64>,
   pub last_calculated_timestamp: Option<i64>,
+  pub config: PriceOracleConfigV0,
+  // Weights and outlier flags, parallel to `oracles`. Kept outside of `OracleV0` so that
+  // accounts created before these existed still deserialize. Empty means equal weights.
+  pub oracle_infos: Vec<OracleInfoV0>,
+  // Set once a price history exists. Every price update must then record to it.
+  pub price_history: Option<Pubkey>,
+}
+
+impl PriceOracleV0 {
+  pub fn oracle_info(&self, index: usize) -> OracleInfoV0 {
+    self.oracle_infos.get(index).cloned().unwrap_or_default()
+  }
+
+  pub fn set_flagged(&mut self, flagged: &[bool]) {
+    // Accounts created before weights existed have no oracle infos. Backfilling them takes
+    // 3 bytes per oracle, well within the slack these accounts were allocated with.
+    self
+      .oracle_infos
+      .resize(self.oracles.len(), OracleInfoV0::default());
+    for (info, flagged) in self.oracle_infos.iter_mut().zip(flagged) {
+      info.flagged = *flagged;
+    }
+  }
+
+  pub fn check_price_history(&self, price_history: Option<Pubkey>) -> Result<()> {
+    require!(
+      self.price_history == price_history,
+      ErrorCode::InvalidPriceHistory
+    );
+    Ok(())
+  }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
@@ -17,3 +50,81 @@ pub struct OracleV0 {
   pub last_submitted_timestamp: Option<i64>,
   pub last_submitted_price: Option<u64>,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PriceOracleConfigV0 {
+  // Submissions older than this are ignored. 0 means one day.
+  pub max_age_seconds: u32,
+  // Submissions further than this from the median of fresh submissions, in basis points,
+  // are flagged and left out of the price. 0 disables the check.
+  pub max_deviation_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct OracleInfoV0 {
+  // 0 is treated as 1
+  pub weight: u16,
+  // Set when the oracle's last submission fell outside of the deviation band
+  pub flagged: bool,
+}
+
+impl OracleInfoV0 {
+  pub fn effective_weight(&self) -> u64 {
+    std::cmp::max(u64::from(self.weight), 1)
+  }
+}
+
+pub const PRICE_HISTORY_SIZE: usize = 256;
+
+// Ring buffer of medians, written whenever the price oracle recalculates its price
+#[account(zero_copy)]
+pub struct PriceHistoryV0 {
+  pub price_oracle: Pubkey,
+  pub num_points: u32,
+  pub next_index: u32,
+  pub bump_seed: u8,
+  pub reserved: [u8; 7],
+  pub points: [PricePointV0; PRICE_HISTORY_SIZE],
+}
+
+#[zero_copy]
+#[derive(Default)]
+pub struct PricePointV0 {
+  pub unix_timestamp: i64,
+  pub price: u64,
+}
+
+impl PriceHistoryV0 {
+  pub fn record(&mut self, unix_timestamp: i64, price: u64) {
+    // Several submissions in the same second only keep the latest median
+    if let Some(last) = self.latest() {
+      if last.unix_timestamp == unix_timestamp {
+        let last_index = (self.next_index as usize + PRICE_HISTORY_SIZE - 1) % PRICE_HISTORY_SIZE;
+        self.points[last_index].price = price;
+        return;
+      }
+    }
+
+    self.points[self.next_index as usize] = PricePointV0 {
+      unix_timestamp,
+      price,
+    };
+    self.next_index = (self.next_index + 1) % PRICE_HISTORY_SIZE as u32;
+    self.num_points = std::cmp::min(self.num_points + 1, PRICE_HISTORY_SIZE as u32);
+  }
+
+  pub fn latest(&self) -> Option<&PricePointV0> {
+    if self.num_points == 0 {
+      return None;
+    }
+    let last_index = (self.next_index as usize + PRICE_HISTORY_SIZE - 1) % PRICE_HISTORY_SIZE;
+    self.points.get(last_index)
+  }
+
+  // Oldest to newest
+  pub fn iter_points(&self) -> impl Iterator<Item = &PricePointV0> {
+    let num_points = self.num_points as usize;
+    let start = (self.next_index as usize + PRICE_HISTORY_SIZE - num_points) % PRICE_HISTORY_SIZE;
+    (0..num_points).map(move |offset| &self.points[(start + offset) % PRICE_HISTORY_SIZE])
+  }
+}
@@ -1,29 +1,281 @@
 use crate::state::*;
 
 const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
-pub fn calculate_current_price(oracles: &[OracleV0], curr_ts: i64) -> Option<u64> {
-  let mut prices: Vec<u64> = oracles
+const BPS: u128 = 10_000;
+
+pub struct CurrentPrice {
+  pub price: Option<u64>,
+  // Parallel to the oracles. True if the oracle's fresh submission was outside of the deviation band
+  pub flagged: Vec<bool>,
+}
+
+pub fn calculate_current_price(price_oracle: &PriceOracleV0, curr_ts: i64) -> CurrentPrice {
+  let oracles = &price_oracle.oracles;
+  let config = &price_oracle.config;
+  let max_age = if config.max_age_seconds == 0 {
+    SECONDS_PER_DAY
+  } else {
+    i64::from(config.max_age_seconds)
+  };
+  let quorum = oracles.len() / 2 + 1;
+
+  // (index, price, weight) of every fresh submission
+  let fresh: Vec<(usize, u64, u64)> = oracles
     .iter()
-    .filter(|oracle| {
-      oracle.last_submitted_price.is_some()
-        && oracle
-          .last_submitted_timestamp
-          .is_some_and(|ts| curr_ts - ts <= SECONDS_PER_DAY)
+    .enumerate()
+    .filter(|(_, oracle)| {
+      oracle
+        .last_submitted_timestamp
+        .is_some_and(|ts| curr_ts - ts <= max_age)
+    })
+    .filter_map(|(index, oracle)| {
+      oracle.last_submitted_price.map(|price| {
+        (
+          index,
+          price,
+          price_oracle.oracle_info(index).effective_weight(),
+        )
+      })
     })
-    .filter_map(|oracle| oracle.last_submitted_price)
     .collect();
 
-  if prices.len() < oracles.len() / 2 + 1 {
-    return None;
+  let mut flagged = vec![false; oracles.len()];
+  if fresh.len() < quorum {
+    return CurrentPrice {
+      price: None,
+      flagged,
+    };
   }
 
-  prices.sort();
-  let n = prices.len();
-  let median = if n % 2 == 0 {
-    ((prices[n / 2 - 1] + prices[n / 2]) as f64 / 2.0).round() as u64
+  let reference = weighted_median(fresh.iter().map(|(_, price, _)| (*price, 1)).collect());
+  if let (Some(reference), true) = (reference, config.max_deviation_bps > 0) {
+    for (index, price, _) in fresh.iter() {
+      flagged[*index] = is_outlier(*price, reference, config.max_deviation_bps);
+    }
+  }
+
+  let accepted: Vec<(u64, u64)> = fresh
+    .iter()
+    .filter(|(index, _, _)| !flagged[*index])
+    .map(|(_, price, weight)| (*price, *weight))
+    .collect();
+  let price = if accepted.len() < quorum {
+    None
   } else {
-    prices[n / 2]
+    weighted_median(accepted)
   };
 
-  Some(median)
+  CurrentPrice { price, flagged }
+}
+
+fn is_outlier(price: u64, reference: u64, max_deviation_bps: u16) -> bool {
+  let deviation = u128::from(price.abs_diff(reference));
+  deviation * BPS > u128::from(reference) * u128::from(max_deviation_bps)
+}
+
+// Takes (price, weight) pairs. With equal weights this is the plain median, averaging the
+// two middle prices when the count is even.
+pub fn weighted_median(mut prices: Vec<(u64, u64)>) -> Option<u64> {
+  prices.sort();
+  let total_weight: u128 = prices.iter().map(|(_, weight)| u128::from(*weight)).sum();
+  let mut cumulative_weight: u128 = 0;
+  for (index, (price, weight)) in prices.iter().enumerate() {
+    cumulative_weight += u128::from(*weight);
+    if cumulative_weight * 2 == total_weight {
+      return prices.get(index + 1).map(|(next, _)| {
+        // Round half up
+        (u128::from(*price) + u128::from(*next)).div_ceil(2) as u64
+      });
+    }
+    if cumulative_weight * 2 > total_weight {
+      return Some(*price);
+    }
+  }
+
+  None
+}
+
+// Time-weighted average of the recorded medians over [curr_ts - lookback_seconds, curr_ts].
+// Each point holds until the next one, and the latest holds until curr_ts. Returns None if
+// the history does not reach back over the full lookback.
+pub fn calculate_twap(
+  history: &PriceHistoryV0,
+  curr_ts: i64,
+  lookback_seconds: u32,
+) -> Option<u64> {
+  if lookback_seconds == 0 {
+    return None;
+  }
+  let start_ts = curr_ts.checked_sub(i64::from(lookback_seconds))?;
+
+  let mut weighted_sum: u128 = 0;
+  let mut covered_from: Option<i64> = None;
+  let mut points = history.iter_points().peekable();
+  while let Some(point) = points.next() {
+    let end_ts = points
+      .peek()
+      .map(|next| next.unix_timestamp)
+      .unwrap_or(curr_ts)
+      .min(curr_ts);
+    let begin_ts = point.unix_timestamp.max(start_ts);
+    if end_ts <= begin_ts {
+      continue;
+    }
+
+    covered_from = Some(covered_from.map_or(begin_ts, |ts| ts.min(begin_ts)));
+    weighted_sum = weighted_sum
+      .checked_add(u128::from(point.price).checked_mul(u128::try_from(end_ts - begin_ts).ok()?)?)?;
+  }
+
+  if covered_from? > start_ts {
+    return None;
+  }
+
+  u64::try_from(weighted_sum / u128::from(lookback_seconds)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn price_oracle(prices: &[Option<u64>], weights: &[u16]) -> PriceOracleV0 {
+    PriceOracleV0 {
+      oracles: prices
+        .iter()
+        .map(|price| OracleV0 {
+          authority: Default::default(),
+          last_submitted_timestamp: price.map(|_| 0),
+          last_submitted_price: *price,
+        })
+        .collect(),
+      oracle_infos: weights
+        .iter()
+        .map(|weight| OracleInfoV0 {
+          weight: *weight,
+          flagged: false,
+        })
+        .collect(),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_plain_median() {
+    let oracle = price_oracle(&[Some(1000), Some(3000), Some(2000)], &[]);
+    assert_eq!(calculate_current_price(&oracle, 0).price, Some(2000));
+
+    let oracle = price_oracle(&[Some(1000), Some(2000), Some(3001), Some(4000)], &[]);
+    assert_eq!(calculate_current_price(&oracle, 0).price, Some(2501));
+
+    // No quorum
+    let oracle = price_oracle(&[Some(1000), None, None], &[]);
+    assert_eq!(calculate_current_price(&oracle, 0).price, None);
+  }
+
+  #[test]
+  fn test_staleness() {
+    let mut oracle = price_oracle(&[Some(1000), Some(2000), Some(3000)], &[]);
+    assert_eq!(
+      calculate_current_price(&oracle, SECONDS_PER_DAY).price,
+      Some(2000)
+    );
+    assert_eq!(
+      calculate_current_price(&oracle, SECONDS_PER_DAY + 1).price,
+      None
+    );
+
+    oracle.config.max_age_seconds = 60;
+    assert_eq!(calculate_current_price(&oracle, 60).price, Some(2000));
+    assert_eq!(calculate_current_price(&oracle, 61).price, None);
+  }
+
+  #[test]
+  fn test_outlier_rejection() {
+    let mut oracle = price_oracle(&[Some(1000), Some(1010), Some(990), Some(5000)], &[]);
+    oracle.config.max_deviation_bps = 500;
+    let result = calculate_current_price(&oracle, 0);
+    assert_eq!(result.flagged, vec![false, false, false, true]);
+    assert_eq!(result.price, Some(1000));
+
+    // Too many outliers to reach quorum
+    let mut oracle = price_oracle(&[Some(1000), Some(5000), Some(6000)], &[]);
+    oracle.config.max_deviation_bps = 500;
+    let result = calculate_current_price(&oracle, 0);
+    assert_eq!(result.flagged, vec![true, false, true]);
+    assert_eq!(result.price, None);
+  }
+
+  #[test]
+  fn test_flags_accounts_without_oracle_infos() {
+    let mut oracle = price_oracle(&[Some(1000), Some(1010), Some(5000)], &[]);
+    oracle.config.max_deviation_bps = 500;
+    let result = calculate_current_price(&oracle, 0);
+    oracle.set_flagged(&result.flagged);
+    assert_eq!(
+      oracle
+        .oracle_infos
+        .iter()
+        .map(|info| info.flagged)
+        .collect::<Vec<_>>(),
+      vec![false, false, true]
+    );
+    // Backfilled infos keep equal weights
+    assert!(oracle.oracle_infos.iter().all(|info| info.weight == 0));
+  }
+
+  #[test]
+  fn test_weighted_median() {
+    let oracle = price_oracle(&[Some(1000), Some(2000), Some(3000)], &[1, 1, 3]);
+    assert_eq!(calculate_current_price(&oracle, 0).price, Some(3000));
+
+    let oracle = price_oracle(&[Some(1000), Some(2000), Some(3000)], &[2, 0, 2]);
+    assert_eq!(calculate_current_price(&oracle, 0).price, Some(2000));
+
+    assert_eq!(weighted_median(vec![(1000, 1), (2000, 1)]), Some(1500));
+    assert_eq!(weighted_median(vec![]), None);
+  }
+
+  fn history(points: &[(i64, u64)]) -> PriceHistoryV0 {
+    let mut history = PriceHistoryV0 {
+      price_oracle: Default::default(),
+      num_points: 0,
+      next_index: 0,
+      bump_seed: 0,
+      reserved: [0; 7],
+      points: [PricePointV0::default(); PRICE_HISTORY_SIZE],
+    };
+    for (unix_timestamp, price) in points {
+      history.record(*unix_timestamp, *price);
+    }
+    history
+  }
+
+  #[test]
+  fn test_twap() {
+    let history = history(&[(0, 1000), (60, 2000), (90, 4000)]);
+    // 30s at 1000, 30s at 2000, 30s at 4000
+    assert_eq!(calculate_twap(&history, 120, 90), Some(2333));
+    // Only the latest point
+    assert_eq!(calculate_twap(&history, 120, 30), Some(4000));
+    // History does not reach back far enough
+    assert_eq!(calculate_twap(&history, 120, 121), None);
+    assert_eq!(calculate_twap(&history, 120, 120), Some(2000));
+  }
+
+  #[test]
+  fn test_price_history_ring() {
+    let mut history = history(&[(0, 1000), (0, 1500)]);
+    assert_eq!(history.num_points, 1);
+    assert_eq!(history.latest().unwrap().price, 1500);
+
+    for i in 1..(PRICE_HISTORY_SIZE as i64 + 10) {
+      history.record(i, i as u64);
+    }
+    assert_eq!(history.num_points as usize, PRICE_HISTORY_SIZE);
+    let points: Vec<i64> = history.iter_points().map(|p| p.unix_timestamp).collect();
+    assert_eq!(points.len(), PRICE_HISTORY_SIZE);
+    assert_eq!(points[0], 10);
+    assert_eq!(*points.last().unwrap(), PRICE_HISTORY_SIZE as i64 + 9);
+    assert!(points.windows(2).all(|w| w[0] < w[1]));
+  }
 }
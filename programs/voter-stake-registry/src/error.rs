@@ -163,4 +163,14 @@ pub enum VsrError {
   NoVoteForThisChoice,
   #[msg("No changes to count")]
   NoChangesToCount,
+  #[msg("Split amount must be greater than zero and less than the position amount")]
+  InvalidSplitAmount,
+  #[msg(
+    "Vesting positions can only be merged with the same vesting schedule or unlocked positions"
+  )]
+  IncompatibleLockups,
+  #[msg("Proposal is not in the voting state, so it has no voting power snapshot")]
+  ProposalNotVoting,
+  #[msg("Position has no tokens locked since the proposal snapshot")]
+  LockupAfterSnapshot,
 }
@@ -111,9 +111,12 @@ pub fn handler(ctx: Context<CountProxyVoteV0>) -> Result<()> {
   let weight = if marker.weight > 0 {
     marker.weight
   } else {
-    ctx.accounts.position.voting_power(
+    ctx.accounts.position.voting_power_at_snapshot(
       voting_mint_config,
-      ctx.accounts.registrar.clock_unix_timestamp(),
+      ctx
+        .accounts
+        .registrar
+        .proposal_snapshot_timestamp(&ctx.accounts.proposal)?,
     )?
   };
   marker.weight = weight;
@@ -177,6 +180,7 @@ pub fn handler(ctx: Context<CountProxyVoteV0>) -> Result<()> {
     ctx.accounts.position.add_recent_proposal(
       ctx.accounts.proposal.key(),
       ctx.accounts.proposal.created_at,
+      true,
     );
   }
 
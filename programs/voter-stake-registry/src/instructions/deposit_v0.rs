@@ -69,11 +69,16 @@ pub fn handler(ctx: Context<DepositV0>, args: DepositArgsV0) -> Result<()> {
 
   let registrar = &ctx.accounts.registrar;
   let position = &mut ctx.accounts.position;
+  let curr_ts = registrar.clock_unix_timestamp();
 
+  // Deposits into a vesting lockup are locked and vest with the remaining periods
+  let amount_unlocked = position.amount_unlocked(curr_ts);
   position.amount_deposited_native = position
     .amount_deposited_native
     .checked_add(amount)
     .unwrap();
+  position.rebase_vesting(curr_ts, amount_unlocked);
+  position.record_deposit(curr_ts, amount);
 
   // Get the exchange rate entry associated with this deposit.
   let mint_idx = registrar.voting_mint_config_index(ctx.accounts.deposit_token.mint)?;
@@ -83,7 +88,6 @@ pub fn handler(ctx: Context<DepositV0>, args: DepositArgsV0) -> Result<()> {
     VsrError::InvalidMint
   );
 
-  let curr_ts = registrar.clock_unix_timestamp();
   let config = &registrar.voting_mints[mint_idx];
 
   require!(
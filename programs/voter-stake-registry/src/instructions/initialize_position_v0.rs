@@ -112,24 +112,100 @@ pub struct InitializePositionV0<'info> {
   pub token_metadata_program: Program<'info, Metadata>,
 }
 
-impl<'info> InitializePositionV0<'info> {
-  fn mint_ctx(&self) -> CpiContext<'_, '_, '_, 'info, MintTo<'info>> {
-    let cpi_accounts = MintTo {
-      mint: self.mint.to_account_info(),
-      to: self.position_token_account.to_account_info(),
-      authority: self.position.to_account_info(),
-    };
-    CpiContext::new(self.token_program.to_account_info(), cpi_accounts)
-  }
+/// The accounts needed to mint the NFT that represents a freshly created position.
+pub struct PositionNftAccounts<'a, 'info> {
+  pub registrar: &'a Account<'info, Registrar>,
+  pub collection: &'a Account<'info, Mint>,
+  pub collection_metadata: AccountInfo<'info>,
+  pub collection_master_edition: AccountInfo<'info>,
+  pub position: &'a Account<'info, PositionV0>,
+  pub mint: &'a Account<'info, Mint>,
+  pub metadata: AccountInfo<'info>,
+  pub position_token_account: &'a Account<'info, TokenAccount>,
+  pub payer: AccountInfo<'info>,
+  pub system_program: AccountInfo<'info>,
+  pub token_program: AccountInfo<'info>,
+  pub token_metadata_program: AccountInfo<'info>,
+}
 
-  fn freeze_ctx(&self) -> CpiContext<'_, '_, '_, 'info, FreezeAccount<'info>> {
-    let cpi_accounts = FreezeAccount {
-      account: self.position_token_account.to_account_info(),
-      mint: self.mint.to_account_info(),
-      authority: self.position.to_account_info(),
-    };
-    CpiContext::new(self.token_program.to_account_info(), cpi_accounts)
-  }
+/// Mints and freezes the position NFT, creates its metadata and verifies it as part of
+/// the registrar collection.
+pub fn mint_position_nft(accounts: PositionNftAccounts) -> Result<()> {
+  let signer_seeds: &[&[&[u8]]] = &[position_seeds!(accounts.position)];
+
+  token::mint_to(
+    CpiContext::new_with_signer(
+      accounts.token_program.clone(),
+      MintTo {
+        mint: accounts.mint.to_account_info(),
+        to: accounts.position_token_account.to_account_info(),
+        authority: accounts.position.to_account_info(),
+      },
+      signer_seeds,
+    ),
+    1,
+  )?;
+
+  token::freeze_account(CpiContext::new_with_signer(
+    accounts.token_program.clone(),
+    FreezeAccount {
+      account: accounts.position_token_account.to_account_info(),
+      mint: accounts.mint.to_account_info(),
+      authority: accounts.position.to_account_info(),
+    },
+    signer_seeds,
+  ))?;
+
+  create_metadata_accounts_v3(
+    CpiContext::new_with_signer(
+      accounts.token_metadata_program.clone(),
+      CreateMetadataAccountsV3 {
+        metadata: accounts.metadata.clone(),
+        mint: accounts.mint.to_account_info(),
+        mint_authority: accounts.position.to_account_info(),
+        payer: accounts.payer.clone(),
+        update_authority: accounts.position.to_account_info(),
+        system_program: accounts.system_program.clone(),
+        rent: accounts.token_metadata_program.clone(),
+      },
+      signer_seeds,
+    ),
+    DataV2 {
+      name: String::from("Voting Escrow Token Position"),
+      symbol: String::from("VSR"),
+      uri: format!("{}/{}", URL, accounts.mint.key()),
+      seller_fee_basis_points: 0,
+      creators: None,
+      collection: Some(Collection {
+        key: accounts.registrar.collection.key(),
+        verified: false, // Verified in cpi
+      }),
+      uses: None,
+    },
+    true,
+    true,
+    None,
+  )?;
+
+  let verify_signer_seeds: &[&[&[u8]]] = &[registrar_seeds!(accounts.registrar)];
+
+  verify_sized_collection_item(
+    CpiContext::new_with_signer(
+      accounts.token_metadata_program.clone(),
+      VerifySizedCollectionItem {
+        payer: accounts.payer.clone(),
+        metadata: accounts.metadata.clone(),
+        collection_authority: accounts.registrar.to_account_info(),
+        collection_mint: accounts.collection.to_account_info(),
+        collection_metadata: accounts.collection_metadata.clone(),
+        collection_master_edition: accounts.collection_master_edition.clone(),
+      },
+      verify_signer_seeds,
+    ),
+    None,
+  )?;
+
+  Ok(())
 }
 
 /// Initializes a new deposit entry.
@@ -175,79 +251,23 @@ pub fn handler(ctx: Context<InitializePositionV0>, args: InitializePositionArgsV
     num_active_votes: 0,
     vote_controller: Pubkey::default(),
     recent_proposals: vec![],
+    proxied_proposals: vec![],
+    recent_deposits: vec![],
     registrar_paid_rent: 0,
   });
 
-  let signer_seeds: &[&[&[u8]]] = &[position_seeds!(ctx.accounts.position)];
-
-  token::mint_to(ctx.accounts.mint_ctx().with_signer(signer_seeds), 1)?;
-
-  token::freeze_account(ctx.accounts.freeze_ctx().with_signer(signer_seeds))?;
-
-  create_metadata_accounts_v3(
-    CpiContext::new_with_signer(
-      ctx
-        .accounts
-        .token_metadata_program
-        .to_account_info()
-        .clone(),
-      CreateMetadataAccountsV3 {
-        metadata: ctx.accounts.metadata.to_account_info().clone(),
-        mint: ctx.accounts.mint.to_account_info().clone(),
-        mint_authority: ctx.accounts.position.to_account_info().clone(),
-        payer: ctx.accounts.payer.to_account_info().clone(),
-        update_authority: ctx.accounts.position.to_account_info().clone(),
-        system_program: ctx.accounts.system_program.to_account_info().clone(),
-        rent: ctx
-          .accounts
-          .token_metadata_program
-          .to_account_info()
-          .clone(),
-      },
-      signer_seeds,
-    ),
-    DataV2 {
-      name: String::from("Voting Escrow Token Position"),
-      symbol: String::from("VSR"),
-      uri: format!("{}/{}", URL, ctx.accounts.mint.key()),
-      seller_fee_basis_points: 0,
-      creators: None,
-      collection: Some(Collection {
-        key: ctx.accounts.registrar.collection.key(),
-        verified: false, // Verified in cpi
-      }),
-      uses: None,
-    },
-    true,
-    true,
-    None,
-  )?;
-
-  let verify_signer_seeds: &[&[&[u8]]] = &[registrar_seeds!(ctx.accounts.registrar)];
-
-  verify_sized_collection_item(
-    CpiContext::new_with_signer(
-      ctx
-        .accounts
-        .token_metadata_program
-        .to_account_info()
-        .clone(),
-      VerifySizedCollectionItem {
-        payer: ctx.accounts.payer.to_account_info().clone(),
-        metadata: ctx.accounts.metadata.to_account_info().clone(),
-        collection_authority: ctx.accounts.registrar.to_account_info().clone(),
-        collection_mint: ctx.accounts.collection.to_account_info().clone(),
-        collection_metadata: ctx.accounts.collection_metadata.to_account_info().clone(),
-        collection_master_edition: ctx
-          .accounts
-          .collection_master_edition
-          .to_account_info()
-          .clone(),
-      },
-      verify_signer_seeds,
-    ),
-    None,
-  )?;
-
-  Ok(())
+  mint_position_nft(PositionNftAccounts {
+    registrar: &ctx.accounts.registrar,
+    collection: &ctx.accounts.collection,
+    collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+    collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+    position: &ctx.accounts.position,
+    mint: &ctx.accounts.mint,
+    metadata: ctx.accounts.metadata.to_account_info(),
+    position_token_account: &ctx.accounts.position_token_account,
+    payer: ctx.accounts.payer.to_account_info(),
+    system_program: ctx.accounts.system_program.to_account_info(),
+    token_program: ctx.accounts.token_program.to_account_info(),
+    token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+  })
 }
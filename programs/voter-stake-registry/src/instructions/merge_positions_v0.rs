@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+
+use super::transfer_v0::*;
+
+#[derive(Accounts)]
+pub struct MergePositionsV0<'info> {
+  pub common: TransferV0<'info>,
+  // The merged lockup can be stricter than the target's, so the target must be owned by the same authority
+  #[account(
+    token::authority = common.position_authority,
+    constraint = target_position_token_account.mint == common.target_position.mint,
+    constraint = target_position_token_account.amount > 0
+  )]
+  pub target_position_token_account: Box<Account<'info, TokenAccount>>,
+}
+
+/// Merges all tokens of the source position into the target position.
+///
+/// The target first takes the stricter of the two lockups, see `Lockup::merge`, after which
+/// the tokens move over as with transfer_v0. No token unlocks earlier than it would have
+/// before the merge. The target keeps its own genesis end and recent proposals.
+///
+/// The emptied source position can then be closed with close_position_v0.
+pub fn handler(ctx: Context<MergePositionsV0>) -> Result<()> {
+  let common = &mut ctx.accounts.common;
+  let curr_ts = common.registrar.clock_unix_timestamp();
+  let amount = common.source_position.amount_deposited_native;
+  let amount_unlocked = common
+    .source_position
+    .amount_unlocked(curr_ts)
+    .checked_add(common.target_position.amount_unlocked(curr_ts))
+    .unwrap();
+
+  let lockup = common
+    .target_position
+    .lockup
+    .merge(&common.source_position.lockup, curr_ts)?;
+  // The target's own tokens were not locked this long before the merge
+  if lockup.kind != common.target_position.lockup.kind
+    || lockup.end_ts != common.target_position.lockup.end_ts
+  {
+    common.target_position.record_relock(curr_ts);
+  }
+  common.target_position.lockup = lockup;
+  common.transfer(amount)?;
+  // Tokens that were withdrawable stay withdrawable under a merged vesting schedule
+  common
+    .target_position
+    .rebase_vesting(curr_ts, amount_unlocked);
+
+  msg!(
+    "Merged amount {} into lockup kind {:?} with {} seconds left",
+    amount,
+    common.target_position.lockup.kind,
+    common.target_position.lockup.seconds_left(curr_ts),
+  );
+
+  Ok(())
+}
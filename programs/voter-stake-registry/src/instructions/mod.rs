@@ -6,6 +6,7 @@ pub use deposit_v0::*;
 pub use initialize_position_v0::*;
 pub use initialize_registrar_v0::*;
 pub use ledger_transfer_position_v0::*;
+pub use merge_positions_v0::*;
 pub use proxied_relinquish_vote_v0::*;
 pub use proxied_relinquish_vote_v1::*;
 pub use proxied_vote_v0::*;
@@ -15,6 +16,7 @@ pub use relinquish_expired_vote_v0::*;
 pub use relinquish_vote_v1::*;
 pub use reset_lockup_v0::*;
 pub use set_time_offset_v0::*;
+pub use split_position_v0::*;
 pub use temp_release_position_v0::*;
 pub use transfer_position_v0::*;
 pub use transfer_v0::*;
@@ -31,6 +33,7 @@ pub mod deposit_v0;
 pub mod initialize_position_v0;
 pub mod initialize_registrar_v0;
 pub mod ledger_transfer_position_v0;
+pub mod merge_positions_v0;
 pub mod proxied_relinquish_vote_v0;
 pub mod proxied_relinquish_vote_v1;
 pub mod proxied_vote_v0;
@@ -40,6 +43,7 @@ pub mod relinquish_expired_vote_v0;
 pub mod relinquish_vote_v1;
 pub mod reset_lockup_v0;
 pub mod set_time_offset_v0;
+pub mod split_position_v0;
 pub mod temp_release_position_v0;
 pub mod transfer_position_v0;
 pub mod transfer_v0;
@@ -110,9 +110,12 @@ pub fn handler(ctx: Context<ProxiedVoteV0>, args: VoteArgsV0) -> Result<()> {
   let weight = if marker.weight > 0 {
     marker.weight
   } else {
-    ctx.accounts.position.voting_power(
+    ctx.accounts.position.voting_power_at_snapshot(
       voting_mint_config,
-      ctx.accounts.registrar.clock_unix_timestamp(),
+      ctx
+        .accounts
+        .registrar
+        .proposal_snapshot_timestamp(&ctx.accounts.proposal)?,
     )?
   };
   marker.weight = weight;
@@ -140,6 +143,7 @@ pub fn handler(ctx: Context<ProxiedVoteV0>, args: VoteArgsV0) -> Result<()> {
   ctx.accounts.position.add_recent_proposal(
     ctx.accounts.proposal.key(),
     ctx.accounts.proposal.created_at,
+    true,
   );
   ctx.accounts.position.registrar_paid_rent = u64::try_from(
     i64::try_from(ctx.accounts.position.registrar_paid_rent).unwrap()
@@ -71,6 +71,7 @@ pub fn handler(ctx: Context<ResetLockupV0>, args: ResetLockupArgsV0) -> Result<(
 
   // Change the deposit entry.
   position.lockup = Lockup::new_from_periods(kind, curr_ts, curr_ts, periods)?;
+  position.record_relock(curr_ts);
   if curr_ts <= mint_config.genesis_vote_power_multiplier_expiration_ts {
     position.genesis_end = i64::try_from(position.lockup.seconds_left(curr_ts)).unwrap() + curr_ts;
   }
@@ -0,0 +1,201 @@
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+  associated_token::AssociatedToken,
+  metadata::Metadata,
+  token::{self, Mint, Token, TokenAccount},
+};
+
+use super::{mint_position_nft, PositionNftAccounts};
+use crate::{error::VsrError, position_seeds, state::*};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct SplitPositionArgsV0 {
+  pub amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct SplitPositionV0<'info> {
+  #[account(
+    has_one = collection
+  )]
+  pub registrar: Box<Account<'info, Registrar>>,
+  /// CHECK: Checked conditionally based on registrar
+  #[account(
+    constraint = registrar.position_update_authority.map(|k|
+      k == *position_update_authority.key
+    ).unwrap_or(true) @ VsrError::UnauthorizedPositionUpdateAuthority,
+  )]
+  pub position_update_authority: Signer<'info>,
+  pub collection: Box<Account<'info, Mint>>,
+  /// CHECK: Handled by cpi
+  #[account(
+    mut,
+    seeds = ["metadata".as_bytes(), token_metadata_program.key().as_ref(), collection.key().as_ref()],
+    seeds::program = token_metadata_program.key(),
+    bump,
+  )]
+  pub collection_metadata: UncheckedAccount<'info>,
+  /// CHECK: Handled By cpi account
+  #[account(
+    seeds = ["metadata".as_bytes(), token_metadata_program.key().as_ref(), collection.key().as_ref(), "edition".as_bytes()],
+    seeds::program = token_metadata_program.key(),
+    bump,
+  )]
+  pub collection_master_edition: UncheckedAccount<'info>,
+  #[account(
+    mut,
+    seeds = [b"position".as_ref(), source_mint.key().as_ref()],
+    bump = source_position.bump_seed,
+    constraint = source_position.num_active_votes == 0 @ VsrError::ActiveVotesExist,
+    has_one = registrar,
+    constraint = source_position.mint == source_mint.key(),
+  )]
+  pub source_position: Box<Account<'info, PositionV0>>,
+  pub source_mint: Box<Account<'info, Mint>>,
+  #[account(
+    token::mint = source_mint,
+    token::authority = position_authority,
+    constraint = source_position_token_account.amount > 0
+  )]
+  pub source_position_token_account: Box<Account<'info, TokenAccount>>,
+  pub position_authority: Signer<'info>,
+  #[account(
+    mut,
+    associated_token::authority = source_position,
+    associated_token::mint = deposit_mint,
+  )]
+  pub source_vault: Box<Account<'info, TokenAccount>>,
+  #[account(
+    init,
+    payer = payer,
+    seeds = [b"position".as_ref(), mint.key().as_ref()],
+    bump,
+    space = 8 + size_of::<PositionV0>() + 60 + source_position.recent_proposals.len() * RecentProposal::INIT_SPACE + source_position.proxied_proposals.len() * 32 + source_position.recent_deposits.len() * RecentDepositV0::INIT_SPACE,
+  )]
+  pub position: Box<Account<'info, PositionV0>>,
+  #[account(
+    mut,
+    constraint = mint.supply == 0,
+    mint::decimals = 0,
+    mint::authority = position,
+    mint::freeze_authority = position,
+  )]
+  pub mint: Box<Account<'info, Mint>>,
+  #[account(
+    mut,
+    seeds = ["metadata".as_bytes(), token_metadata_program.key().as_ref(), mint.key().as_ref()],
+    seeds::program = token_metadata_program.key(),
+    bump,
+  )]
+  /// CHECK: Checked by cpi
+  pub metadata: UncheckedAccount<'info>,
+  #[account(
+    init_if_needed,
+    payer = payer,
+    associated_token::mint = mint,
+    associated_token::authority = recipient,
+  )]
+  pub position_token_account: Box<Account<'info, TokenAccount>>,
+  /// CHECK: needed for token account init
+  pub recipient: UncheckedAccount<'info>,
+  #[account(
+    init_if_needed,
+    associated_token::authority = position,
+    associated_token::mint = deposit_mint,
+    payer = payer
+  )]
+  pub vault: Box<Account<'info, TokenAccount>>,
+  #[account(mut)]
+  pub payer: Signer<'info>,
+  pub deposit_mint: Box<Account<'info, Mint>>,
+
+  pub system_program: Program<'info, System>,
+  pub token_program: Program<'info, Token>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub token_metadata_program: Program<'info, Metadata>,
+}
+
+impl<'info> SplitPositionV0<'info> {
+  pub fn transfer_ctx(&self) -> CpiContext<'_, '_, '_, 'info, token::Transfer<'info>> {
+    let program = self.token_program.to_account_info();
+    let accounts = token::Transfer {
+      from: self.source_vault.to_account_info(),
+      to: self.vault.to_account_info(),
+      authority: self.source_position.to_account_info(),
+    };
+    CpiContext::new(program, accounts)
+  }
+}
+
+/// Splits `amount` locked tokens off of the source position into a new position.
+///
+/// The new position keeps the exact lockup (kind, start and end), genesis multiplier
+/// and recent proposals of the source position, so the voting power of the two pieces
+/// always adds up to the voting power of the original position.
+///
+/// The primary usecases are:
+/// - partially undelegating or moving part of a delegated position to another subdao
+/// - giving away part of a position without changing its lockup
+pub fn handler(ctx: Context<SplitPositionV0>, args: SplitPositionArgsV0) -> Result<()> {
+  let SplitPositionArgsV0 { amount } = args;
+  let source_position = &mut ctx.accounts.source_position;
+
+  require!(
+    amount > 0 && amount < source_position.amount_deposited_native,
+    VsrError::InvalidSplitAmount
+  );
+  let config =
+    &ctx.accounts.registrar.voting_mints[usize::from(source_position.voting_mint_config_idx)];
+  require_eq!(
+    ctx.accounts.deposit_mint.key(),
+    config.mint,
+    VsrError::InvalidMint
+  );
+
+  let recent_deposits = source_position.split_recent_deposits(amount);
+  source_position.amount_deposited_native = source_position
+    .amount_deposited_native
+    .checked_sub(amount)
+    .unwrap();
+
+  ctx.accounts.position.set_inner(PositionV0 {
+    registrar: ctx.accounts.registrar.key(),
+    mint: ctx.accounts.mint.key(),
+    bump_seed: ctx.bumps.position,
+    amount_deposited_native: amount,
+    voting_mint_config_idx: source_position.voting_mint_config_idx,
+    lockup: source_position.lockup.clone(),
+    genesis_end: source_position.genesis_end,
+    num_active_votes: 0,
+    vote_controller: Pubkey::default(),
+    recent_proposals: source_position.recent_proposals.clone(),
+    proxied_proposals: source_position.proxied_proposals.clone(),
+    recent_deposits,
+    registrar_paid_rent: 0,
+  });
+
+  token::transfer(
+    ctx
+      .accounts
+      .transfer_ctx()
+      .with_signer(&[position_seeds!(ctx.accounts.source_position)]),
+    amount,
+  )?;
+
+  mint_position_nft(PositionNftAccounts {
+    registrar: &ctx.accounts.registrar,
+    collection: &ctx.accounts.collection,
+    collection_metadata: ctx.accounts.collection_metadata.to_account_info(),
+    collection_master_edition: ctx.accounts.collection_master_edition.to_account_info(),
+    position: &ctx.accounts.position,
+    mint: &ctx.accounts.mint,
+    metadata: ctx.accounts.metadata.to_account_info(),
+    position_token_account: &ctx.accounts.position_token_account,
+    payer: ctx.accounts.payer.to_account_info(),
+    system_program: ctx.accounts.system_program.to_account_info(),
+    token_program: ctx.accounts.token_program.to_account_info(),
+    token_metadata_program: ctx.accounts.token_metadata_program.to_account_info(),
+  })
+}
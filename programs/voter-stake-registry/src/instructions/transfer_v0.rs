@@ -74,6 +74,41 @@ impl<'info> TransferV0<'info> {
     };
     CpiContext::new(program, accounts)
   }
+
+  pub fn transfer(&mut self, amount: u64) -> Result<()> {
+    let registrar = &self.registrar;
+    let source_position = &mut self.source_position;
+    let target_position = &mut self.target_position;
+    let curr_ts = registrar.clock_unix_timestamp();
+
+    let config = &registrar.voting_mints[usize::from(source_position.voting_mint_config_idx)];
+    require_eq!(self.deposit_mint.key(), config.mint, VsrError::InvalidMint);
+    source_position.check_transfer_to(target_position, config, curr_ts)?;
+
+    // Vested tokens of the source are transferred first
+    let source_amount_unlocked = source_position.amount_unlocked(curr_ts);
+    source_position.amount_deposited_native = source_position
+      .amount_deposited_native
+      .checked_sub(amount)
+      .unwrap();
+    source_position.rebase_vesting(curr_ts, source_amount_unlocked.saturating_sub(amount));
+
+    // Add target amounts
+    let target_amount_unlocked = target_position.amount_unlocked(curr_ts);
+    target_position.amount_deposited_native = target_position
+      .amount_deposited_native
+      .checked_add(amount)
+      .unwrap();
+    target_position.rebase_vesting(curr_ts, target_amount_unlocked);
+    target_position.record_deposit(curr_ts, amount);
+
+    token::transfer(
+      self
+        .transfer_ctx()
+        .with_signer(&[position_seeds!(self.source_position)]),
+      amount,
+    )
+  }
 }
 
 /// Transfers locked tokens from the source position to the target position.
@@ -87,64 +122,5 @@ impl<'info> TransferV0<'info> {
 ///   locked position to start the unlocking process (reset_lockup could only
 ///   change the whole position to "cliff")
 pub fn handler(ctx: Context<TransferV0>, args: TransferArgsV0) -> Result<()> {
-  let TransferArgsV0 { amount } = args;
-  let registrar = &ctx.accounts.registrar;
-  let source_position = &mut ctx.accounts.source_position;
-  let target_position = &mut ctx.accounts.target_position;
-  let curr_ts = registrar.clock_unix_timestamp();
-
-  let source_seconds_left = source_position.lockup.seconds_left(curr_ts);
-  let source_strictness = source_position.lockup.kind.strictness();
-  let source_mint_idx = source_position.voting_mint_config_idx;
-
-  source_position.amount_deposited_native = source_position
-    .amount_deposited_native
-    .checked_sub(amount)
-    .unwrap();
-
-  // Check target compatibility
-  let config = &registrar.voting_mints[usize::from(source_mint_idx)];
-  let mint_id = config.mint;
-  require_eq!(
-    ctx.accounts.deposit_mint.key(),
-    mint_id,
-    VsrError::InvalidMint
-  );
-  require_eq!(
-    target_position.voting_mint_config_idx,
-    source_mint_idx,
-    VsrError::InvalidMint
-  );
-  require_gte!(
-    target_position.lockup.seconds_left(curr_ts),
-    source_seconds_left,
-    VsrError::InvalidLockupPeriod
-  );
-  require_gte!(
-    target_position.lockup.kind.strictness(),
-    source_strictness,
-    VsrError::InvalidLockupKind
-  );
-
-  require!(
-    curr_ts >= target_position.genesis_end
-      || curr_ts <= config.genesis_vote_power_multiplier_expiration_ts,
-    VsrError::NoDepositOnGenesisPositions
-  );
-
-  // Add target amounts
-  target_position.amount_deposited_native = target_position
-    .amount_deposited_native
-    .checked_add(amount)
-    .unwrap();
-
-  token::transfer(
-    ctx
-      .accounts
-      .transfer_ctx()
-      .with_signer(&[position_seeds!(ctx.accounts.source_position)]),
-    amount,
-  )?;
-
-  Ok(())
+  ctx.accounts.transfer(args.amount)
 }
@@ -95,12 +95,16 @@ pub fn handler(ctx: Context<VoteV0>, args: VoteArgsV0) -> Result<()> {
 
   // Use the original voting weight for this nft until all votes removed
   // This prevents inconsistensies with decaying positions
+  // Weight is taken at the proposal snapshot, so it does not depend on when the vote is cast
   let weight = if marker.weight > 0 {
     marker.weight
   } else {
-    ctx.accounts.position.voting_power(
+    ctx.accounts.position.voting_power_at_snapshot(
       voting_mint_config,
-      ctx.accounts.registrar.clock_unix_timestamp(),
+      ctx
+        .accounts
+        .registrar
+        .proposal_snapshot_timestamp(&ctx.accounts.proposal)?,
     )?
   };
   marker.weight = weight;
@@ -128,6 +132,7 @@ pub fn handler(ctx: Context<VoteV0>, args: VoteArgsV0) -> Result<()> {
   ctx.accounts.position.add_recent_proposal(
     ctx.accounts.proposal.key(),
     ctx.accounts.proposal.created_at,
+    false,
   );
   ctx.accounts.position.registrar_paid_rent = u64::try_from(
     i64::try_from(ctx.accounts.position.registrar_paid_rent).unwrap()
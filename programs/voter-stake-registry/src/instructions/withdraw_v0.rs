@@ -70,7 +70,8 @@ impl<'info> WithdrawV0<'info> {
   }
 }
 
-/// Withdraws tokens from a deposit entry, if they are unlocked
+/// Withdraws tokens from a deposit entry, if they are unlocked. Vesting lockups
+/// unlock a fraction of the tokens every period.
 ///
 /// `deposit_entry_index`: The deposit entry to withdraw from.
 pub fn handler(ctx: Context<WithdrawV0>, args: WithdrawArgsV0) -> Result<()> {
@@ -99,10 +100,13 @@ pub fn handler(ctx: Context<WithdrawV0>, args: WithdrawArgsV0) -> Result<()> {
     amount,
     VsrError::InternalProgramError
   );
+  let amount_unlocked = position.amount_unlocked(curr_ts);
   position.amount_deposited_native = position
     .amount_deposited_native
     .checked_sub(amount)
     .unwrap();
+  // Vested tokens that were not withdrawn stay withdrawable
+  position.rebase_vesting(curr_ts, amount_unlocked.checked_sub(amount).unwrap());
 
   msg!(
     "Withdrew amount {} with lockup kind {:?} and {} seconds left",
@@ -164,4 +164,12 @@ pub mod voter_stake_registry {
   pub fn transfer_position_v0(ctx: Context<TransferPositionV0>) -> Result<()> {
     instructions::transfer_position_v0::handler(ctx)
   }
+
+  pub fn split_position_v0(ctx: Context<SplitPositionV0>, args: SplitPositionArgsV0) -> Result<()> {
+    instructions::split_position_v0::handler(ctx, args)
+  }
+
+  pub fn merge_positions_v0(ctx: Context<MergePositionsV0>) -> Result<()> {
+    instructions::merge_positions_v0::handler(ctx)
+  }
 }
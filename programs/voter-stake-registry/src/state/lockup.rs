@@ -105,6 +105,37 @@ impl Lockup {
     }
   }
 
+  // Number of the current period, counted from 0 at start_ts. Vesting lockups
+  // have vested this many periods.
+  pub fn period_current(&self, curr_ts: i64) -> u64 {
+    let period_secs = self.kind.period_secs();
+    if curr_ts < self.start_ts || period_secs == 0 {
+      return 0;
+    }
+    (curr_ts - self.start_ts) as u64 / period_secs
+  }
+
+  // Total number of periods in the lockup.
+  pub fn periods_total(&self) -> u64 {
+    let period_secs = self.kind.period_secs();
+    if period_secs == 0 {
+      return 0;
+    }
+    self.total_seconds() / period_secs
+  }
+
+  // Number of periods that have not fully passed yet, including the current one.
+  pub fn periods_left(&self, curr_ts: i64) -> u64 {
+    let period_secs = self.kind.period_secs();
+    if period_secs == 0 {
+      return 0;
+    }
+    if curr_ts < self.start_ts {
+      return self.periods_total();
+    }
+    self.seconds_left(curr_ts).div_ceil(period_secs)
+  }
+
   // Number of seconds since the lockup expired.
   // Returns 0 if the lockup hasn't expired
   pub fn seconds_since_expiry(&self, curr_ts: i64) -> u64 {
@@ -113,6 +144,41 @@ impl Lockup {
     }
     (curr_ts - self.end_ts) as u64
   }
+
+  // Lockup of the tokens of two merged positions, which is at least as strict
+  // as both of them. Cliff and constant lockups lock everything for the longest
+  // of the remaining durations, restarting at curr_ts.
+  //
+  // A vesting schedule can only absorb unlocked tokens, so vesting lockups can
+  // only be merged with an identical schedule or with no lockup.
+  pub fn merge(&self, other: &Lockup, curr_ts: i64) -> Result<Lockup> {
+    let (stricter, looser) = if self.kind.strictness() >= other.kind.strictness() {
+      (self, other)
+    } else {
+      (other, self)
+    };
+    if stricter.kind.is_vesting() {
+      require!(
+        looser.kind.is_none() || (looser.kind == stricter.kind && looser.end_ts == stricter.end_ts),
+        VsrError::IncompatibleLockups
+      );
+      return Ok(stricter.clone());
+    }
+
+    let kind = if self.kind == LockupKind::Constant || other.kind == LockupKind::Constant {
+      LockupKind::Constant
+    } else {
+      stricter.kind
+    };
+    let seconds = self.seconds_left(curr_ts).max(other.seconds_left(curr_ts));
+    Ok(Lockup {
+      start_ts: curr_ts,
+      end_ts: curr_ts
+        .checked_add(i64::try_from(seconds).unwrap())
+        .unwrap(),
+      kind,
+    })
+  }
 }
 
 #[repr(u8)]
@@ -128,6 +194,12 @@ pub enum LockupKind {
   // Lock up permanently. The number of days specified becomes the minimum
   // unlock period when the deposit (or a part of it) is changed to Cliff.
   Constant,
+
+  // Lock up for a number of days, where a linear fraction vests each day.
+  Daily,
+
+  // Lock up for a number of months, where a linear fraction vests each month.
+  Monthly,
 }
 
 impl LockupKind {
@@ -140,15 +212,22 @@ impl LockupKind {
       LockupKind::None => 0,
       LockupKind::Cliff => SECS_PER_DAY,    // arbitrary choice
       LockupKind::Constant => SECS_PER_DAY, // arbitrary choice
+      LockupKind::Daily => SECS_PER_DAY,
+      LockupKind::Monthly => SECS_PER_MONTH,
     }
   }
 
-  // Lockups cannot decrease in strictness
+  // Lockups cannot decrease in strictness. Vesting releases tokens before the end of the
+  // lockup, so it ranks below Cliff and Constant. Those moved from 1 to 3 to make room; the
+  // values are only ever compared with each other and never stored, so none of the existing
+  // transitions changed.
   pub fn strictness(&self) -> u8 {
     match self {
       LockupKind::None => 0,
-      LockupKind::Cliff => 1, // can freely move between Cliff and Constant
-      LockupKind::Constant => 1,
+      LockupKind::Daily => 1,
+      LockupKind::Monthly => 2,
+      LockupKind::Cliff => 3, // can freely move between Cliff and Constant
+      LockupKind::Constant => 3,
     }
   }
 
@@ -157,6 +236,12 @@ impl LockupKind {
       LockupKind::None => true,
       LockupKind::Cliff => false,
       LockupKind::Constant => false,
+      LockupKind::Daily => false,
+      LockupKind::Monthly => false,
     }
   }
+
+  pub fn is_vesting(&self) -> bool {
+    matches!(self, LockupKind::Daily | LockupKind::Monthly)
+  }
 }
@@ -31,6 +31,10 @@ pub struct PositionV0 {
   pub vote_controller: Pubkey,
   pub registrar_paid_rent: u64,
   pub recent_proposals: Vec<RecentProposal>,
+  // Proposals in `recent_proposals` that were voted on by a proxy rather than the owner
+  pub proxied_proposals: Vec<Pubkey>,
+  // Tokens added to the position recently, excluded from proposals snapshotted before them
+  pub recent_deposits: Vec<RecentDepositV0>,
 }
 
 #[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize, Default)]
@@ -39,6 +43,15 @@ pub struct RecentProposal {
   pub ts: i64,
 }
 
+// Deposits are kept within the account's padding, so older ones are folded into newer ones
+pub const MAX_RECENT_DEPOSITS: usize = 2;
+
+#[derive(Debug, InitSpace, Clone, AnchorSerialize, AnchorDeserialize, Default)]
+pub struct RecentDepositV0 {
+  pub ts: i64,
+  pub amount: u64,
+}
+
 const ONE_WEEK: i64 = 60 * 60 * 24 * 7;
 impl RecentProposal {
   pub fn is_in_progress(&self, curr_ts: i64) -> bool {
@@ -78,6 +91,15 @@ impl PositionV0 {
   // each point in time the lockup should be equivalent to a new lockup
   // made for the remaining time period.
   //
+  // ## Linear Vesting Lockup
+  //
+  // Daily and monthly lockups vest a linear fraction of the tokens at the end
+  // of every period. Each fraction is valued like a cliff lockup ending at its
+  // own vesting date, so the voting power steps down as periods vest.
+  //
+  // The vested tokens can be withdrawn, after which the lockup start is moved
+  // so the remaining tokens keep vesting on the original schedule.
+  //
   pub fn voting_power(
     &self,
     voting_mint_config: &VotingMintConfigV0,
@@ -113,6 +135,29 @@ impl PositionV0 {
       .ok_or_else(|| error!(VsrError::VoterWeightOverflow))
   }
 
+  // Voting power at a proposal's snapshot, so that every vote on the proposal is
+  // weighted at the same point of the decay.
+  //
+  // Only tokens that were locked at the snapshot count. Tokens deposited or relocked
+  // after it would be valued for time they were not locked, so a position without
+  // any other tokens cannot vote on the proposal.
+  pub fn voting_power_at_snapshot(
+    &self,
+    voting_mint_config: &VotingMintConfigV0,
+    snapshot_ts: i64,
+  ) -> Result<u128> {
+    let mut position = self.clone();
+    position.amount_deposited_native = self
+      .amount_deposited_native
+      .saturating_sub(self.deposited_after(snapshot_ts));
+    require_gt!(
+      position.amount_deposited_native,
+      0,
+      VsrError::LockupAfterSnapshot
+    );
+    position.voting_power(voting_mint_config, snapshot_ts)
+  }
+
   // Vote power contribution from locked funds only.
   pub fn voting_power_locked(
     &self,
@@ -132,6 +177,9 @@ impl PositionV0 {
       LockupKind::Constant => {
         self.voting_power_cliff(curr_ts, max_locked_vote_weight, lockup_saturation_secs)
       }
+      LockupKind::Daily | LockupKind::Monthly => {
+        self.voting_power_linear_vesting(curr_ts, max_locked_vote_weight, lockup_saturation_secs)
+      }
     }
   }
 
@@ -151,12 +199,217 @@ impl PositionV0 {
     )
   }
 
+  // Vote power contribution of a vesting lockup. Sums the cliff voting power of
+  // each period's fraction of the tokens:
+  //
+  // ```
+  //    voting_power = max_locked_vote_weight / periods_total
+  //                   * sum(min(secs_to_vest_i, lockup_saturation_secs) / lockup_saturation_secs)
+  // ```
+  //
+  // over the periods that have not vested yet, where secs_to_vest_i of the
+  // i-th of those is secs_to_closest_cliff + i * period_secs.
+  pub fn voting_power_linear_vesting(
+    &self,
+    curr_ts: i64,
+    max_locked_vote_weight: u128,
+    lockup_saturation_secs: u64,
+  ) -> Result<u128> {
+    let periods_left = self.lockup.periods_left(curr_ts);
+    let periods_total = self.lockup.periods_total();
+    let period_secs = self.lockup.kind.period_secs();
+    if periods_left == 0 || periods_total == 0 {
+      return Ok(0);
+    }
+
+    let secs_to_closest_cliff = self
+      .lockup
+      .seconds_left(curr_ts)
+      .checked_sub(period_secs.checked_mul(periods_left - 1).unwrap())
+      .unwrap();
+
+    // Every remaining period is saturated
+    if secs_to_closest_cliff >= lockup_saturation_secs {
+      return Ok(
+        max_locked_vote_weight
+          .checked_mul(periods_left as u128)
+          .unwrap()
+          .checked_div(periods_total as u128)
+          .unwrap(),
+      );
+    }
+
+    // The first q periods are not saturated, the remaining r are
+    let q = min(
+      (lockup_saturation_secs - secs_to_closest_cliff) / period_secs + 1,
+      periods_left,
+    ) as u128;
+    let r = periods_left as u128 - q;
+
+    // secs_to_closest_cliff * q + period_secs * (0 + 1 + ... + (q - 1))
+    let lockup_secs_unsaturated = (secs_to_closest_cliff as u128)
+      .checked_mul(q)
+      .unwrap()
+      .checked_add((period_secs as u128).checked_mul(q * (q - 1) / 2).unwrap())
+      .unwrap();
+    let lockup_secs_saturated = r.checked_mul(lockup_saturation_secs as u128).unwrap();
+    let denominator = (periods_total as u128)
+      .checked_mul(lockup_saturation_secs as u128)
+      .unwrap();
+
+    Ok(
+      max_locked_vote_weight
+        .checked_mul(
+          lockup_secs_unsaturated
+            .checked_add(lockup_secs_saturated)
+            .unwrap(),
+        )
+        .ok_or_else(|| error!(VsrError::VoterWeightOverflow))?
+        .checked_div(denominator)
+        .unwrap(),
+    )
+  }
+
   pub fn amount_unlocked(&self, curr_ts: i64) -> u64 {
     if self.lockup.end_ts <= curr_ts {
-      self.amount_deposited_native
-    } else {
-      0
+      return self.amount_deposited_native;
+    }
+    if !self.lockup.kind.is_vesting() {
+      return 0;
+    }
+
+    let periods_total = self.lockup.periods_total();
+    if periods_total == 0 {
+      return 0;
     }
+    (self.amount_deposited_native as u128)
+      .checked_mul(self.lockup.period_current(curr_ts) as u128)
+      .unwrap()
+      .checked_div(periods_total as u128)
+      .unwrap() as u64
+  }
+
+  // Moves the start of a vesting lockup after amount_deposited_native changed,
+  // so that at most `unlocked` tokens stay withdrawable and the remaining
+  // tokens keep vesting until the same end date.
+  //
+  // The lockup cannot store the amount it started vesting with, so the vested
+  // share is instead encoded by the number of periods that passed.
+  pub fn rebase_vesting(&mut self, curr_ts: i64, unlocked: u64) {
+    if !self.lockup.kind.is_vesting()
+      || curr_ts < self.lockup.start_ts
+      || self.lockup.expired(curr_ts)
+    {
+      return;
+    }
+    let locked = self.amount_deposited_native.saturating_sub(unlocked);
+    if locked == 0 {
+      return;
+    }
+
+    let periods_left = self.lockup.periods_left(curr_ts);
+    // Rounding down never unlocks more than intended
+    let periods_vested = min(
+      (unlocked as u128)
+        .checked_mul(periods_left as u128)
+        .unwrap()
+        .checked_div(locked as u128)
+        .unwrap(),
+      self.lockup.period_current(curr_ts) as u128,
+    ) as u64;
+    self.lockup.start_ts = self
+      .lockup
+      .end_ts
+      .checked_sub(
+        i64::try_from(
+          (periods_left + periods_vested)
+            .checked_mul(self.lockup.kind.period_secs())
+            .unwrap(),
+        )
+        .unwrap(),
+      )
+      .unwrap();
+  }
+
+  // Checks that tokens of this position can move into `target` without unlocking any earlier
+  pub fn check_transfer_to(
+    &self,
+    target: &PositionV0,
+    voting_mint_config: &VotingMintConfigV0,
+    curr_ts: i64,
+  ) -> Result<()> {
+    require_eq!(
+      target.voting_mint_config_idx,
+      self.voting_mint_config_idx,
+      VsrError::InvalidMint
+    );
+    require_gte!(
+      target.lockup.seconds_left(curr_ts),
+      self.lockup.seconds_left(curr_ts),
+      VsrError::InvalidLockupPeriod
+    );
+    require_gte!(
+      target.lockup.kind.strictness(),
+      self.lockup.kind.strictness(),
+      VsrError::InvalidLockupKind
+    );
+    require!(
+      curr_ts >= target.genesis_end
+        || curr_ts <= voting_mint_config.genesis_vote_power_multiplier_expiration_ts,
+      VsrError::NoDepositOnGenesisPositions
+    );
+
+    Ok(())
+  }
+
+  // Amount deposited into the position after `ts`
+  pub fn deposited_after(&self, ts: i64) -> u64 {
+    self
+      .recent_deposits
+      .iter()
+      .filter(|deposit| deposit.ts > ts)
+      .map(|deposit| deposit.amount)
+      .sum()
+  }
+
+  // Records tokens added to the position. Once full, the oldest deposit is folded into the
+  // next one, which only ever excludes it from more snapshots.
+  pub fn record_deposit(&mut self, ts: i64, amount: u64) {
+    match self.recent_deposits.last_mut() {
+      Some(last) if last.ts == ts => last.amount = last.amount.checked_add(amount).unwrap(),
+      _ => self.recent_deposits.push(RecentDepositV0 { ts, amount }),
+    }
+    while self.recent_deposits.len() > MAX_RECENT_DEPOSITS {
+      let oldest = self.recent_deposits.remove(0);
+      self.recent_deposits[0].amount = self.recent_deposits[0]
+        .amount
+        .checked_add(oldest.amount)
+        .unwrap();
+    }
+  }
+
+  // Treats every token as deposited at `ts`, for when the lockup itself changes
+  pub fn record_relock(&mut self, ts: i64) {
+    self.recent_deposits = vec![RecentDepositV0 {
+      ts,
+      amount: self.amount_deposited_native,
+    }];
+  }
+
+  // Splits the recent deposits proportionally for `amount` out of the deposited tokens
+  pub fn split_recent_deposits(&mut self, amount: u64) -> Vec<RecentDepositV0> {
+    let total = self.amount_deposited_native;
+    let mut piece_deposits = Vec::with_capacity(self.recent_deposits.len());
+    for deposit in self.recent_deposits.iter_mut() {
+      let piece_amount =
+        u64::try_from(u128::from(deposit.amount) * u128::from(amount) / u128::from(total)).unwrap();
+      deposit.amount -= piece_amount;
+      piece_deposits.push(RecentDepositV0 {
+        ts: deposit.ts,
+        amount: piece_amount,
+      });
+    }
+    piece_deposits
   }
 
   pub fn amount_locked(&self, curr_ts: i64) -> u64 {
@@ -166,8 +419,12 @@ impl PositionV0 {
       .unwrap()
   }
 
-  // Add a proposal to the recent proposals list
-  pub fn add_recent_proposal(&mut self, proposal: Pubkey, ts: i64) {
+  // Add a proposal to the recent proposals list. A direct vote replaces a proxy vote, but not
+  // the other way around.
+  pub fn add_recent_proposal(&mut self, proposal: Pubkey, ts: i64, proxied: bool) {
+    if !proxied {
+      self.proxied_proposals.retain(|p| *p != proposal);
+    }
     if self.recent_proposals.iter().any(|p| p.proposal == proposal) {
       return;
     }
@@ -181,14 +438,26 @@ impl PositionV0 {
       .unwrap_or(self.recent_proposals.len());
     // Insert the new proposal
     self.recent_proposals.insert(insert_index, new_proposal);
+    if proxied {
+      self.proxied_proposals.push(proposal);
+    }
   }
   pub fn remove_recent_proposal(&mut self, proposal: Pubkey) {
     self.recent_proposals.retain(|p| p.proposal != proposal);
+    self.proxied_proposals.retain(|p| *p != proposal);
   }
 
   // Remove proposals older than the given timestamp
   pub fn remove_proposals_older_than(&mut self, ts: i64) {
     self.recent_proposals.retain(|p| p.ts >= ts);
+    let recent_proposals = &self.recent_proposals;
+    self
+      .proxied_proposals
+      .retain(|proposal| recent_proposals.iter().any(|p| p.proposal == *proposal));
+  }
+
+  pub fn is_proxied(&self, proposal: Pubkey) -> bool {
+    self.proxied_proposals.contains(&proposal)
   }
 }
 
@@ -1,6 +1,7 @@
 use crate::error::*;
 use crate::state::voting_mint_config::VotingMintConfigV0;
 use anchor_lang::prelude::*;
+use modular_governance::proposal::{accounts::ProposalV0, types::ProposalState};
 
 // Instance of a voting rights distributor.
 #[account]
@@ -35,6 +36,15 @@ impl Registrar {
       .unwrap()
   }
 
+  // Timestamp that votes on the proposal are weighted at, the start of its
+  // voting period.
+  pub fn proposal_snapshot_timestamp(&self, proposal: &ProposalV0) -> Result<i64> {
+    match proposal.state {
+      ProposalState::Voting { start_ts } => Ok(start_ts),
+      _ => err!(VsrError::ProposalNotVoting),
+    }
+  }
+
   pub fn voting_mint_config_index(&self, mint: Pubkey) -> Result<usize> {
     self
       .voting_mints
@@ -21,8 +21,26 @@ import {
 
 const CIRCUIT_BREAKER_TRIGGERED_MESSAGE = "The circuit breaker was triggered";
 const CIRCUIT_BREAKER_TRIGGERED_CODE = 6000;
+const CIRCUIT_BREAKER_PAUSED_MESSAGE = "The circuit breaker is paused";
+const CIRCUIT_BREAKER_PAUSED_CODE = 6003;
 
 function expectCircuitBreakerTriggered(error: any) {
+  expectError(
+    error,
+    CIRCUIT_BREAKER_TRIGGERED_CODE,
+    CIRCUIT_BREAKER_TRIGGERED_MESSAGE
+  );
+}
+
+function expectCircuitBreakerPaused(error: any) {
+  expectError(
+    error,
+    CIRCUIT_BREAKER_PAUSED_CODE,
+    CIRCUIT_BREAKER_PAUSED_MESSAGE
+  );
+}
+
+function expectError(error: any, code: number, message: string) {
   const errorText = [
     error?.toString?.(),
     error?.message,
@@ -37,10 +55,10 @@ function expectCircuitBreakerTriggered(error: any) {
     .join("\n");
 
   const errorCode = Number(error?.code ?? error?.error?.errorCode?.number);
-  expect(
-    errorCode === CIRCUIT_BREAKER_TRIGGERED_CODE ||
-      errorText.includes(CIRCUIT_BREAKER_TRIGGERED_MESSAGE)
-  ).to.eq(true, errorText);
+  expect(errorCode === code || errorText.includes(message)).to.eq(
+    true,
+    errorText
+  );
 }
 
 describe("circuit-breaker", () => {
@@ -115,6 +133,169 @@ describe("circuit-breaker", () => {
     expect(acct.config.windowSizeSeconds.toNumber()).to.eq(10);
   });
 
+  it("initializes breakers with a guardian", async () => {
+    const guardian = Keypair.generate();
+    const mint = await createMint(provider, 8, me, me);
+    const mintBreaker = mintWindowedBreakerKey(mint)[0];
+    await program.methods
+      .initializeMintWindowedBreakerV1({
+        authority: me,
+        mintAuthority: me,
+        config: {
+          windows: [
+            {
+              windowSizeSeconds: new BN(10),
+              thresholdType: ThresholdType.Percent as never,
+              threshold: thresholdPercent(50),
+            },
+            {
+              windowSizeSeconds: new BN(100),
+              thresholdType: ThresholdType.Percent as never,
+              threshold: thresholdPercent(80),
+            },
+          ],
+        } as never,
+        guardian: guardian.publicKey,
+      })
+      .accountsPartial({
+        mint,
+      })
+      .rpc({ skipPreflight: true });
+    const mintAcct = await program.account.mintWindowedCircuitBreakerV0.fetch(
+      mintBreaker
+    );
+    expect(mintAcct.guardian.toBase58()).to.eq(guardian.publicKey.toBase58());
+    expect(mintAcct.additionalWindows.length).to.eq(1);
+
+    // The guardian can pause right away, without an update from the authority
+    await program.methods
+      .pauseMintWindowedBreakerV0({
+        expiryTs: null,
+      })
+      .accountsPartial({
+        circuitBreaker: mintBreaker,
+        guardian: guardian.publicKey,
+      })
+      .signers([guardian])
+      .rpc({ skipPreflight: true });
+    expect(
+      (await program.account.mintWindowedCircuitBreakerV0.fetch(mintBreaker))
+        .paused
+    ).to.be.true;
+
+    const tokenAccount = await createAtaAndMint(
+      provider,
+      await createMint(provider, 8, me, me),
+      200
+    );
+    const accountBreaker = accountWindowedBreakerKey(tokenAccount)[0];
+    await program.methods
+      .initializeAccountWindowedBreakerV1({
+        authority: me,
+        owner: me,
+        config: {
+          windows: [
+            {
+              windowSizeSeconds: new BN(10),
+              thresholdType: ThresholdType.Percent as never,
+              threshold: thresholdPercent(50),
+            },
+          ],
+        } as never,
+        guardian: guardian.publicKey,
+      })
+      .accountsPartial({
+        tokenAccount,
+      })
+      .rpc({ skipPreflight: true });
+    await program.methods
+      .pauseAccountWindowedBreakerV0({
+        expiryTs: null,
+      })
+      .accountsPartial({
+        circuitBreaker: accountBreaker,
+        guardian: guardian.publicKey,
+      })
+      .signers([guardian])
+      .rpc({ skipPreflight: true });
+    const accountAcct =
+      await program.account.accountWindowedCircuitBreakerV0.fetch(
+        accountBreaker
+      );
+    expect(accountAcct.guardian.toBase58()).to.eq(
+      guardian.publicKey.toBase58()
+    );
+    expect(accountAcct.paused).to.be.true;
+  });
+
+  it("rejects a guardian that is also the authority", async () => {
+    const mint = await createMint(provider, 8, me, me);
+    try {
+      await program.methods
+        .initializeMintWindowedBreakerV1({
+          authority: me,
+          mintAuthority: me,
+          config: {
+            windows: [
+              {
+                windowSizeSeconds: new BN(10),
+                thresholdType: ThresholdType.Percent as never,
+                threshold: thresholdPercent(50),
+              },
+            ],
+          } as never,
+          guardian: me,
+        })
+        .accountsPartial({
+          mint,
+        })
+        .rpc();
+      throw new Error("should not get here");
+    } catch (e: any) {
+      expect(e.toString()).to.include("InvalidConfig");
+    }
+
+    const guardian = Keypair.generate();
+    await program.methods
+      .initializeMintWindowedBreakerV1({
+        authority: me,
+        mintAuthority: me,
+        config: {
+          windows: [
+            {
+              windowSizeSeconds: new BN(10),
+              thresholdType: ThresholdType.Percent as never,
+              threshold: thresholdPercent(50),
+            },
+          ],
+        } as never,
+        guardian: guardian.publicKey,
+      })
+      .accountsPartial({
+        mint,
+      })
+      .rpc({ skipPreflight: true });
+    for (const args of [
+      { newAuthority: null, newGuardian: me },
+      { newAuthority: guardian.publicKey, newGuardian: null },
+    ]) {
+      try {
+        await program.methods
+          .updateMintWindowedBreakerV1({
+            ...args,
+            config: null,
+          })
+          .accountsPartial({
+            circuitBreaker: mintWindowedBreakerKey(mint)[0],
+          })
+          .rpc();
+        throw new Error("should not get here");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidConfig");
+      }
+    }
+  });
+
   describe("with mint windowed breaker", () => {
     let mint: PublicKey;
     const INITIAL_SUPPLY = 200;
@@ -162,6 +343,46 @@ describe("circuit-breaker", () => {
       );
     });
 
+    it("reports the remaining capacity", async () => {
+      const dest = await getAssociatedTokenAddress(mint, me);
+      await program.methods
+        .mintV0({
+          amount: new BN(50),
+        })
+        .accountsPartial({
+          mint,
+          to: dest,
+        })
+        .rpc({ skipPreflight: true });
+
+      // Curr supply: 250, agg value: 50... Break threshold at 125
+      const capacity = await program.methods
+        .getMintWindowedBreakerCapacityV0({
+          amount: new BN(100),
+        })
+        .accountsPartial({
+          mint,
+          circuitBreaker: mintWindowedBreakerKey(mint)[0],
+        })
+        .view();
+
+      expect(capacity.windows.length).to.eq(1);
+      expect(capacity.windows[0].threshold.toNumber()).to.be.within(124, 125);
+      expect(capacity.windows[0].aggregatedValue.toNumber()).to.be.within(
+        0,
+        50
+      );
+      expect(capacity.remaining.toNumber()).to.eq(
+        capacity.windows[0].threshold
+          .sub(capacity.windows[0].aggregatedValue)
+          .toNumber()
+      );
+      expect(capacity.amountFitsAt.toNumber()).to.be.within(
+        capacity.unixTimestamp.toNumber(),
+        capacity.unixTimestamp.toNumber() + 10
+      );
+    });
+
     it("allows removing the mint authority", async () => {
       const method = await program.methods
         .removeMintAuthorityV0()
@@ -249,6 +470,221 @@ describe("circuit-breaker", () => {
       assert.isTrue(PublicKey.default.equals(cbAcc.authority));
       assert.equal(cbAcc.config.windowSizeSeconds.toNumber(), 11);
     });
+
+    it("enforces every window of a multi-window config", async () => {
+      const cb = mintWindowedBreakerKey(mint)[0];
+      const dest = await getAssociatedTokenAddress(mint, me);
+
+      // 50% per 10 seconds, and at most 60 per 100 seconds
+      await program.methods
+        .updateMintWindowedBreakerV1({
+          newAuthority: null,
+          config: {
+            windows: [
+              {
+                windowSizeSeconds: new BN(10),
+                thresholdType: ThresholdType.Percent as never,
+                threshold: thresholdPercent(50),
+              },
+              {
+                windowSizeSeconds: new BN(100),
+                thresholdType: ThresholdType.Absolute as never,
+                threshold: new BN(60),
+              },
+            ],
+          },
+        })
+        .accountsPartial({
+          circuitBreaker: cb,
+        })
+        .rpc({ skipPreflight: true });
+
+      const cbAcc = await program.account.mintWindowedCircuitBreakerV0.fetch(
+        cb
+      );
+      expect(cbAcc.config.windowSizeSeconds.toNumber()).to.eq(10);
+      expect(cbAcc.additionalWindows.length).to.eq(1);
+      expect(
+        cbAcc.additionalWindows[0].config.windowSizeSeconds.toNumber()
+      ).to.eq(100);
+
+      await program.methods
+        .mintV0({
+          amount: new BN(50),
+        })
+        .accountsPartial({
+          mint,
+          to: dest,
+        })
+        .rpc({ skipPreflight: true });
+
+      // Wait til the short window passes, the long window still holds
+      await new Promise((resolve) => setTimeout(resolve, 10 * 1000));
+      try {
+        await program.methods
+          .mintV0({
+            amount: new BN(50),
+          })
+          .accountsPartial({
+            mint,
+            to: dest,
+          })
+          .rpc();
+        throw new Error("should not get here");
+      } catch (e: any) {
+        expectCircuitBreakerTriggered(e);
+      }
+    });
+
+    describe("with a guardian", () => {
+      const guardian = Keypair.generate();
+
+      beforeEach(async () => {
+        await program.methods
+          .updateMintWindowedBreakerV1({
+            newAuthority: null,
+            config: null,
+            newGuardian: guardian.publicKey,
+          })
+          .accountsPartial({
+            circuitBreaker: mintWindowedBreakerKey(mint)[0],
+          })
+          .rpc({ skipPreflight: true });
+        await program.methods
+          .pauseMintWindowedBreakerV0({
+            expiryTs: null,
+          })
+          .accountsPartial({
+            circuitBreaker: mintWindowedBreakerKey(mint)[0],
+            guardian: guardian.publicKey,
+          })
+          .signers([guardian])
+          .rpc({ skipPreflight: true });
+      });
+
+      it("rejects mints while paused", async () => {
+        const dest = await getAssociatedTokenAddress(mint, me);
+        try {
+          await program.methods
+            .mintV0({
+              amount: new BN(1),
+            })
+            .accountsPartial({
+              mint,
+              to: dest,
+            })
+            .rpc();
+          throw new Error("should not get here");
+        } catch (e: any) {
+          expectCircuitBreakerPaused(e);
+        }
+
+        const capacity = await program.methods
+          .getMintWindowedBreakerCapacityV0({
+            amount: new BN(1),
+          })
+          .accountsPartial({
+            mint,
+            circuitBreaker: mintWindowedBreakerKey(mint)[0],
+          })
+          .view();
+        expect(capacity.paused).to.be.true;
+        expect(capacity.remaining.toNumber()).to.eq(0);
+        expect(capacity.amountFitsAt).to.be.null;
+      });
+
+      it("allows only the authority to resume", async () => {
+        const dest = await getAssociatedTokenAddress(mint, me);
+        const cb = mintWindowedBreakerKey(mint)[0];
+        try {
+          await program.methods
+            .resumeMintWindowedBreakerV0()
+            .accountsPartial({
+              circuitBreaker: cb,
+              authority: guardian.publicKey,
+            })
+            .signers([guardian])
+            .rpc();
+          throw new Error("should not get here");
+        } catch (e: any) {
+          expect(e.toString()).to.not.include("should not get here");
+        }
+
+        await program.methods
+          .resumeMintWindowedBreakerV0()
+          .accountsPartial({
+            circuitBreaker: cb,
+          })
+          .rpc({ skipPreflight: true });
+
+        await program.methods
+          .mintV0({
+            amount: new BN(1),
+          })
+          .accountsPartial({
+            mint,
+            to: dest,
+          })
+          .rpc({ skipPreflight: true });
+      });
+
+      it("does not let the guardian shorten an existing pause", async () => {
+        const cb = mintWindowedBreakerKey(mint)[0];
+        try {
+          await program.methods
+            .pauseMintWindowedBreakerV0({
+              expiryTs: new BN(Math.floor(Date.now() / 1000) + 5),
+            })
+            .accountsPartial({
+              circuitBreaker: cb,
+              guardian: guardian.publicKey,
+            })
+            .signers([guardian])
+            .rpc();
+          throw new Error("should not get here");
+        } catch (e: any) {
+          expect(e.toString()).to.include("PauseCannotBeShortened");
+        }
+        const acct = await program.account.mintWindowedCircuitBreakerV0.fetch(
+          cb
+        );
+        expect(acct.paused).to.be.true;
+        expect(acct.pauseExpiryTs).to.be.null;
+      });
+
+      it("lifts the pause at the expiry", async () => {
+        const dest = await getAssociatedTokenAddress(mint, me);
+        const expiryTs = Math.floor(Date.now() / 1000) + 5;
+        // Only the authority can end the indefinite pause before pausing with an expiry
+        await program.methods
+          .resumeMintWindowedBreakerV0()
+          .accountsPartial({
+            circuitBreaker: mintWindowedBreakerKey(mint)[0],
+          })
+          .rpc({ skipPreflight: true });
+        await program.methods
+          .pauseMintWindowedBreakerV0({
+            expiryTs: new BN(expiryTs),
+          })
+          .accountsPartial({
+            circuitBreaker: mintWindowedBreakerKey(mint)[0],
+            guardian: guardian.publicKey,
+          })
+          .signers([guardian])
+          .rpc({ skipPreflight: true });
+
+        await new Promise((resolve) => setTimeout(resolve, 7 * 1000));
+        await program.methods
+          .mintV0({
+            amount: new BN(1),
+          })
+          .accountsPartial({
+            mint,
+            to: dest,
+          })
+          .rpc({ skipPreflight: true });
+      });
+    });
   });
 
   describe("with account windowed breaker", () => {
@@ -385,5 +821,100 @@ describe("circuit-breaker", () => {
       assert.isTrue(PublicKey.default.equals(cbAcc.authority));
       assert.equal(cbAcc.config.windowSizeSeconds.toNumber(), 11);
     });
+
+    describe("with a guardian", () => {
+      const guardian = Keypair.generate();
+
+      beforeEach(async () => {
+        const cb = accountWindowedBreakerKey(tokenAccount)[0];
+        await program.methods
+          .updateAccountWindowedBreakerV1({
+            newAuthority: null,
+            config: null,
+            newGuardian: guardian.publicKey,
+          })
+          .accountsPartial({
+            circuitBreaker: cb,
+          })
+          .rpc({ skipPreflight: true });
+        await program.methods
+          .pauseAccountWindowedBreakerV0({
+            expiryTs: null,
+          })
+          .accountsPartial({
+            circuitBreaker: cb,
+            guardian: guardian.publicKey,
+          })
+          .signers([guardian])
+          .rpc({ skipPreflight: true });
+      });
+
+      it("rejects transfers while paused", async () => {
+        const dest = await getAssociatedTokenAddress(mint, me);
+        try {
+          await program.methods
+            .transferV0({
+              amount: new BN(1),
+            })
+            .preInstructions([
+              createAssociatedTokenAccountInstruction(me, dest, me, mint),
+            ])
+            .signers([accountHolder])
+            .accountsPartial({
+              from: tokenAccount,
+              to: dest,
+              owner: accountHolder.publicKey,
+            })
+            .rpc();
+          throw new Error("should not get here");
+        } catch (e: any) {
+          expectCircuitBreakerPaused(e);
+        }
+      });
+
+      it("rejects burns while paused", async () => {
+        try {
+          await program.methods
+            .burnV0({
+              amount: new BN(1),
+            })
+            .signers([accountHolder])
+            .accountsPartial({
+              from: tokenAccount,
+              mint,
+              owner: accountHolder.publicKey,
+            })
+            .rpc();
+          throw new Error("should not get here");
+        } catch (e: any) {
+          expectCircuitBreakerPaused(e);
+        }
+      });
+
+      it("allows transfers after the authority resumes", async () => {
+        const dest = await getAssociatedTokenAddress(mint, me);
+        await program.methods
+          .resumeAccountWindowedBreakerV0()
+          .accountsPartial({
+            circuitBreaker: accountWindowedBreakerKey(tokenAccount)[0],
+          })
+          .rpc({ skipPreflight: true });
+
+        await program.methods
+          .transferV0({
+            amount: new BN(1),
+          })
+          .preInstructions([
+            createAssociatedTokenAccountInstruction(me, dest, me, mint),
+          ])
+          .signers([accountHolder])
+          .accountsPartial({
+            from: tokenAccount,
+            to: dest,
+            owner: accountHolder.publicKey,
+          })
+          .rpc();
+      });
+    });
   });
 });
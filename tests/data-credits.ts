@@ -16,6 +16,7 @@ import { ThresholdType } from "../packages/circuit-breaker-sdk/src";
 import {
   accountPayerKey,
   dataCreditsKey,
+  delegatedDataCreditsDepositKey,
   delegatedDataCreditsKey,
   escrowAccountKey,
   init,
@@ -286,6 +287,67 @@ describe("data-credits", () => {
     expect(error.error?.errorCode?.code).to.eq("InvalidPriceOracleOwner");
   });
 
+  it("mints some data credits at a fixed rate", async () => {
+    await program.methods
+      .updateDataCreditsV0({
+        newAuthority: null,
+        hntPriceOracle: null,
+        // $2.50 per HNT
+        priceSource: { fixed: { price: new BN(250), exponent: -2 } },
+      })
+      .accountsPartial({
+        dcMint,
+        authority: me,
+      })
+      .rpc({ skipPreflight: true });
+
+    await program.methods
+      .mintDataCreditsV0({
+        hntAmount: new BN(1 * 10 ** 8),
+        dcAmount: null,
+      })
+      .accountsPartial({
+        dcMint,
+        hntPriceOracle: PRO_HNT_PRICE_FEED,
+      })
+      .rpc({ skipPreflight: true });
+
+    const dcBal = await provider.connection.getTokenAccountBalance(
+      await getAssociatedTokenAddress(dcMint, me),
+    );
+    expect(dcBal.value.uiAmount).to.eq(startDcBal + 250000);
+  });
+
+  it("quotes the data credits minted for hnt and the hnt burned for dc", async () => {
+    await program.methods
+      .updateDataCreditsV0({
+        newAuthority: null,
+        hntPriceOracle: null,
+        priceSource: { fixed: { price: new BN(250), exponent: -2 } },
+      })
+      .accountsPartial({
+        dcMint,
+        authority: me,
+      })
+      .rpc({ skipPreflight: true });
+
+    const quote = await program.methods
+      .quoteMintDataCreditsV0({
+        hntAmount: new BN(1 * 10 ** 8),
+        dcAmount: new BN(1),
+      })
+      .accountsPartial({
+        dcMint,
+        hntPriceOracle: PRO_HNT_PRICE_FEED,
+      })
+      .view();
+
+    expect(quote.hntPrice.price.toNumber()).to.eq(250);
+    expect(quote.hntPrice.exponent).to.eq(-2);
+    expect(quote.dcForHntAmount.toNumber()).to.eq(250000);
+    expect(quote.hntForDcAmount.toNumber()).to.eq(400);
+  });
+
   describe("with data credits", async () => {
     let dao: PublicKey;
     let subDao: PublicKey;
@@ -452,6 +514,288 @@ describe("data-credits", () => {
       expect(numBurned).to.eq(1);
     });
 
+    it("enforces the delegated allowance on burns", async () => {
+      await provider.connection.requestAirdrop(
+        accountPayerKey()[0],
+        web3.LAMPORTS_PER_SOL,
+      );
+
+      const routerKey = (await HeliumKeypair.makeRandom()).address.b58;
+      const delegatedDataCredits = delegatedDataCreditsKey(
+        subDao,
+        routerKey,
+      )[0];
+      const deposit = delegatedDataCreditsDepositKey(delegatedDataCredits, me)[0];
+      await program.methods
+        .delegateDataCreditsV1({
+          amount: toBN(2, 0),
+          routerKey,
+        })
+        .accountsPartial({
+          common: { subDao },
+        })
+        .rpc({ skipPreflight: true });
+
+      const setAllowance = (deposit: PublicKey | null) =>
+        program.methods
+          .updateDelegatedAllowanceV0({
+            newOwner: me,
+            paused: null,
+            maxBurnPerEpoch: new BN(1),
+            maxBurnLifetime: null,
+          })
+          .accountsPartial({
+            dataCredits: dcKey,
+            delegatedDataCredits,
+            deposit,
+          });
+      // The data credits authority does not own the escrow
+      let error: any = null;
+      try {
+        await setAllowance(null).rpc();
+      } catch (e: any) {
+        error = e;
+      }
+      expect(error.error?.errorCode?.code).to.eq("InvalidAllowanceAuthority");
+      await setAllowance(deposit).rpc({ skipPreflight: true });
+
+      const burn = () =>
+        program.methods
+          .burnDelegatedDataCreditsV0({
+            amount: new BN(1),
+          })
+          .accountsPartial({
+            delegatedDataCredits,
+          })
+          .rpc();
+
+      await burn();
+      let allowance = (
+        await program.account.delegatedDataCreditsV0.fetch(
+          delegatedDataCredits,
+        )
+      ).allowance!;
+      expect(allowance.totalBurned.toNumber()).to.eq(1);
+      expect(allowance.remainingAllowance.toNumber()).to.eq(0);
+
+      error = null;
+      try {
+        await burn();
+      } catch (e: any) {
+        error = e;
+      }
+      expect(error.error?.errorCode?.code).to.eq("AllowanceExceeded");
+
+      await program.methods
+        .updateDelegatedAllowanceV0({
+          newOwner: null,
+          paused: true,
+          maxBurnPerEpoch: new BN(0),
+          maxBurnLifetime: null,
+        })
+        .accountsPartial({
+          dataCredits: dcKey,
+          delegatedDataCredits,
+        })
+        .rpc({ skipPreflight: true });
+
+      error = null;
+      try {
+        await burn();
+      } catch (e: any) {
+        error = e;
+      }
+      // Paused allowances have nothing left to burn
+      expect(error.error?.errorCode?.code).to.eq("AllowanceExceeded");
+    });
+
+    it("withdraws unburned delegated data credits", async () => {
+      const routerKey = (await HeliumKeypair.makeRandom()).address.b58;
+      const delegatedDataCredits = delegatedDataCreditsKey(
+        subDao,
+        routerKey,
+      )[0];
+      const deposit = delegatedDataCreditsDepositKey(delegatedDataCredits, me)[0];
+      await program.methods
+        .delegateDataCreditsV1({
+          amount: toBN(2, 0),
+          routerKey,
+        })
+        .accountsPartial({
+          common: { subDao },
+        })
+        .rpc({ skipPreflight: true });
+
+      const delegated =
+        await program.account.delegatedDataCreditsV0.fetch(
+          delegatedDataCredits,
+        );
+      const depositAcc =
+        await program.account.delegatedDataCreditsDepositV0.fetch(deposit);
+      expect(depositAcc.refundAuthority.toBase58()).to.eq(me.toBase58());
+      expect(depositAcc.shares.toString()).to.eq(
+        delegated.totalShares.toString(),
+      );
+
+      const dcAta = await getAssociatedTokenAddress(dcMint, me);
+      await program.methods
+        .requestDelegatedWithdrawalV0({
+          amount: toBN(2, 0),
+        })
+        .accountsPartial({
+          dcMint,
+          delegatedDataCredits,
+          deposit,
+          destination: dcAta,
+        })
+        .rpc({ skipPreflight: true });
+
+      await program.methods
+        .withdrawDelegatedDataCreditsV0()
+        .accountsPartial({
+          dcMint,
+          subDao,
+          delegatedDataCredits,
+          deposit,
+          destination: dcAta,
+        })
+        .rpc({ skipPreflight: true });
+
+      const dcAtaAcc = await getAccount(provider.connection, dcAta);
+      assert(dcAtaAcc.isFrozen);
+      const dcBal = await provider.connection.getTokenAccountBalance(dcAta);
+      expect(dcBal.value.uiAmount).to.eq(startDcBal);
+      const escrowBal = await provider.connection.getTokenAccountBalance(
+        delegated.escrowAccount,
+      );
+      expect(escrowBal.value.uiAmount).to.eq(0);
+    });
+
+    it("caps withdrawals at the delegator's share of the escrow", async () => {
+      const routerKey = (await HeliumKeypair.makeRandom()).address.b58;
+      const delegatedDataCredits = delegatedDataCreditsKey(
+        subDao,
+        routerKey,
+      )[0];
+      const deposit = delegatedDataCreditsDepositKey(delegatedDataCredits, me)[0];
+      // DC delegated without a deposit belongs to nobody and stays in the escrow
+      await program.methods
+        .delegateDataCreditsV0({
+          amount: toBN(1, 0),
+          routerKey,
+        })
+        .accountsPartial({
+          subDao,
+        })
+        .rpc({ skipPreflight: true });
+      await program.methods
+        .delegateDataCreditsV1({
+          amount: toBN(1, 0),
+          routerKey,
+        })
+        .accountsPartial({
+          common: { subDao },
+        })
+        .rpc({ skipPreflight: true });
+
+      const dcAta = await getAssociatedTokenAddress(dcMint, me);
+      await program.methods
+        .requestDelegatedWithdrawalV0({
+          amount: toBN(2, 0),
+        })
+        .accountsPartial({
+          dcMint,
+          delegatedDataCredits,
+          deposit,
+          destination: dcAta,
+        })
+        .rpc({ skipPreflight: true });
+      await program.methods
+        .withdrawDelegatedDataCreditsV0()
+        .accountsPartial({
+          dcMint,
+          subDao,
+          delegatedDataCredits,
+          deposit,
+          destination: dcAta,
+        })
+        .rpc({ skipPreflight: true });
+
+      const dcBal = await provider.connection.getTokenAccountBalance(dcAta);
+      expect(dcBal.value.uiAmount).to.eq(startDcBal - 1);
+      const delegated =
+        await program.account.delegatedDataCreditsV0.fetch(
+          delegatedDataCredits,
+        );
+      const escrowBal = await provider.connection.getTokenAccountBalance(
+        delegated.escrowAccount,
+      );
+      expect(escrowBal.value.uiAmount).to.eq(1);
+      const depositAcc =
+        await program.account.delegatedDataCreditsDepositV0.fetch(deposit);
+      expect(depositAcc.shares.toNumber()).to.eq(0);
+    });
+
+    it("holds withdrawals until the timelock passes", async () => {
+      await program.methods
+        .updateDataCreditsV0({
+          newAuthority: null,
+          hntPriceOracle: null,
+          priceSource: null,
+          withdrawalTimelockSeconds: 60 * 60,
+        })
+        .accountsPartial({
+          dcMint,
+        })
+        .rpc({ skipPreflight: true });
+
+      const routerKey = (await HeliumKeypair.makeRandom()).address.b58;
+      const delegatedDataCredits = delegatedDataCreditsKey(
+        subDao,
+        routerKey,
+      )[0];
+      const deposit = delegatedDataCreditsDepositKey(delegatedDataCredits, me)[0];
+      await program.methods
+        .delegateDataCreditsV1({
+          amount: toBN(2, 0),
+          routerKey,
+        })
+        .accountsPartial({
+          common: { subDao },
+        })
+        .rpc({ skipPreflight: true });
+
+      const dcAta = await getAssociatedTokenAddress(dcMint, me);
+      await program.methods
+        .requestDelegatedWithdrawalV0({
+          amount: toBN(2, 0),
+        })
+        .accountsPartial({
+          dcMint,
+          delegatedDataCredits,
+          deposit,
+          destination: dcAta,
+        })
+        .rpc({ skipPreflight: true });
+
+      let error: any = null;
+      try {
+        await program.methods
+          .withdrawDelegatedDataCreditsV0()
+          .accountsPartial({
+            dcMint,
+            subDao,
+            delegatedDataCredits,
+            deposit,
+            destination: dcAta,
+          })
+          .rpc();
+      } catch (e: any) {
+        error = e;
+      }
+      expect(error.error?.errorCode?.code).to.eq("WithdrawalLocked");
+    });
+
     it("updates data credits", async () => {
       await program.methods
         .updateDataCreditsV0({
@@ -523,5 +867,52 @@ describe("data-credits", () => {
       expect(sourceBal.value.uiAmount).to.eq(0);
       expect(destinationBal.value.uiAmount).to.eq(amount);
     });
+
+    it("does not move DC owned by deposits to another sub dao", async () => {
+      const { subDao: destinationSubDao } = await initTestSubdao({
+        hsdProgram,
+        provider,
+        authority: me,
+        dao,
+      });
+
+      const routerKey = (await HeliumKeypair.makeRandom()).address.b58;
+      const delegatedDataCredits = delegatedDataCreditsKey(
+        subDao,
+        routerKey,
+      )[0];
+      await program.methods
+        .delegateDataCreditsV1({
+          amount: toBN(2, 0),
+          routerKey,
+        })
+        .accountsPartial({
+          common: { subDao },
+        })
+        .rpc({ skipPreflight: true });
+
+      let error: any = null;
+      try {
+        await program.methods
+          .changeDelegatedSubDaoV0({
+            amount: toBN(2, 0),
+            routerKey,
+          })
+          .accountsPartial({
+            delegatedDataCredits,
+            destinationDelegatedDataCredits: delegatedDataCreditsKey(
+              destinationSubDao,
+              routerKey,
+            )[0],
+            subDao,
+            destinationSubDao,
+            authority: me,
+          })
+          .rpc();
+      } catch (e: any) {
+        error = e;
+      }
+      expect(error.error?.errorCode?.code).to.eq("EscrowHasDeposits");
+    });
   });
 });
@@ -25,7 +25,11 @@ import {
   toBN,
   toNumber,
 } from "@helium/spl-utils";
-import { AccountLayout, getMint } from "@solana/spl-token";
+import {
+  AccountLayout,
+  getAssociatedTokenAddressSync,
+  getMint,
+} from "@solana/spl-token";
 import {
   ComputeBudgetProgram,
   Keypair,
@@ -46,10 +50,15 @@ import {
 } from "../packages/helium-entity-manager-sdk/src";
 import {
   currentEpoch,
+  daoEpochInfoKey,
+  delegatedPositionKey,
   subDaoEpochInfoKey,
   init as initHSD,
 } from "../packages/helium-sub-daos-sdk/src";
-import { init as vsrInit } from "../packages/voter-stake-registry-sdk/src";
+import {
+  init as vsrInit,
+  positionKey,
+} from "../packages/voter-stake-registry-sdk/src";
 import { DataCredits } from "../target/types/data_credits";
 import { HeliumEntityManager } from "../target/types/helium_entity_manager";
 import { burnDataCredits } from "./data-credits";
@@ -312,6 +321,58 @@ describe("helium-sub-daos", () => {
       expect(daoAcc.authority.toString()).to.eq(newAuth.toString());
     });
 
+    it("updates the participation policy", async () => {
+      const participationPolicy = {
+        window: 3,
+        requiredVotes: 2,
+        countProxyVotes: false,
+        partialRewardPercents: [0, 50, 0, 0],
+      };
+      await program.methods
+        .updateDaoV0({
+          authority: null,
+          emissionSchedule: null,
+          hstEmissionSchedule: null,
+          hstPool: null,
+          netEmissionsCap: null,
+          proposalNamespace: null,
+          delegatorRewardsPercent: null,
+          rewardsEscrow: null,
+          participationPolicy,
+        })
+        .accountsPartial({
+          dao,
+        })
+        .rpc({ skipPreflight: true });
+
+      const daoAcc = await program.account.daoV0.fetch(dao);
+      expect(daoAcc.participationPolicy).to.deep.eq(participationPolicy);
+
+      try {
+        await program.methods
+          .updateDaoV0({
+            authority: null,
+            emissionSchedule: null,
+            hstEmissionSchedule: null,
+            hstPool: null,
+            netEmissionsCap: null,
+            proposalNamespace: null,
+            delegatorRewardsPercent: null,
+            rewardsEscrow: null,
+            participationPolicy: { ...participationPolicy, window: 5 },
+          })
+          .accountsPartial({
+            dao,
+          })
+          .rpc();
+        expect.fail("Should have failed");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.eq(
+          "InvalidParticipationPolicy"
+        );
+      }
+    });
+
     it("updates the subdao", async () => {
       const newAuth = Keypair.generate().publicKey;
       await program.methods
@@ -801,6 +862,43 @@ describe("helium-sub-daos", () => {
             .rpc();
         });
 
+        it("allows merging positions", async () => {
+          const { position: newPos } = await createPosition(
+            vsrProgram,
+            provider,
+            registrar,
+            hntMint,
+            options,
+            positionAuthorityKp
+          );
+          const positionAcc = await vsrProgram.account.positionV0.fetch(
+            position
+          );
+          await program.methods
+            .mergePositionsV0()
+            .accountsPartial({
+              common: {
+                sourcePosition: newPos,
+                targetPosition: position,
+                depositMint: hntMint,
+                positionAuthority: positionAuthorityKp.publicKey,
+              },
+              targetPositionTokenAccount: getAssociatedTokenAddressSync(
+                positionAcc.mint,
+                positionAuthorityKp.publicKey
+              ),
+            })
+            .signers([positionAuthorityKp])
+            .rpc();
+
+          const mergedAcc = await vsrProgram.account.positionV0.fetch(position);
+          expect(mergedAcc.amountDepositedNative.toString()).to.eq(
+            positionAcc.amountDepositedNative
+              .add(toBN(options.lockupAmount, 8))
+              .toString()
+          );
+        });
+
         it("allows lockup resets", async () => {
           await program.methods
             .resetLockupV0({
@@ -926,6 +1024,40 @@ describe("helium-sub-daos", () => {
             );
           });
 
+          it("does not allow merging positions", async () => {
+            const { position: newPos } = await createPosition(
+              vsrProgram,
+              provider,
+              registrar,
+              hntMint,
+              options,
+              positionAuthorityKp
+            );
+            const positionAcc = await vsrProgram.account.positionV0.fetch(
+              position
+            );
+            await expect(
+              program.methods
+                .mergePositionsV0()
+                .accountsPartial({
+                  common: {
+                    sourcePosition: newPos,
+                    targetPosition: position,
+                    depositMint: hntMint,
+                    positionAuthority: positionAuthorityKp.publicKey,
+                  },
+                  targetPositionTokenAccount: getAssociatedTokenAddressSync(
+                    positionAcc.mint,
+                    positionAuthorityKp.publicKey
+                  ),
+                })
+                .signers([positionAuthorityKp])
+                .rpc()
+            ).to.eventually.be.rejectedWith(
+              "AnchorError caused by account: target_delegated_position. Error Code: PositionChangeWhileDelegated. Error Number: 6014. Error Message: Cannot change a position while it is delegated."
+            );
+          });
+
           it("does not allow lockup resets", async () => {
             await expect(
               program.methods
@@ -945,6 +1077,88 @@ describe("helium-sub-daos", () => {
             );
           });
 
+          it("cannot be turned into a vesting position", async () => {
+            const vestingOptions = {
+              lockupPeriods: 365,
+              lockupAmount: 10,
+              kind: { daily: {} },
+            };
+            const { position: vestingPos } = await createPosition(
+              vsrProgram,
+              provider,
+              registrar,
+              hntMint,
+              vestingOptions,
+              positionAuthorityKp
+            );
+            const positionAcc = await vsrProgram.account.positionV0.fetch(
+              position
+            );
+
+            await expect(
+              program.methods
+                .resetLockupV0({
+                  kind: { daily: {} },
+                  periods: 365 * 4,
+                })
+                .accountsPartial({
+                  dao,
+                  position,
+                  positionAuthority: positionAuthorityKp.publicKey,
+                })
+                .signers([positionAuthorityKp])
+                .rpc()
+            ).to.eventually.be.rejectedWith("PositionChangeWhileDelegated");
+            await expect(
+              program.methods
+                .transferV0({ amount: toBN(10, 8) })
+                .accountsPartial({
+                  sourcePosition: vestingPos,
+                  targetPosition: position,
+                  depositMint: hntMint,
+                  positionAuthority: positionAuthorityKp.publicKey,
+                })
+                .signers([positionAuthorityKp])
+                .rpc()
+            ).to.eventually.be.rejectedWith("PositionChangeWhileDelegated");
+            await expect(
+              program.methods
+                .mergePositionsV0()
+                .accountsPartial({
+                  common: {
+                    sourcePosition: vestingPos,
+                    targetPosition: position,
+                    depositMint: hntMint,
+                    positionAuthority: positionAuthorityKp.publicKey,
+                  },
+                  targetPositionTokenAccount: getAssociatedTokenAddressSync(
+                    positionAcc.mint,
+                    positionAuthorityKp.publicKey
+                  ),
+                })
+                .signers([positionAuthorityKp])
+                .rpc()
+            ).to.eventually.be.rejectedWith("PositionChangeWhileDelegated");
+            // Nor can a vesting position be delegated to merge it as a delegation
+            await expect(
+              program.methods
+                .delegateV0()
+                .accountsPartial({
+                  position: vestingPos,
+                  subDao,
+                  positionAuthority: positionAuthorityKp.publicKey,
+                })
+                .signers([positionAuthorityKp])
+                .rpc()
+            ).to.eventually.be.rejectedWith("VestingPositionDelegation");
+
+            const after = await vsrProgram.account.positionV0.fetch(position);
+            expect(after.lockup.kind).to.deep.eq(positionAcc.lockup.kind);
+            expect(after.lockup.endTs.toString()).to.eq(
+              positionAcc.lockup.endTs.toString()
+            );
+          });
+
           it("allows closing delegate", async () => {
             await sleep(options.delay);
             const method = program.methods
@@ -971,6 +1185,120 @@ describe("helium-sub-daos", () => {
             );
           });
 
+          it("allows splitting and merging delegated positions", async () => {
+            const newMintKp = Keypair.generate();
+            const newPosition = positionKey(newMintKp.publicKey)[0];
+            const { collection } = await vsrProgram.account.registrar.fetch(
+              registrar
+            );
+            const preSdAcc = await program.account.subDaoV0.fetch(subDao);
+            const prePositionAcc = await vsrProgram.account.positionV0.fetch(
+              position
+            );
+            const amount = prePositionAcc.amountDepositedNative.divn(4);
+
+            await program.methods
+              .splitDelegatedPositionV0({ amount })
+              .preInstructions([
+                ComputeBudgetProgram.setComputeUnitLimit({ units: 500000 }),
+                ...(await createMintInstructions(
+                  provider,
+                  0,
+                  newPosition,
+                  newPosition,
+                  newMintKp
+                )),
+              ])
+              .accountsPartial({
+                position,
+                subDao,
+                newMint: newMintKp.publicKey,
+                collection,
+                depositMint: hntMint,
+                positionAuthority: positionAuthorityKp.publicKey,
+              })
+              .signers([positionAuthorityKp, newMintKp])
+              .rpc({ skipPreflight: true });
+
+            const positionAcc = await vsrProgram.account.positionV0.fetch(
+              position
+            );
+            const newPositionAcc = await vsrProgram.account.positionV0.fetch(
+              newPosition
+            );
+            expect(positionAcc.amountDepositedNative.toString()).to.eq(
+              prePositionAcc.amountDepositedNative.sub(amount).toString()
+            );
+            expect(newPositionAcc.amountDepositedNative.toString()).to.eq(
+              amount.toString()
+            );
+            expect(newPositionAcc.lockup.endTs.toString()).to.eq(
+              positionAcc.lockup.endTs.toString()
+            );
+            const delegatedPositionAcc =
+              await program.account.delegatedPositionV0.fetch(
+                delegatedPositionKey(position)[0]
+              );
+            const newDelegatedPositionAcc =
+              await program.account.delegatedPositionV0.fetch(
+                delegatedPositionKey(newPosition)[0]
+              );
+            expect(newDelegatedPositionAcc.subDao.toBase58()).to.eq(
+              subDao.toBase58()
+            );
+            expect(newDelegatedPositionAcc.lastClaimedEpoch.toNumber()).to.eq(
+              delegatedPositionAcc.lastClaimedEpoch.toNumber()
+            );
+            expect(newDelegatedPositionAcc.expirationTs.toNumber()).to.eq(
+              delegatedPositionAcc.expirationTs.toNumber()
+            );
+            let sdAcc = await program.account.subDaoV0.fetch(subDao);
+            expectBnAccuracy(
+              preSdAcc.vehntDelegated,
+              sdAcc.vehntDelegated,
+              0.0000001
+            );
+            expect(sdAcc.vehntFallRate.toNumber()).to.be.closeTo(
+              preSdAcc.vehntFallRate.toNumber(),
+              2
+            );
+
+            await program.methods
+              .mergeDelegatedPositionsV0()
+              .accountsPartial({
+                sourcePosition: newPosition,
+                mint: newMintKp.publicKey,
+                targetPosition: position,
+                subDao,
+                depositMint: hntMint,
+                positionAuthority: positionAuthorityKp.publicKey,
+              })
+              .signers([positionAuthorityKp])
+              .rpc({ skipPreflight: true });
+
+            expect(
+              (
+                await vsrProgram.account.positionV0.fetch(position)
+              ).amountDepositedNative.toString()
+            ).to.eq(prePositionAcc.amountDepositedNative.toString());
+            expect(
+              (
+                await vsrProgram.account.positionV0.fetch(newPosition)
+              ).amountDepositedNative.toNumber()
+            ).to.eq(0);
+            assert.isFalse(
+              !!(await provider.connection.getAccountInfo(
+                delegatedPositionKey(newPosition)[0]
+              ))
+            );
+            sdAcc = await program.account.subDaoV0.fetch(subDao);
+            expectBnAccuracy(
+              preSdAcc.vehntDelegated,
+              sdAcc.vehntDelegated,
+              0.0000001
+            );
+          });
+
           describe("HIP 149 cap redirect", () => {
             it("redirects data-bucket overflow above the cap to the delegator pool", async () => {
               await vsrProgram.methods
@@ -1326,11 +1654,17 @@ describe("helium-sub-daos", () => {
                   })
                   .accountsPartial({ proposalConfig })
                   .rpcAndKeys({ skipPreflight: true });
+                // Votes are weighted at the start of voting, on the registrar's clock
+                const { timeOffset } = await vsrProgram.account.registrar.fetch(
+                  registrar
+                );
                 await proposalProgram.methods
                   .updateStateV0({
                     newState: {
                       voting: {
-                        startTs: new anchor.BN(new Date().valueOf() / 1000),
+                        startTs: new anchor.BN(
+                          new Date().valueOf() / 1000
+                        ).add(timeOffset),
                       } as any,
                     },
                   })
@@ -1413,6 +1747,181 @@ describe("helium-sub-daos", () => {
                   5
               );
             });
+
+            it("batch claims rewards for a range of epochs", async () => {
+              await sendInstructions(provider, [
+                await program.methods
+                  .issueRewardsV0({
+                    epoch,
+                  })
+                  .accountsPartial({
+                    subDao,
+                    supplementVault: null,
+                    councilVault: null,
+                  })
+                  .instruction(),
+              ]);
+
+              const method = program.methods
+                .batchClaimRewardsV0()
+                .accountsPartial({
+                  position,
+                  subDao,
+                  payer: positionAuthorityKp.publicKey,
+                  positionAuthority: positionAuthorityKp.publicKey,
+                })
+                .remainingAccounts([
+                  {
+                    pubkey: daoEpochInfoKey(
+                      dao,
+                      epoch.toNumber() * EPOCH_LENGTH
+                    )[0],
+                    isWritable: false,
+                    isSigner: false,
+                  },
+                ])
+                .signers([positionAuthorityKp]);
+              const { delegatorAta } = await method.pubkeys();
+              const preAtaBalance = AccountLayout.decode(
+                (await provider.connection.getAccountInfo(delegatorAta!))?.data!
+              ).amount;
+              await method.rpc({ skipPreflight: true });
+
+              const postAtaBalance = AccountLayout.decode(
+                (await provider.connection.getAccountInfo(delegatorAta!))?.data!
+              ).amount;
+              expect(
+                Number(postAtaBalance) - Number(preAtaBalance)
+              ).to.be.within(
+                EPOCH_REWARDS_PLUS_NET_EMISSIONS *
+                  (delegatorRewardsPercent(6).toNumber() / 10_000000000) -
+                  5,
+                EPOCH_REWARDS_PLUS_NET_EMISSIONS *
+                  (delegatorRewardsPercent(6).toNumber() / 10_000000000) +
+                  5
+              );
+            });
+
+            it("views pending rewards for a range of epochs", async () => {
+              await sendInstructions(provider, [
+                await program.methods
+                  .issueRewardsV0({
+                    epoch,
+                  })
+                  .accountsPartial({
+                    subDao,
+                    supplementVault: null,
+                    councilVault: null,
+                  })
+                  .instruction(),
+              ]);
+
+              const pending = await program.methods
+                .getPendingRewardsV0()
+                .accountsPartial({
+                  position,
+                  subDao,
+                })
+                .remainingAccounts([
+                  {
+                    pubkey: daoEpochInfoKey(
+                      dao,
+                      epoch.toNumber() * EPOCH_LENGTH
+                    )[0],
+                    isWritable: false,
+                    isSigner: false,
+                  },
+                ])
+                .view();
+
+              const expected =
+                EPOCH_REWARDS_PLUS_NET_EMISSIONS *
+                (delegatorRewardsPercent(6).toNumber() / 10_000000000);
+              expect(pending.epochs.length).to.eq(1);
+              expect(pending.epochs[0].epoch.toString()).to.eq(
+                epoch.toString()
+              );
+              expect(pending.epochs[0].rewardPercent).to.eq(100);
+              expect(pending.claimableRewards.toNumber()).to.be.within(
+                expected - 5,
+                expected + 5
+              );
+              expect(pending.ineligibleRewards.toNumber()).to.eq(0);
+            });
+
+            it("compounds rewards into the position", async () => {
+              await program.methods
+                .updateDelegatedPositionV0({
+                  compoundRewards: true,
+                })
+                .accountsPartial({
+                  position,
+                  positionAuthority: positionAuthorityKp.publicKey,
+                })
+                .signers([positionAuthorityKp])
+                .rpc({ skipPreflight: true });
+              await sendInstructions(provider, [
+                await program.methods
+                  .issueRewardsV0({
+                    epoch,
+                  })
+                  .accountsPartial({
+                    subDao,
+                    supplementVault: null,
+                    councilVault: null,
+                  })
+                  .instruction(),
+              ]);
+
+              const preAmount = (
+                await vsrProgram.account.positionV0.fetch(position)
+              ).amountDepositedNative;
+              const preVehnt = (await program.account.subDaoV0.fetch(subDao))
+                .vehntDelegated;
+              const method = program.methods
+                .claimRewardsV1({
+                  epoch,
+                })
+                .accountsPartial({
+                  position,
+                  subDao,
+                  payer: positionAuthorityKp.publicKey,
+                  positionAuthority: positionAuthorityKp.publicKey,
+                })
+                .signers([positionAuthorityKp]);
+              const { delegatorAta } = await method.pubkeys();
+              const preAtaBalance = AccountLayout.decode(
+                (await provider.connection.getAccountInfo(delegatorAta!))?.data!
+              ).amount;
+              await method.rpc({ skipPreflight: true });
+
+              const postAtaBalance = AccountLayout.decode(
+                (await provider.connection.getAccountInfo(delegatorAta!))?.data!
+              ).amount;
+              const postAmount = (
+                await vsrProgram.account.positionV0.fetch(position)
+              ).amountDepositedNative;
+              const postVehnt = (await program.account.subDaoV0.fetch(subDao))
+                .vehntDelegated;
+              const expected =
+                EPOCH_REWARDS_PLUS_NET_EMISSIONS *
+                (delegatorRewardsPercent(6).toNumber() / 10_000000000);
+              expect(Number(postAtaBalance)).to.eq(Number(preAtaBalance));
+              expect(postAmount.sub(preAmount).toNumber()).to.be.within(
+                expected - 5,
+                expected + 5
+              );
+              expect(postVehnt.gt(preVehnt)).to.be.true;
+
+              const delegatedPositionAcc =
+                await program.account.delegatedPositionV0.fetch(
+                  delegatedPositionKey(position)[0]
+                );
+              expect(delegatedPositionAcc.compoundRewards).to.be.true;
+              expect(delegatedPositionAcc.hntAmount.toString()).to.eq(
+                postAmount.toString()
+              );
+            });
           });
         });
       });
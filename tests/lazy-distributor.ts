@@ -10,13 +10,16 @@ import {
 } from "@helium/spl-utils";
 import { getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { Ed25519Program, Keypair, PublicKey } from "@solana/web3.js";
-import { assert, expect } from "chai";
+import chai, { assert, expect } from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { keccak_256 } from "js-sha3";
 import { sign } from "tweetnacl";
 import {
   distributeCompressionRewards,
   init,
   initializeCompressionRecipient,
   updateCompressionDestination,
+  vestingEscrowKey,
 } from "../packages/lazy-distributor-sdk/src";
 import { PROGRAM_ID } from "../packages/lazy-distributor-sdk/src/constants";
 import { LazyDistributor } from "../target/types/lazy_distributor";
@@ -25,6 +28,16 @@ import { ensureLDIdl } from "./utils/fixtures";
 import { MerkleTree, MerkleTreeProof } from "@solana/spl-account-compression";
 import { loadKeypair } from "./utils/solana";
 
+chai.use(chaiAsPromised);
+
+function rewardsLeaf(asset: PublicKey, currentRewards: anchor.BN): Buffer {
+  return Buffer.from(
+    keccak_256.digest(
+      Buffer.concat([asset.toBuffer(), currentRewards.toArrayLike(Buffer, "le", 8)])
+    )
+  );
+}
+
 describe("lazy-distributor", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.local("http://127.0.0.1:8899"));
@@ -538,6 +551,306 @@ describe("lazy-distributor", () => {
         expect(balance2.value.uiAmount).to.eq(5);
       });
 
+      it("allows distributing current rewards from a rewards root", async () => {
+        const currentRewards = new anchor.BN("5000000");
+        const tree = new MerkleTree([
+          rewardsLeaf(mint, currentRewards),
+          rewardsLeaf(Keypair.generate().publicKey, new anchor.BN("1000000")),
+        ]);
+        const { root, proof } = tree.getProof(0);
+        await program.methods
+          .setRewardsRootV0({ period: new anchor.BN(1), root: [...root] })
+          .accountsPartial({ lazyDistributor })
+          .remainingAccounts([
+            { pubkey: me, isWritable: false, isSigner: true },
+          ])
+          .rpc({ skipPreflight: true });
+
+        await expect(
+          program.methods
+            .proveCurrentRewardsV0({
+              currentRewards: new anchor.BN("6000000"),
+              index: 0,
+            })
+            .accountsPartial({ lazyDistributor, recipient })
+            .remainingAccounts(
+              proof.map((p) => ({
+                pubkey: new PublicKey(p),
+                isWritable: false,
+                isSigner: false,
+              }))
+            )
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: InvalidRewardsProof");
+
+        await program.methods
+          .proveCurrentRewardsV0({ currentRewards, index: 0 })
+          .accountsPartial({ lazyDistributor, recipient })
+          .remainingAccounts(
+            proof.map((p) => ({
+              pubkey: new PublicKey(p),
+              isWritable: false,
+              isSigner: false,
+            }))
+          )
+          .rpc({ skipPreflight: true });
+        const method = program.methods.distributeRewardsV0().accountsPartial({
+          common: { recipient, lazyDistributor, rewardsMint },
+        });
+        await method.rpc({ skipPreflight: true });
+        // @ts-ignore
+        const destination = (await method.pubkeys()).common.destinationAccount!;
+        const balance = await provider.connection.getTokenAccountBalance(
+          destination
+        );
+        expect(balance.value.uiAmount).to.eq(5);
+
+        // Roots can only move forward
+        await expect(
+          program.methods
+            .setRewardsRootV0({ period: new anchor.BN(1), root: [...root] })
+            .accountsPartial({ lazyDistributor })
+            .remainingAccounts([
+              { pubkey: me, isWritable: false, isSigner: true },
+            ])
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: InvalidRewardsPeriod");
+      });
+
+      it("forgets the rewards root when the oracles rotate", async () => {
+        const currentRewards = new anchor.BN("5000000");
+        const tree = new MerkleTree([
+          rewardsLeaf(mint, currentRewards),
+          rewardsLeaf(Keypair.generate().publicKey, new anchor.BN("1000000")),
+        ]);
+        const { root, proof } = tree.getProof(0);
+        await program.methods
+          .setRewardsRootV0({ period: new anchor.BN(1), root: [...root] })
+          .accountsPartial({ lazyDistributor })
+          .remainingAccounts([
+            { pubkey: me, isWritable: false, isSigner: true },
+          ])
+          .rpc({ skipPreflight: true });
+
+        await program.methods
+          .updateLazyDistributorV0({
+            authority: null,
+            oracles: [
+              {
+                oracle: me,
+                url: "https://some-other-url/",
+              },
+            ],
+            approver: null,
+          })
+          .accountsPartial({
+            rewardsMint,
+          })
+          .rpc({ skipPreflight: true });
+        const ld = await program.account.lazyDistributorV0.fetch(
+          lazyDistributor
+        );
+        expect(ld.rewardsRoot).to.be.null;
+        expect(ld.lastRewardsPeriod.toNumber()).to.eq(1);
+
+        await expect(
+          program.methods
+            .proveCurrentRewardsV0({ currentRewards, index: 0 })
+            .accountsPartial({ lazyDistributor, recipient })
+            .remainingAccounts(
+              proof.map((p) => ({
+                pubkey: new PublicKey(p),
+                isWritable: false,
+                isSigner: false,
+              }))
+            )
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: MissingRewardsRoot");
+        // Nor can the old root be posted again
+        await expect(
+          program.methods
+            .setRewardsRootV0({ period: new anchor.BN(1), root: [...root] })
+            .accountsPartial({ lazyDistributor })
+            .remainingAccounts([
+              { pubkey: me, isWritable: false, isSigner: true },
+            ])
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: InvalidRewardsPeriod");
+      });
+
+      describe("with vesting destination", () => {
+        let vestingEscrow: PublicKey;
+        beforeEach(async () => {
+          vestingEscrow = vestingEscrowKey(recipient)[0];
+          await program.methods
+            .initializeVestingEscrowV0({
+              vestingPeriodSecs: new anchor.BN(5),
+            })
+            .accountsPartial({
+              lazyDistributor,
+              rewardsMint,
+              recipient,
+              recipientMintAccount: getAssociatedTokenAddressSync(mint, me),
+            })
+            .rpc({ skipPreflight: true });
+          await program.methods
+            .setCurrentRewardsV0({
+              currentRewards: new anchor.BN("5000000"),
+              oracleIndex: 0,
+            })
+            .accountsPartial({
+              lazyDistributor,
+              recipient,
+            })
+            .rpc({ skipPreflight: true });
+        });
+
+        it("vests distributed rewards linearly", async () => {
+          await program.methods
+            .distributeVestingDestinationV0()
+            .accountsPartial({
+              common: {
+                recipient,
+                lazyDistributor,
+                rewardsMint,
+                owner: vestingEscrow,
+              },
+            })
+            .rpc({ skipPreflight: true });
+          const escrowAccount = getAssociatedTokenAddressSync(
+            rewardsMint,
+            vestingEscrow,
+            true
+          );
+          const escrowBalance =
+            await provider.connection.getTokenAccountBalance(escrowAccount);
+          expect(escrowBalance.value.uiAmount).to.eq(5);
+
+          const claim = program.methods.claimVestingV0().accountsPartial({
+            common: { owner: me, recipient, rewardsMint },
+            recipientMintAccount: getAssociatedTokenAddressSync(mint, me),
+          });
+          const destination = (await claim.pubkeys()).common!
+            .destinationAccount!;
+          await claim.rpc({ skipPreflight: true });
+          const claimed = await provider.connection.getTokenAccountBalance(
+            destination
+          );
+          expect(claimed.value.uiAmount).to.be.lessThan(5);
+
+          await new Promise((resolve) => setTimeout(resolve, 6 * 1000));
+          await claim.rpc({ skipPreflight: true });
+          const balance = await provider.connection.getTokenAccountBalance(
+            destination
+          );
+          expect(balance.value.uiAmount).to.eq(5);
+        });
+
+        it("lets the owner close the escrow once everything vested", async () => {
+          await program.methods
+            .distributeVestingDestinationV0()
+            .accountsPartial({
+              common: {
+                recipient,
+                lazyDistributor,
+                rewardsMint,
+                owner: vestingEscrow,
+              },
+            })
+            .rpc({ skipPreflight: true });
+
+          const close = () =>
+            program.methods
+              .closeVestingEscrowV0()
+              .accountsPartial({
+                common: { owner: me, recipient, rewardsMint },
+                recipientMintAccount: getAssociatedTokenAddressSync(mint, me),
+              })
+              .rpc({ skipPreflight: true });
+          await expect(close()).to.eventually.be.rejectedWith(
+            "Error Code: VestingNotComplete"
+          );
+
+          await new Promise((resolve) => setTimeout(resolve, 6 * 1000));
+          await close();
+          expect(await provider.connection.getAccountInfo(vestingEscrow)).to.be
+            .null;
+          const recipientAcc = await program.account.recipientV0.fetch(
+            recipient
+          );
+          expect(recipientAcc.destination.toBase58()).to.eq(
+            PublicKey.default.toBase58()
+          );
+          const balance = await provider.connection.getTokenAccountBalance(
+            getAssociatedTokenAddressSync(rewardsMint, me)
+          );
+          expect(balance.value.uiAmount).to.eq(5);
+        });
+
+        it("sweeps dust sent to the escrow when closing it", async () => {
+          await program.methods
+            .distributeVestingDestinationV0()
+            .accountsPartial({
+              common: {
+                recipient,
+                lazyDistributor,
+                rewardsMint,
+                owner: vestingEscrow,
+              },
+            })
+            .rpc({ skipPreflight: true });
+          // Anyone can send tokens to the escrow's token account
+          const escrowAccount = await createAtaAndMint(
+            provider,
+            rewardsMint,
+            1,
+            vestingEscrow
+          );
+
+          await new Promise((resolve) => setTimeout(resolve, 6 * 1000));
+          await program.methods
+            .closeVestingEscrowV0()
+            .accountsPartial({
+              common: { owner: me, recipient, rewardsMint },
+              recipientMintAccount: getAssociatedTokenAddressSync(mint, me),
+            })
+            .rpc({ skipPreflight: true });
+          expect(await provider.connection.getAccountInfo(escrowAccount)).to.be
+            .null;
+          const balance = await provider.connection.getTokenAccountBalance(
+            getAssociatedTokenAddressSync(rewardsMint, me)
+          );
+          expect(balance.value.amount).to.eq("5000001");
+        });
+
+        it("does not allow bypassing the vesting escrow", async () => {
+          await expect(
+            program.methods
+              .distributeCustomDestinationV0()
+              .accountsPartial({
+                common: {
+                  recipient,
+                  lazyDistributor,
+                  rewardsMint,
+                  owner: vestingEscrow,
+                },
+              })
+              .rpc()
+          ).to.eventually.be.rejectedWith("Error Code: VestingDestination");
+          await expect(
+            program.methods
+              .updateDestinationV0()
+              .accountsPartial({
+                recipient,
+                owner: me,
+                destination: me,
+                recipientMintAccount: getAssociatedTokenAddressSync(mint, me),
+              })
+              .rpc()
+          ).to.eventually.be.rejectedWith("Error Code: VestingDestination");
+        });
+      });
+
       describe("with custom destination", () => {
         const destinationWallet = Keypair.generate();
         beforeEach(async () => {
@@ -743,5 +1056,179 @@ describe("lazy-distributor", () => {
       );
       expect(balance.value.uiAmount).to.eq(1);
     });
+
+    describe("with quorum", () => {
+      async function setRewards(rewards: number[]) {
+        return (
+          await Promise.all(
+            [oracle1, oracle2, oracle3].map(async (oracle, index) => {
+              return await program.methods
+                .setCurrentRewardsV0({
+                  currentRewards: new anchor.BN(rewards[index]),
+                  oracleIndex: index,
+                })
+                .accountsPartial({
+                  lazyDistributor,
+                  recipient,
+                  oracle: oracle.publicKey,
+                })
+                .instruction();
+            })
+          )
+        ).flat();
+      }
+
+      beforeEach(async () => {
+        await program.methods
+          .updateLazyDistributorV1({
+            oracles: null,
+            authority: null,
+            approver: null,
+            quorum: {
+              minSigners: 3,
+              oracleWeights: [1, 1, 3],
+              maxSpread: new anchor.BN(2000000),
+            },
+          })
+          .accountsPartial({ rewardsMint })
+          .rpc({ skipPreflight: true });
+      });
+
+      it("distributes the weighted median amount", async () => {
+        const {
+          instruction: distributeInstruction,
+          pubkeys: { common },
+        } = await program.methods
+          .distributeRewardsV0()
+          .accountsPartial({
+            common: { recipient, lazyDistributor, rewardsMint },
+          })
+          .prepare();
+
+        await sendInstructions(
+          provider,
+          [...(await setRewards([0, 1000000, 2000000])), distributeInstruction],
+          [oracle1, oracle2, oracle3]
+        );
+
+        // oracle3 holds the majority of the weight
+        const balance = await provider.connection.getTokenAccountBalance(
+          common!.destinationAccount!
+        );
+        expect(balance.value.uiAmount).to.eq(2);
+      });
+
+      it("refuses to distribute when oracles disagree", async () => {
+        await sendInstructions(
+          provider,
+          await setRewards([0, 1000000, 3000000]),
+          [oracle1, oracle2, oracle3]
+        );
+
+        await expect(
+          program.methods
+            .distributeRewardsV0()
+            .accountsPartial({
+              common: { recipient, lazyDistributor, rewardsMint },
+            })
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: OracleDisagreement");
+      });
+
+      it("does not allow rewards roots with a max spread", async () => {
+        await expect(
+          program.methods
+            .setRewardsRootV0({
+              period: new anchor.BN(1),
+              root: [...Buffer.alloc(32)],
+            })
+            .accountsPartial({ lazyDistributor })
+            .remainingAccounts(
+              [oracle1, oracle2, oracle3].map((oracle) => ({
+                pubkey: oracle.publicKey,
+                isWritable: false,
+                isSigner: true,
+              }))
+            )
+            .signers([oracle1, oracle2, oracle3])
+            .rpc()
+        ).to.eventually.be.rejectedWith(
+          "Error Code: RewardsRootWithMaxSpread"
+        );
+      });
+
+      it("requires a majority of the oracle weight for rewards roots", async () => {
+        await program.methods
+          .updateLazyDistributorV1({
+            oracles: null,
+            authority: null,
+            approver: null,
+            quorum: {
+              minSigners: 2,
+              oracleWeights: [1, 1, 3],
+              maxSpread: null,
+            },
+          })
+          .accountsPartial({ rewardsMint })
+          .rpc({ skipPreflight: true });
+
+        const setRoot = (signers: Keypair[]) =>
+          program.methods
+            .setRewardsRootV0({
+              period: new anchor.BN(1),
+              root: [...Buffer.alloc(32)],
+            })
+            .accountsPartial({ lazyDistributor })
+            .remainingAccounts(
+              signers.map((oracle) => ({
+                pubkey: oracle.publicKey,
+                isWritable: false,
+                isSigner: true,
+              }))
+            )
+            .signers(signers)
+            .rpc({ skipPreflight: true });
+
+        // Enough signers, but only 2 of the 5 weight
+        await expect(
+          setRoot([oracle1, oracle2])
+        ).to.eventually.be.rejectedWith(
+          "Error Code: InsufficientOracleSignatures"
+        );
+        await setRoot([oracle1, oracle3]);
+      });
+
+      it("refuses to distribute without enough oracles", async () => {
+        const [setRewardsInstruction] = await setRewards([1000000, 0, 0]);
+        await sendInstructions(provider, [setRewardsInstruction], [oracle1]);
+
+        await expect(
+          program.methods
+            .distributeRewardsV0()
+            .accountsPartial({
+              common: { recipient, lazyDistributor, rewardsMint },
+            })
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: ConstraintRaw");
+      });
+
+      it("rejects weights that do not match the oracles", async () => {
+        await expect(
+          program.methods
+            .updateLazyDistributorV1({
+              oracles: null,
+              authority: null,
+              approver: null,
+              quorum: {
+                minSigners: 2,
+                oracleWeights: [1, 1],
+                maxSpread: null,
+              },
+            })
+            .accountsPartial({ rewardsMint })
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: InvalidQuorumConfig");
+      });
+    });
   });
 });
@@ -23,6 +23,7 @@ import {
   fillCanopy,
   getCanopySize,
   getBitmapLen,
+  MerkleTree,
 } from "../packages/lazy-transactions-sdk/src";
 import { LazyTransactions } from "../target/types/lazy_transactions";
 import { random } from "./utils/string";
@@ -232,8 +233,246 @@ describe("lazy-transactions", () => {
       .accountsPartial({ lazyTransactions, refund: provider.wallet.publicKey })
       .rpc({ skipPreflight: true });
   });
+
+  it("refuses revoked transactions and transactions outside the validity window", async () => {
+    const name = random();
+    const lazyTransactions = lazyTransactionsKey(name)[0];
+    const lazySigner = lazySignerKey(name)[0];
+    await sendInstructions(provider, [
+      SystemProgram.transfer({
+        fromPubkey: me,
+        toPubkey: lazySigner,
+        lamports: 1000000000,
+      }),
+    ]);
+    const instructions = [
+      SystemProgram.transfer({
+        fromPubkey: lazySigner,
+        toPubkey: me,
+        lamports: 1000,
+      }),
+    ];
+    const { merkleTree, compiledTransactions } = compile(lazySigner, [
+      { instructions, signerSeeds: [] },
+      { instructions, signerSeeds: [] },
+      { instructions, signerSeeds: [] },
+    ]);
+    await initializeLazyTransactions(program, name, merkleTree);
+    await sleep(2000);
+
+    const execute = async (index: number) =>
+      program.methods
+        .executeTransactionV1({
+          instructions: compiledTransactions[index].instructions,
+          index: compiledTransactions[index].index,
+          signerSeeds: compiledTransactions[index].signerSeeds,
+        })
+        .accountsPartial({
+          common: { lazyTransactions },
+          revokedTransactions: (
+            await program.account.lazyTransactionsV0.fetch(lazyTransactions)
+          ).revokedTransactions,
+        })
+        .remainingAccounts(compiledTransactions[index].accounts)
+        .rpc();
+    const createBitmap = async (bitmap: Keypair) => {
+      const size = 1 + getBitmapLen(merkleTree.depth - 1);
+      return SystemProgram.createAccount({
+        fromPubkey: me,
+        newAccountPubkey: bitmap.publicKey,
+        space: size,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(
+          size
+        ),
+        programId: program.programId,
+      });
+    };
+
+    const revokedTransactions = Keypair.generate();
+    await program.methods
+      .revokeTransactionsV0({ indices: [1] })
+      .accountsPartial({
+        lazyTransactions,
+        revokedTransactions: revokedTransactions.publicKey,
+      })
+      .preInstructions([await createBitmap(revokedTransactions)])
+      .signers([revokedTransactions])
+      .rpc({ skipPreflight: true });
+    expect(
+      (
+        await program.account.lazyTransactionsV0.fetch(lazyTransactions)
+      ).revokedTransactions.toBase58()
+    ).to.eq(revokedTransactions.publicKey.toBase58());
+
+    try {
+      await execute(1);
+      throw new Error("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("Transaction has been revoked");
+    }
+
+    // A fresh bitmap cannot replace the one holding the revocations
+    const freshBitmap = Keypair.generate();
+    try {
+      await program.methods
+        .revokeTransactionsV0({ indices: [] })
+        .accountsPartial({
+          lazyTransactions,
+          revokedTransactions: freshBitmap.publicKey,
+        })
+        .preInstructions([await createBitmap(freshBitmap)])
+        .signers([freshBitmap])
+        .rpc();
+      throw new Error("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("ConstraintRaw");
+    }
+
+    // v0 cannot check revocations, so it refuses once there are any
+    try {
+      await program.methods
+        .executeTransactionV0({
+          instructions: compiledTransactions[0].instructions,
+          index: compiledTransactions[0].index,
+          signerSeeds: compiledTransactions[0].signerSeeds,
+        })
+        .accountsPartial({ lazyTransactions })
+        .remainingAccounts(compiledTransactions[0].accounts)
+        .rpc();
+      throw new Error("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include(
+        "Transactions have been revoked, use execute_transaction_v1"
+      );
+    }
+    await execute(0);
+
+    const now = Math.floor(Date.now() / 1000);
+    try {
+      await program.methods
+        .setValidityWindowV0({
+          validFrom: new anchor.BN(now),
+          validUntil: new anchor.BN(now),
+        })
+        .accountsPartial({ lazyTransactions })
+        .rpc();
+      throw new Error("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include(
+        "Validity window must end after it starts"
+      );
+    }
+
+    await program.methods
+      .setValidityWindowV0({
+        validFrom: null,
+        validUntil: new anchor.BN(now - 60 * 60),
+      })
+      .accountsPartial({ lazyTransactions })
+      .rpc({ skipPreflight: true });
+    try {
+      await execute(2);
+      throw new Error("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("Transaction has expired");
+    }
+
+    await program.methods
+      .setValidityWindowV0({
+        validFrom: new anchor.BN(now + 60 * 60),
+        validUntil: null,
+      })
+      .accountsPartial({ lazyTransactions })
+      .rpc({ skipPreflight: true });
+    try {
+      await execute(2);
+      throw new Error("Should have failed");
+    } catch (e: any) {
+      expect(e.toString()).to.include("Transaction is not valid yet");
+    }
+
+    await program.methods
+      .setValidityWindowV0({
+        validFrom: new anchor.BN(now - 60 * 60),
+        validUntil: new anchor.BN(now + 60 * 60),
+      })
+      .accountsPartial({ lazyTransactions })
+      .rpc({ skipPreflight: true });
+    await execute(2);
+
+    // Revocations refer to leaves of the old root
+    const lt = await program.account.lazyTransactionsV0.fetch(lazyTransactions);
+    await program.methods
+      .updateLazyTransactionsV0({
+        root: merkleTree.getRoot().toJSON().data,
+        authority: null,
+      })
+      .accountsPartial({
+        lazyTransactions,
+        canopy: lt.canopy,
+        executedTransactions: lt.executedTransactions,
+      })
+      .rpc({ skipPreflight: true });
+    expect(
+      (
+        await program.account.lazyTransactionsV0.fetch(lazyTransactions)
+      ).revokedTransactions.toBase58()
+    ).to.eq(PublicKey.default.toBase58());
+  });
 });
 
+async function initializeLazyTransactions(
+  program: Program<LazyTransactions>,
+  name: string,
+  merkleTree: MerkleTree
+): Promise<void> {
+  const provider = program.provider as anchor.AnchorProvider;
+  const canopy = Keypair.generate();
+  const executedTransactions = Keypair.generate();
+  const canopySize = getCanopySize(merkleTree.depth - 1);
+  const executedTransactionsSize = 1 + getBitmapLen(merkleTree.depth - 1);
+  await program.methods
+    .initializeLazyTransactionsV0({
+      root: merkleTree.getRoot().toJSON().data,
+      name,
+      authority: provider.wallet.publicKey,
+      maxDepth: merkleTree.depth - 1,
+    })
+    .accountsPartial({
+      canopy: canopy.publicKey,
+      executedTransactions: executedTransactions.publicKey,
+    })
+    .preInstructions([
+      SystemProgram.createAccount({
+        fromPubkey: provider.wallet.publicKey,
+        newAccountPubkey: canopy.publicKey,
+        space: canopySize,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(
+          canopySize
+        ),
+        programId: program.programId,
+      }),
+      SystemProgram.createAccount({
+        fromPubkey: provider.wallet.publicKey,
+        newAccountPubkey: executedTransactions.publicKey,
+        space: executedTransactionsSize,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(
+          executedTransactionsSize
+        ),
+        programId: program.programId,
+      }),
+    ])
+    .signers([canopy, executedTransactions])
+    .rpc({ skipPreflight: true });
+
+  await fillCanopy({
+    program,
+    lazyTransactions: lazyTransactionsKey(name)[0],
+    merkleTree,
+    cacheDepth: merkleTree.depth - 1,
+  });
+}
+
 async function sleep(ms: number): Promise<void> {
   return new Promise((resolve) => setTimeout(resolve, ms));
 }
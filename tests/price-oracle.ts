@@ -5,7 +5,11 @@ import { Program } from "@coral-xyz/anchor";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import { BN } from "bn.js";
 import { expect } from "chai";
-import { init, PROGRAM_ID } from "../packages/price-oracle-sdk";
+import {
+  init,
+  priceHistoryKey,
+  PROGRAM_ID,
+} from "../packages/price-oracle-sdk";
 
 describe("price-oracle", () => {
   anchor.setProvider(anchor.AnchorProvider.local("http://127.0.0.1:8899"));
@@ -192,5 +196,211 @@ describe("price-oracle", () => {
       expect(priceOracleAcc.currentPrice?.eq(new BN(1000)));
       expect(priceOracleAcc.lastCalculatedTimestamp).to.not.be.null;
     });
+
+    it("leaves outliers out of the current price", async () => {
+      await program.methods
+        .updatePriceOracleV0({
+          oracles: null,
+          authority: null,
+          config: {
+            maxAgeSeconds: 60 * 60,
+            maxDeviationBps: 500,
+          },
+          oracleWeights: [1, 1, 1],
+        })
+        .accountsPartial({
+          priceOracle,
+        })
+        .rpc({ skipPreflight: true });
+
+      const prices = [1000, 1010, 5000];
+      const ixs = await Promise.all(
+        oracles.map((x, i) =>
+          program.methods
+            .submitPriceV0({
+              oracleIndex: i,
+              price: new BN(prices[i]),
+            })
+            .accountsPartial({
+              priceOracle,
+              oracle: x.publicKey,
+            })
+            .instruction()
+        )
+      );
+      await sendInstructions(provider, ixs, oracles);
+
+      const priceOracleAcc = await program.account.priceOracleV0.fetch(
+        priceOracle
+      );
+      expect(priceOracleAcc.currentPrice?.toNumber()).to.eq(1005);
+      expect(priceOracleAcc.oracleInfos.map((info) => info.flagged)).to.deep.eq(
+        [false, false, true]
+      );
+    });
+
+    it("records medians to the price history", async () => {
+      const priceHistory = priceHistoryKey(priceOracle)[0];
+      await program.methods
+        .initializePriceHistoryV0()
+        .accountsPartial({
+          priceOracle,
+          priceHistory,
+        })
+        .rpc({ skipPreflight: true });
+
+      let history = await program.account.priceHistoryV0.fetch(priceHistory);
+      expect(history.priceOracle.toBase58()).to.eq(priceOracle.toBase58());
+      expect(history.numPoints).to.eq(1);
+
+      const prices = [2000, 2000, 2000];
+      const ixs = await Promise.all(
+        oracles.map((x, i) =>
+          program.methods
+            .submitPriceV0({
+              oracleIndex: i,
+              price: new BN(prices[i]),
+            })
+            .accountsPartial({
+              priceOracle,
+              oracle: x.publicKey,
+              priceHistory,
+            })
+            .instruction()
+        )
+      );
+      await sendInstructions(provider, ixs, oracles);
+
+      history = await program.account.priceHistoryV0.fetch(priceHistory);
+      const latest = history.points[history.nextIndex - 1];
+      expect(latest.price.toNumber()).to.eq(2000);
+      expect(history.numPoints).to.be.gte(2);
+      expect(
+        (
+          await program.account.priceOracleV0.fetch(priceOracle)
+        ).priceHistory?.toBase58()
+      ).to.eq(priceHistory.toBase58());
+
+      // Once the history exists, updates that skip it are rejected
+      try {
+        await program.methods
+          .submitPriceV0({
+            oracleIndex: 0,
+            price: new BN(2000),
+          })
+          .accountsPartial({
+            priceOracle,
+            oracle: oracles[0].publicKey,
+          })
+          .signers([oracles[0]])
+          .rpc();
+        throw new Error("Should have failed");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidPriceHistory");
+      }
+      try {
+        await program.methods
+          .updatePriceV0()
+          .accountsPartial({
+            priceOracle,
+          })
+          .rpc();
+        throw new Error("Should have failed");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidPriceHistory");
+      }
+    });
+
+    it("rejects an out of range oracle index", async () => {
+      try {
+        await program.methods
+          .submitPriceV0({
+            oracleIndex: 5,
+            price: new BN(1000),
+          })
+          .accountsPartial({
+            priceOracle,
+            oracle: oracles[0].publicKey,
+          })
+          .signers([oracles[0]])
+          .rpc();
+        throw new Error("Should have failed");
+      } catch (e: any) {
+        expect(e.toString()).to.include("InvalidOracleIndex");
+      }
+    });
+
+    it("looks up the oracle by signer", async () => {
+      await program.methods
+        .submitPriceV1({
+          oracleIndex: null,
+          price: new BN(1000),
+        })
+        .accountsPartial({
+          priceOracle,
+          oracle: oracles[2].publicKey,
+        })
+        .signers([oracles[2]])
+        .rpc({ skipPreflight: true });
+
+      const priceOracleAcc = await program.account.priceOracleV0.fetch(
+        priceOracle
+      );
+      expect(priceOracleAcc.oracles[2].lastSubmittedPrice?.toNumber()).to.eq(
+        1000
+      );
+      expect(priceOracleAcc.oracles[0].lastSubmittedPrice).to.be.null;
+    });
+
+    it("submits prices to several price oracles in one transaction", async () => {
+      const otherKp = Keypair.generate();
+      await program.methods
+        .initializePriceOracleV0({
+          oracles: [...oracles].reverse().map((o) => ({
+            authority: o.publicKey,
+            lastSubmittedPrice: null,
+            lastSubmittedTimestamp: null,
+          })),
+          decimals: 8,
+          authority: me,
+        })
+        .accountsPartial({
+          priceOracle: otherKp.publicKey,
+          payer: me,
+        })
+        .signers([otherKp])
+        .rpc({ skipPreflight: true });
+
+      await program.methods
+        .submitPricesV0({
+          prices: [
+            { oracleIndex: null, price: new BN(1000), hasPriceHistory: false },
+            { oracleIndex: null, price: new BN(2000), hasPriceHistory: false },
+          ],
+        })
+        .accountsPartial({
+          oracle: oracles[0].publicKey,
+        })
+        .remainingAccounts(
+          [priceOracle, otherKp.publicKey].map((pubkey) => ({
+            pubkey,
+            isWritable: true,
+            isSigner: false,
+          }))
+        )
+        .signers([oracles[0]])
+        .rpc({ skipPreflight: true });
+
+      const priceOracleAcc = await program.account.priceOracleV0.fetch(
+        priceOracle
+      );
+      const otherAcc = await program.account.priceOracleV0.fetch(
+        otherKp.publicKey
+      );
+      expect(priceOracleAcc.oracles[0].lastSubmittedPrice?.toNumber()).to.eq(
+        1000
+      );
+      expect(otherAcc.oracles[2].lastSubmittedPrice?.toNumber()).to.eq(2000);
+    });
   });
 });
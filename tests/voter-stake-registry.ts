@@ -307,18 +307,26 @@ describe("voter-stake-registry", () => {
         })
         .rpcAndKeys();
       proposal = proposalK as PublicKey;
+    });
 
+    // Votes are weighted at the start of voting, so positions must be created before it.
+    // Pass the registrar time offset to start voting at the registrar's time.
+    async function startVoting(timeOffset: number = 0) {
+      const startTs =
+        (await provider.connection.getBlockTime(
+          await provider.connection.getSlot()
+        ))! + timeOffset;
       await proposalProgram.methods
         .updateStateV0({
           newState: {
             voting: {
-              startTs: new anchor.BN(new Date().valueOf() / 1000),
+              startTs: new anchor.BN(startTs),
             } as any,
           },
         })
         .accountsPartial({ proposal, proposalConfig })
         .rpc({ skipPreflight: true });
-    });
+    }
 
     let voteTestCases = [
       {
@@ -385,6 +393,24 @@ describe("voter-stake-registry", () => {
           (BASELINE +
             Math.min((SECS_PER_DAY * (200 - 60)) / MAX_LOCKUP, 1) * SCALE),
       },
+      {
+        name: "daily vesting 1 position (outside genesis)",
+        delay: 7, // days
+        fastForward: 60, // days
+        positions: [
+          {
+            lockupAmount: 10000,
+            periods: 200,
+            kind: { daily: {} },
+          },
+        ],
+        // Each of the 140 unvested days counts as a cliff ending on its vesting day
+        expectedVeHnt:
+          10000 *
+          (BASELINE +
+            ((SECS_PER_DAY * (140 + (140 * 139) / 2)) / (200 * MAX_LOCKUP)) *
+              SCALE),
+      },
     ];
     voteTestCases.forEach((testCase) => {
       const depositor = Keypair.generate();
@@ -415,6 +441,9 @@ describe("voter-stake-registry", () => {
           )
           .accountsPartial({ registrar })
           .rpc();
+        await startVoting(
+          (testCase.delay + testCase.fastForward) * SECS_PER_DAY
+        );
 
         const voteIxs = await Promise.all(
           positions.map(
@@ -447,6 +476,138 @@ describe("voter-stake-registry", () => {
       });
     });
 
+    it("weights votes at the proposal snapshot", async () => {
+      const { position } = await createAndDeposit(10000, 200);
+      await startVoting();
+
+      await program.methods
+        .setTimeOffsetV0(new anchor.BN(60 * SECS_PER_DAY))
+        .accountsPartial({ registrar })
+        .rpc();
+      await program.methods
+        .voteV0({
+          choice: 0,
+        })
+        .accountsPartial({
+          registrar,
+          proposal,
+          proposalConfig,
+          position,
+          stateController: me,
+          onVoteHook: PublicKey.default,
+        })
+        .rpc({ skipPreflight: true });
+
+      // The cliff has not decayed since voting started
+      const acc = await proposalProgram.account.proposalV0.fetch(proposal!);
+      expectBnAccuracy(
+        toBN(
+          10000 *
+            (GENESIS_MULTIPLIER || 1) *
+            (BASELINE + ((SECS_PER_DAY * 200) / MAX_LOCKUP) * SCALE),
+          8
+        ),
+        acc.choices[0].weight,
+        0.0001
+      );
+    });
+
+    it("does not allow positions created after the snapshot to vote", async () => {
+      await startVoting();
+      await program.methods
+        .setTimeOffsetV0(new anchor.BN(SECS_PER_DAY))
+        .accountsPartial({ registrar })
+        .rpc();
+      const { position } = await createAndDeposit(10000, 200);
+
+      await expect(
+        program.methods
+          .voteV0({
+            choice: 0,
+          })
+          .accountsPartial({
+            registrar,
+            proposal,
+            proposalConfig,
+            position,
+            stateController: me,
+            onVoteHook: PublicKey.default,
+          })
+          .rpc()
+      ).to.eventually.be.rejectedWith("Error Code: LockupAfterSnapshot");
+    });
+
+    it("excludes tokens deposited after the snapshot", async () => {
+      const { position } = await createAndDeposit(10000, 200);
+      await startVoting();
+
+      await program.methods
+        .setTimeOffsetV0(new anchor.BN(SECS_PER_DAY))
+        .accountsPartial({ registrar })
+        .rpc();
+      await program.methods
+        .depositV0({ amount: toBN(10000, 8) })
+        .accountsPartial({ registrar, position, mint: hntMint })
+        .rpc({ skipPreflight: true });
+      await program.methods
+        .voteV0({
+          choice: 0,
+        })
+        .accountsPartial({
+          registrar,
+          proposal,
+          proposalConfig,
+          position,
+          stateController: me,
+          onVoteHook: PublicKey.default,
+        })
+        .rpc({ skipPreflight: true });
+
+      // Only the tokens locked at the snapshot count
+      const acc = await proposalProgram.account.proposalV0.fetch(proposal!);
+      expectBnAccuracy(
+        toBN(
+          10000 *
+            (GENESIS_MULTIPLIER || 1) *
+            (BASELINE + ((SECS_PER_DAY * 200) / MAX_LOCKUP) * SCALE),
+          8
+        ),
+        acc.choices[0].weight,
+        0.0001
+      );
+    });
+
+    it("allows vesting positions to vote after withdrawing vested tokens", async () => {
+      const { position } = await createAndDeposit(100, 10, { daily: {} });
+      await startVoting();
+
+      // 30 tokens have vested after 3 days
+      await program.methods
+        .setTimeOffsetV0(new anchor.BN(3.5 * SECS_PER_DAY))
+        .accountsPartial({ registrar })
+        .rpc();
+      await program.methods
+        .withdrawV0({ amount: toBN(30, 8) })
+        .accountsPartial({ position, depositMint: hntMint })
+        .rpc({ skipPreflight: true });
+      await program.methods
+        .voteV0({
+          choice: 0,
+        })
+        .accountsPartial({
+          registrar,
+          proposal,
+          proposalConfig,
+          position,
+          stateController: me,
+          onVoteHook: PublicKey.default,
+        })
+        .rpc({ skipPreflight: true });
+
+      const acc = await proposalProgram.account.proposalV0.fetch(proposal!);
+      expect(acc.choices[0].weight.gt(new anchor.BN(0))).to.be.true;
+    });
+
     describe("with proxy", async () => {
       let delegatee = Keypair.generate();
       let position: PublicKey;
@@ -469,6 +630,7 @@ describe("voter-stake-registry", () => {
           .rpcAndKeys({ skipPreflight: true });
 
         proxyAssignment = proxyAssignmentK!;
+        await startVoting();
       });
 
       it("(v0) allows voting on and relinquishing votes on the proposal", async () => {
@@ -496,6 +658,10 @@ describe("voter-stake-registry", () => {
           marker! as PublicKey
         );
         expect(markerA?.choices).to.deep.eq([0]);
+        let positionAcc = await program.account.positionV0.fetch(position);
+        expect(positionAcc.proxiedProposals.map((p) => p.toBase58())).to.deep.eq(
+          [proposal!.toBase58()]
+        );
 
         await program.methods
           .proxiedRelinquishVoteV0({
@@ -818,6 +984,7 @@ describe("voter-stake-registry", () => {
 
       beforeEach(async () => {
         ({ position, mint } = await createAndDeposit(10000, 200));
+        await startVoting();
 
         await program.methods
           .voteV0({
@@ -922,6 +1089,29 @@ describe("voter-stake-registry", () => {
           "AnchorError caused by account: source_position. Error Code: ActiveVotesExist. Error Number: 6055. Error Message: Cannot change a position while active votes exist."
         );
       });
+
+      it("should not allow me to merge into a position while vote is active", async () => {
+        const { position: newPos, mint: newMint } = await createAndDeposit(
+          10,
+          185
+        );
+        await expect(
+          program.methods
+            .mergePositionsV0()
+            .accountsPartial({
+              common: {
+                sourcePosition: newPos,
+                targetPosition: position,
+                depositMint: hntMint,
+              },
+              targetPositionTokenAccount: getAssociatedTokenAddressSync(
+                mint,
+                me
+              ),
+            })
+            .rpc()
+        ).to.eventually.be.rejectedWith("Error Code: ActiveVotesExist");
+      });
     });
   });
 
@@ -1046,5 +1236,169 @@ describe("voter-stake-registry", () => {
         toBN(90, 8).toNumber()
       );
     });
+
+    it("allows merging a position into one with a shorter lockup", async () => {
+      const { position: newPos } = await createAndDeposit(10, 200);
+      const positionAcc = await program.account.positionV0.fetch(position);
+      await program.methods
+        .mergePositionsV0()
+        .accountsPartial({
+          common: {
+            sourcePosition: newPos,
+            targetPosition: position,
+            depositMint: hntMint,
+          },
+          targetPositionTokenAccount: getAssociatedTokenAddressSync(
+            positionAcc.mint,
+            me
+          ),
+        })
+        .rpc({ skipPreflight: true });
+
+      const newPosAcc = await program.account.positionV0.fetch(newPos);
+      const mergedAcc = await program.account.positionV0.fetch(position);
+      expect(newPosAcc.amountDepositedNative.toNumber()).to.equal(0);
+      expect(mergedAcc.amountDepositedNative.toNumber()).to.equal(
+        toBN(110, 8).toNumber()
+      );
+      // The merged position takes the stricter lockup end
+      expect(mergedAcc.lockup.endTs.toNumber()).to.equal(
+        newPosAcc.lockup.endTs.toNumber()
+      );
+      expect(Boolean(mergedAcc.lockup.kind.cliff)).to.be.true;
+
+      await program.methods
+        .closePositionV0()
+        .accountsPartial({ position: newPos })
+        .rpc();
+    });
+
+    it("allows splitting a position while keeping its lockup", async () => {
+      const mintKeypair = Keypair.generate();
+      const newPos = positionKey(mintKeypair.publicKey)[0];
+      await program.methods
+        .splitPositionV0({ amount: toBN(30, 8) })
+        .preInstructions(
+          await createMintInstructions(provider, 0, newPos, newPos, mintKeypair)
+        )
+        .accountsPartial({
+          registrar,
+          collection,
+          sourcePosition: position,
+          mint: mintKeypair.publicKey,
+          depositMint: hntMint,
+        })
+        .signers([mintKeypair])
+        .rpc({ skipPreflight: true });
+
+      const newPosAcc = await program.account.positionV0.fetch(newPos);
+      const oldPosAcc = await program.account.positionV0.fetch(position);
+      expect(newPosAcc.amountDepositedNative.toNumber()).to.equal(
+        toBN(30, 8).toNumber()
+      );
+      expect(oldPosAcc.amountDepositedNative.toNumber()).to.equal(
+        toBN(70, 8).toNumber()
+      );
+      expect(newPosAcc.lockup.startTs.toNumber()).to.equal(
+        oldPosAcc.lockup.startTs.toNumber()
+      );
+      expect(newPosAcc.lockup.endTs.toNumber()).to.equal(
+        oldPosAcc.lockup.endTs.toNumber()
+      );
+
+      const fullMintKeypair = Keypair.generate();
+      const fullPos = positionKey(fullMintKeypair.publicKey)[0];
+      await expect(
+        program.methods
+          .splitPositionV0({ amount: toBN(70, 8) })
+          .preInstructions(
+            await createMintInstructions(
+              provider,
+              0,
+              fullPos,
+              fullPos,
+              fullMintKeypair
+            )
+          )
+          .accountsPartial({
+            registrar,
+            collection,
+            sourcePosition: position,
+            mint: fullMintKeypair.publicKey,
+            depositMint: hntMint,
+          })
+          .signers([fullMintKeypair])
+          .rpc()
+      ).to.eventually.be.rejectedWith("Error Code: InvalidSplitAmount");
+    });
+  });
+
+  describe("with vesting position", async () => {
+    let position: PublicKey;
+
+    beforeEach(async () => {
+      ({ position } = await createAndDeposit(100, 10, { daily: {} }));
+    });
+
+    it("allows withdrawing vested tokens on the original schedule", async () => {
+      await program.methods
+        .setTimeOffsetV0(new anchor.BN(3.5 * SECS_PER_DAY))
+        .accountsPartial({ registrar })
+        .rpc({ skipPreflight: true });
+
+      // 30 tokens have vested after 3 days
+      await program.methods
+        .withdrawV0({ amount: toBN(20, 8) })
+        .accountsPartial({ position, depositMint: hntMint })
+        .rpc({ skipPreflight: true });
+      await expect(
+        program.methods
+          .withdrawV0({ amount: toBN(11, 8) })
+          .accountsPartial({ position, depositMint: hntMint })
+          .rpc()
+      ).to.eventually.be.rejectedWith(
+        "Error Code: InsufficientUnlockedTokens"
+      );
+      await program.methods
+        .withdrawV0({ amount: toBN(10, 8) })
+        .accountsPartial({ position, depositMint: hntMint })
+        .rpc({ skipPreflight: true });
+
+      // The remaining 70 tokens keep vesting 10 per day
+      await program.methods
+        .setTimeOffsetV0(new anchor.BN(4.5 * SECS_PER_DAY))
+        .accountsPartial({ registrar })
+        .rpc({ skipPreflight: true });
+      await program.methods
+        .withdrawV0({ amount: toBN(10, 8) })
+        .accountsPartial({ position, depositMint: hntMint })
+        .rpc({ skipPreflight: true });
+
+      const positionAcc = await program.account.positionV0.fetch(position);
+      expect(positionAcc.amountDepositedNative.toNumber()).to.equal(
+        toBN(60, 8).toNumber()
+      );
+      // Withdrawing everything vested leaves the 6 unvested days
+      expect(
+        positionAcc.lockup.endTs.sub(positionAcc.lockup.startTs).toNumber()
+      ).to.equal(6 * SECS_PER_DAY);
+    });
+
+    it("does not allow moving tokens into a less strict lockup", async () => {
+      const { position: newPos } = await createAndDeposit(10, 10, {
+        daily: {},
+      });
+      const { position: cliffPos } = await createAndDeposit(10, 10);
+      await expect(
+        program.methods
+          .transferV0({ amount: toBN(10, 8) })
+          .accountsPartial({
+            sourcePosition: cliffPos,
+            targetPosition: newPos,
+            depositMint: hntMint,
+          })
+          .rpc()
+      ).to.eventually.be.rejectedWith("Error Code: InvalidLockupKind");
+    });
   });
 });
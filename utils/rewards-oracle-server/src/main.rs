@@ -0,0 +1,357 @@
+#[macro_use]
+extern crate rocket;
+use std::{env, str::FromStr};
+
+use rocket::{
+  http::Status,
+  serde::{json::Json, Deserialize, Serialize},
+  Build, Rocket, State,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+  hash::Hash,
+  pubkey::Pubkey,
+  signature::{read_keypair_file, Keypair},
+  signer::Signer,
+};
+
+use crate::{
+  source::RewardsSource,
+  transaction::{set_current_rewards_transaction, SetCurrentRewardsParams},
+};
+
+mod source;
+mod transaction;
+
+pub struct OracleState {
+  pub oracle: Keypair,
+  pub lazy_distributor: Pubkey,
+  pub oracle_index: u16,
+  pub source: RewardsSource,
+  // None in mock mode, where transactions use a default blockhash
+  pub rpc_client: Option<RpcClient>,
+}
+
+impl OracleState {
+  /// Configures the oracle from the environment:
+  ///
+  /// - `ANCHOR_WALLET`: oracle keypair, defaults to `keypair.json`. Mock mode generates one if
+  ///   the file is missing
+  /// - `LAZY_DISTRIBUTOR`: lazy distributor the oracle reports for
+  /// - `ORACLE_INDEX`: index of the oracle in the lazy distributor, defaults to 0
+  /// - `REWARDS_SOURCE`: `postgres://` url or path to a rewards JSON file
+  /// - `SOLANA_URL`: rpc used for recent blockhashes, defaults to localnet
+  /// - `MOCK`: set to `true` to run without rpc access
+  pub async fn from_env() -> Result<Self, String> {
+    let mock = env::var("MOCK").map(|mock| mock == "true").unwrap_or(false);
+    let keypair_path = env::var("ANCHOR_WALLET").unwrap_or("keypair.json".to_string());
+    let oracle = match read_keypair_file(&keypair_path) {
+      Ok(oracle) => oracle,
+      Err(_) if mock => Keypair::new(),
+      Err(e) => return Err(format!("failed to read keypair: {:?}", e)),
+    };
+    let lazy_distributor = env::var("LAZY_DISTRIBUTOR")
+      .map_err(|_| "LAZY_DISTRIBUTOR is required".to_string())
+      .and_then(|key| Pubkey::from_str(&key).map_err(|e| e.to_string()))?;
+    let oracle_index = env::var("ORACLE_INDEX")
+      .map(|index| {
+        index
+          .parse()
+          .map_err(|_| "invalid ORACLE_INDEX".to_string())
+      })
+      .unwrap_or(Ok(0))?;
+    let source = RewardsSource::open(
+      &env::var("REWARDS_SOURCE").map_err(|_| "REWARDS_SOURCE is required".to_string())?,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    let rpc_client = (!mock).then(|| {
+      RpcClient::new(env::var("SOLANA_URL").unwrap_or("http://127.0.0.1:8899".to_string()))
+    });
+
+    info!(
+      "oracle {} at index {} for lazy distributor {}",
+      oracle.pubkey(),
+      oracle_index,
+      lazy_distributor
+    );
+    Ok(Self {
+      oracle,
+      lazy_distributor,
+      oracle_index,
+      source,
+      rpc_client,
+    })
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct HealthResponse {
+  pub ok: bool,
+}
+
+#[get("/health")]
+fn health() -> Json<HealthResponse> {
+  Json(HealthResponse { ok: true })
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct CurrentRewardsResponse {
+  // Decimal string, since cumulative rewards can exceed what JSON numbers hold exactly
+  pub current_rewards: String,
+}
+
+fn parse_asset(asset_id: &str) -> Result<Pubkey, Status> {
+  Pubkey::from_str(asset_id).map_err(|_| {
+    error!("invalid asset id {}", asset_id);
+    Status::BadRequest
+  })
+}
+
+async fn lookup(state: &OracleState, asset: &Pubkey) -> Result<source::RewardsEntry, Status> {
+  state
+    .source
+    .current_rewards(asset)
+    .await
+    .map_err(|e| {
+      error!("failed to read current rewards: {}", e);
+      Status::InternalServerError
+    })?
+    .ok_or(Status::NotFound)
+}
+
+#[get("/?<asset_id>")]
+async fn current_rewards(
+  asset_id: &str,
+  state: &State<OracleState>,
+) -> Result<Json<CurrentRewardsResponse>, Status> {
+  let entry = lookup(state, &parse_asset(asset_id)?).await?;
+
+  Ok(Json(CurrentRewardsResponse {
+    current_rewards: entry.current_rewards.to_string(),
+  }))
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct SetCurrentRewardsRequest<'a> {
+  pub asset_id: &'a str,
+  // Fee payer of the transaction, the only signature left to add
+  pub payer: &'a str,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct SetCurrentRewardsResult {
+  // hex encoded solana transaction
+  pub transaction: String,
+  pub current_rewards: String,
+}
+
+#[post(
+  "/v1/set-current-rewards",
+  format = "application/json",
+  data = "<request>"
+)]
+async fn set_current_rewards<'a>(
+  request: Json<SetCurrentRewardsRequest<'a>>,
+  state: &State<OracleState>,
+) -> Result<Json<SetCurrentRewardsResult>, Status> {
+  let asset = parse_asset(request.asset_id)?;
+  let payer = Pubkey::from_str(request.payer).map_err(|_| {
+    error!("invalid payer {}", request.payer);
+    Status::BadRequest
+  })?;
+  let entry = lookup(state, &asset).await?;
+
+  let recent_blockhash = match &state.rpc_client {
+    Some(rpc_client) => rpc_client.get_latest_blockhash().await.map_err(|e| {
+      error!("failed to get blockhash: {:?}", e);
+      Status::ServiceUnavailable
+    })?,
+    None => Hash::default(),
+  };
+  let transaction = set_current_rewards_transaction(
+    &state.oracle,
+    &SetCurrentRewardsParams {
+      lazy_distributor: state.lazy_distributor,
+      oracle_index: state.oracle_index,
+      asset,
+      key_to_asset: entry.key_to_asset,
+      current_rewards: entry.current_rewards,
+      payer,
+    },
+    recent_blockhash,
+  )
+  .map_err(|e| {
+    error!("failed to build transaction: {:?}", e);
+    Status::InternalServerError
+  })?;
+
+  let serialized_txn = hex::encode(bincode::serialize(&transaction).map_err(|e| {
+    error!("failed to serialize transaction: {:?}", e);
+    Status::InternalServerError
+  })?);
+
+  Ok(Json(SetCurrentRewardsResult {
+    transaction: serialized_txn,
+    current_rewards: entry.current_rewards.to_string(),
+  }))
+}
+
+pub fn build(state: OracleState) -> Rocket<Build> {
+  rocket::build()
+    .manage(state)
+    .mount("/", routes![health, current_rewards, set_current_rewards])
+}
+
+#[launch]
+async fn rocket() -> _ {
+  let state = OracleState::from_env()
+    .await
+    .unwrap_or_else(|e| panic!("failed to configure oracle: {}", e));
+  build(state)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use anchor_lang::AccountDeserialize;
+  use lazy_distributor::{ed25519::check_ed25519_data, SetCurrentRewardsTransactionV0};
+  use rocket::{http::ContentType, local::asynchronous::Client};
+  use solana_sdk::{ed25519_program, signature::Signature, transaction::VersionedTransaction};
+
+  use super::*;
+  use crate::{source::RewardsEntry, transaction::recipient_key};
+
+  async fn mock_client(oracle: Keypair, asset: Pubkey, entry: RewardsEntry) -> Client {
+    let state = OracleState {
+      oracle,
+      lazy_distributor: Pubkey::new_unique(),
+      oracle_index: 1,
+      source: RewardsSource::File(HashMap::from([(asset, entry)])),
+      rpc_client: None,
+    };
+    Client::tracked(build(state)).await.unwrap()
+  }
+
+  #[rocket::async_test]
+  async fn returns_current_rewards() {
+    let asset = Pubkey::new_unique();
+    let client = mock_client(
+      Keypair::new(),
+      asset,
+      RewardsEntry {
+        key_to_asset: Pubkey::new_unique(),
+        current_rewards: 5_000_000,
+      },
+    )
+    .await;
+
+    let response = client.get(format!("/?asset_id={}", asset)).dispatch().await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: CurrentRewardsResponse = response.into_json().await.unwrap();
+    assert_eq!(body.current_rewards, "5000000");
+
+    let response = client
+      .get(format!("/?asset_id={}", Pubkey::new_unique()))
+      .dispatch()
+      .await;
+    assert_eq!(response.status(), Status::NotFound);
+  }
+
+  #[rocket::async_test]
+  async fn signs_set_current_rewards() {
+    let oracle = Keypair::new();
+    let oracle_key = oracle.pubkey();
+    let asset = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let entry = RewardsEntry {
+      key_to_asset: Pubkey::new_unique(),
+      current_rewards: 5_000_000,
+    };
+    let client = mock_client(oracle, asset, entry).await;
+    let lazy_distributor = client
+      .rocket()
+      .state::<OracleState>()
+      .unwrap()
+      .lazy_distributor;
+
+    let response = client
+      .post("/v1/set-current-rewards")
+      .header(ContentType::JSON)
+      .body(format!(r#"{{"asset_id":"{}","payer":"{}"}}"#, asset, payer))
+      .dispatch()
+      .await;
+    assert_eq!(response.status(), Status::Ok);
+    let body: SetCurrentRewardsResult = response.into_json().await.unwrap();
+    let transaction: VersionedTransaction =
+      bincode::deserialize(&hex::decode(body.transaction).unwrap()).unwrap();
+
+    // Only the payer is left to sign
+    let account_keys = transaction.message.static_account_keys();
+    assert_eq!(transaction.signatures, vec![Signature::default()]);
+    assert_eq!(account_keys[0], payer);
+
+    let instructions = transaction.message.instructions();
+    assert_eq!(instructions.len(), 2);
+    assert_eq!(
+      account_keys[usize::from(instructions[0].program_id_index)],
+      ed25519_program::ID
+    );
+    let data = &instructions[0].data;
+    let message = check_ed25519_data(data, oracle_key.as_ref()).unwrap();
+    assert!(Signature::try_from(&data[48..112])
+      .unwrap()
+      .verify(oracle_key.as_ref(), &message));
+    let signed = SetCurrentRewardsTransactionV0::try_deserialize(&mut message.as_slice()).unwrap();
+    assert_eq!(signed.lazy_distributor, lazy_distributor);
+    assert_eq!(signed.oracle_index, 1);
+    assert_eq!(signed.current_rewards, entry.current_rewards);
+    assert_eq!(signed.asset, asset);
+
+    assert_eq!(
+      account_keys[usize::from(instructions[1].program_id_index)],
+      rewards_oracle::ID
+    );
+    assert!(account_keys.contains(&recipient_key(&lazy_distributor, &asset)));
+    assert!(account_keys.contains(&entry.key_to_asset));
+  }
+
+  #[rocket::async_test]
+  async fn rejects_invalid_requests() {
+    let asset = Pubkey::new_unique();
+    let client = mock_client(
+      Keypair::new(),
+      asset,
+      RewardsEntry {
+        key_to_asset: Pubkey::new_unique(),
+        current_rewards: 1,
+      },
+    )
+    .await;
+
+    let response = client
+      .post("/v1/set-current-rewards")
+      .header(ContentType::JSON)
+      .body(format!(r#"{{"asset_id":"{}","payer":"not-a-key"}}"#, asset))
+      .dispatch()
+      .await;
+    assert_eq!(response.status(), Status::BadRequest);
+
+    let response = client
+      .post("/v1/set-current-rewards")
+      .header(ContentType::JSON)
+      .body(format!(
+        r#"{{"asset_id":"{}","payer":"{}"}}"#,
+        Pubkey::new_unique(),
+        Pubkey::new_unique()
+      ))
+      .dispatch()
+      .await;
+    assert_eq!(response.status(), Status::NotFound);
+  }
+}
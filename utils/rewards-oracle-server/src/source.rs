@@ -0,0 +1,101 @@
+use std::{collections::HashMap, fs::File, io::BufReader, str::FromStr};
+
+use rocket::serde::{json::serde_json, Deserialize};
+use solana_sdk::pubkey::Pubkey;
+use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SourceError {
+  #[error("failed to read rewards file: {0}")]
+  Io(#[from] std::io::Error),
+  #[error("failed to parse rewards file: {0}")]
+  Json(#[from] serde_json::Error),
+  #[error("invalid pubkey {0}")]
+  InvalidPubkey(String),
+  #[error("database error: {0}")]
+  Database(#[from] sqlx::Error),
+  #[error("invalid current rewards {0}")]
+  InvalidRewards(i64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RewardsEntry {
+  // KeyToAssetV0 linking the entity key the rewards were earned by to the asset
+  pub key_to_asset: Pubkey,
+  // Cumulative rewards of the asset, in the lazy distributor's rewards mint
+  pub current_rewards: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(crate = "rocket::serde")]
+struct FileEntry {
+  key_to_asset: String,
+  current_rewards: u64,
+}
+
+/// Where the oracle reads cumulative rewards from
+pub enum RewardsSource {
+  /// JSON object of asset id to `{ "key_to_asset", "current_rewards" }`, read once at startup
+  File(HashMap<Pubkey, RewardsEntry>),
+  /// Table `oracle_rewards (asset TEXT PRIMARY KEY, key_to_asset TEXT, current_rewards BIGINT)`
+  Postgres(PgPool),
+}
+
+fn parse_pubkey(key: &str) -> Result<Pubkey, SourceError> {
+  Pubkey::from_str(key).map_err(|_| SourceError::InvalidPubkey(key.to_string()))
+}
+
+impl RewardsSource {
+  /// Connects to Postgres for `postgres://` urls and reads a rewards file otherwise
+  pub async fn open(source: &str) -> Result<Self, SourceError> {
+    if source.starts_with("postgres://") || source.starts_with("postgresql://") {
+      let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .connect(source)
+        .await?;
+      return Ok(Self::Postgres(pool));
+    }
+
+    let entries: HashMap<String, FileEntry> =
+      serde_json::from_reader(BufReader::new(File::open(source)?))?;
+    Self::from_file_entries(entries)
+  }
+
+  fn from_file_entries(entries: HashMap<String, FileEntry>) -> Result<Self, SourceError> {
+    entries
+      .into_iter()
+      .map(|(asset, entry)| {
+        Ok((
+          parse_pubkey(&asset)?,
+          RewardsEntry {
+            key_to_asset: parse_pubkey(&entry.key_to_asset)?,
+            current_rewards: entry.current_rewards,
+          },
+        ))
+      })
+      .collect::<Result<HashMap<_, _>, _>>()
+      .map(Self::File)
+  }
+
+  pub async fn current_rewards(&self, asset: &Pubkey) -> Result<Option<RewardsEntry>, SourceError> {
+    match self {
+      Self::File(entries) => Ok(entries.get(asset).copied()),
+      Self::Postgres(pool) => {
+        let row =
+          sqlx::query("SELECT key_to_asset, current_rewards FROM oracle_rewards WHERE asset = $1")
+            .bind(asset.to_string())
+            .fetch_optional(pool)
+            .await?;
+        let Some(row) = row else {
+          return Ok(None);
+        };
+        let current_rewards: i64 = row.try_get("current_rewards")?;
+        Ok(Some(RewardsEntry {
+          key_to_asset: parse_pubkey(row.try_get("key_to_asset")?)?,
+          current_rewards: u64::try_from(current_rewards)
+            .map_err(|_| SourceError::InvalidRewards(current_rewards))?,
+        }))
+      }
+    }
+  }
+}
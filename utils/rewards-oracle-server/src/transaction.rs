@@ -0,0 +1,115 @@
+use anchor_lang::{AccountSerialize, InstructionData, ToAccountMetas};
+use lazy_distributor::SetCurrentRewardsTransactionV0;
+use rewards_oracle::SetCurrentRewardsWrapperArgsV1;
+use solana_sdk::{
+  ed25519_program,
+  hash::Hash,
+  instruction::Instruction,
+  message::{Message, VersionedMessage},
+  pubkey::Pubkey,
+  signature::{Keypair, Signature},
+  signer::Signer,
+  system_program, sysvar,
+  transaction::VersionedTransaction,
+};
+
+pub struct SetCurrentRewardsParams {
+  pub lazy_distributor: Pubkey,
+  pub oracle_index: u16,
+  pub asset: Pubkey,
+  pub key_to_asset: Pubkey,
+  pub current_rewards: u64,
+  pub payer: Pubkey,
+}
+
+pub fn recipient_key(lazy_distributor: &Pubkey, asset: &Pubkey) -> Pubkey {
+  Pubkey::find_program_address(
+    &[b"recipient", lazy_distributor.as_ref(), asset.as_ref()],
+    &lazy_distributor::ID,
+  )
+  .0
+}
+
+/// Ed25519 program instruction carrying the signature, pubkey and message inline, in the
+/// layout `lazy_distributor::ed25519::check_ed25519_data` expects
+pub fn ed25519_instruction(pubkey: &Pubkey, signature: &Signature, message: &[u8]) -> Instruction {
+  let public_key_offset: u16 = 16; // 2*u8 + 7*u16
+  let signature_offset = public_key_offset + 32;
+  let message_offset = signature_offset + 64;
+
+  let mut data = Vec::with_capacity(usize::from(message_offset) + message.len());
+  data.extend([1, 0]);
+  data.extend(signature_offset.to_le_bytes());
+  data.extend(u16::MAX.to_le_bytes());
+  data.extend(public_key_offset.to_le_bytes());
+  data.extend(u16::MAX.to_le_bytes());
+  data.extend(message_offset.to_le_bytes());
+  data.extend(u16::try_from(message.len()).unwrap().to_le_bytes());
+  data.extend(u16::MAX.to_le_bytes());
+  data.extend(pubkey.as_ref());
+  data.extend(signature.as_ref());
+  data.extend(message);
+
+  Instruction {
+    program_id: ed25519_program::ID,
+    accounts: vec![],
+    data,
+  }
+}
+
+/// Builds a set_current_rewards_wrapper_v2 transaction preceded by the oracle's signature over
+/// the SetCurrentRewardsTransactionV0 message. Only the payer's signature is left to add.
+pub fn set_current_rewards_transaction(
+  oracle: &Keypair,
+  params: &SetCurrentRewardsParams,
+  recent_blockhash: Hash,
+) -> anchor_lang::Result<VersionedTransaction> {
+  let mut message = Vec::new();
+  SetCurrentRewardsTransactionV0 {
+    lazy_distributor: params.lazy_distributor,
+    oracle_index: params.oracle_index,
+    current_rewards: params.current_rewards,
+    asset: params.asset,
+  }
+  .try_serialize(&mut message)?;
+  let signature = oracle.sign_message(&message);
+
+  let set_current_rewards_ix = Instruction {
+    program_id: rewards_oracle::ID,
+    accounts: rewards_oracle::accounts::SetCurrentRewardsWrapperV2 {
+      payer: params.payer,
+      lazy_distributor: params.lazy_distributor,
+      recipient: recipient_key(&params.lazy_distributor, &params.asset),
+      key_to_asset: params.key_to_asset,
+      oracle_signer: Pubkey::find_program_address(
+        &[rewards_oracle::ORACLE_SIGNER_SEED],
+        &rewards_oracle::ID,
+      )
+      .0,
+      lazy_distributor_program: lazy_distributor::ID,
+      system_program: system_program::ID,
+      sysvar_instructions: sysvar::instructions::ID,
+    }
+    .to_account_metas(None),
+    data: rewards_oracle::instruction::SetCurrentRewardsWrapperV2 {
+      args: SetCurrentRewardsWrapperArgsV1 {
+        oracle_index: params.oracle_index,
+        current_rewards: params.current_rewards,
+      },
+    }
+    .data(),
+  };
+
+  let message = Message::new_with_blockhash(
+    &[
+      ed25519_instruction(&oracle.pubkey(), &signature, &message),
+      set_current_rewards_ix,
+    ],
+    Some(&params.payer),
+    &recent_blockhash,
+  );
+  Ok(VersionedTransaction {
+    signatures: vec![Signature::default(); usize::from(message.header.num_required_signatures)],
+    message: VersionedMessage::Legacy(message),
+  })
+}
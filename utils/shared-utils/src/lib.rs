@@ -1,5 +1,6 @@
 pub mod compressed_nfts;
 pub mod error;
+pub mod merkle_proof;
 pub mod precise_number;
 pub mod resize_to_fit;
 pub mod signed_precise_number;
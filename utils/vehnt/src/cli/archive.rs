@@ -0,0 +1,295 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fs::{self, File},
+  io::{BufRead, BufReader, BufWriter, Write},
+  path::{Path, PathBuf},
+};
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use helium_sub_daos::{
+  current_epoch, DaoEpochInfoV0, DelegatedPositionV0, SubDaoEpochInfoV0, SubDaoV0,
+};
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{clock::Clock, pubkey::Pubkey, sysvar};
+use voter_stake_registry::state::{PositionV0, Registrar};
+
+use super::*;
+use crate::cli::delegated::{get_accounts_with_prefix, get_stake_accounts};
+
+#[derive(Debug, Clone, clap::Args)]
+/// Snapshots sub dao epoch accounting and all delegated positions into `<dir>/<epoch>.jsonl`.
+///
+/// Run it at least once per epoch to build up a history that `replay` can audit.
+pub struct Archive {
+  #[arg(short, long, default_value = "archive")]
+  pub dir: PathBuf,
+}
+
+/// A single line of a snapshot file. Accounts are stored as base64 encoded raw account data so
+/// they can be replayed with the exact on-chain types.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ArchiveRecord {
+  Snapshot {
+    epoch: u64,
+    unix_timestamp: i64,
+    slot: u64,
+  },
+  SubDao {
+    key: String,
+    data: String,
+  },
+  SubDaoEpochInfo {
+    key: String,
+    data: String,
+  },
+  DaoEpochInfo {
+    key: String,
+    data: String,
+  },
+  Registrar {
+    key: String,
+    data: String,
+  },
+  Position {
+    key: String,
+    data: String,
+  },
+  DelegatedPosition {
+    key: String,
+    data: String,
+  },
+}
+
+#[derive(Default)]
+pub struct Snapshot {
+  pub epoch: u64,
+  pub unix_timestamp: i64,
+  pub slot: u64,
+  pub sub_daos: HashMap<Pubkey, SubDaoV0>,
+  pub sub_dao_epoch_infos: HashMap<Pubkey, SubDaoEpochInfoV0>,
+  pub dao_epoch_infos: HashMap<Pubkey, DaoEpochInfoV0>,
+  pub registrars: HashMap<Pubkey, Registrar>,
+  pub positions: HashMap<Pubkey, PositionV0>,
+  pub delegated_positions: Vec<(Pubkey, DelegatedPositionV0)>,
+}
+
+fn encode(key: &Pubkey, data: &[u8]) -> (String, String) {
+  (key.to_string(), STANDARD.encode(data))
+}
+
+fn decode<T: AccountDeserialize>(key: &str, data: &str) -> MyResult<(Pubkey, T)> {
+  let bytes = STANDARD.decode(data)?;
+  Ok((
+    Pubkey::from_str(key)?,
+    T::try_deserialize(&mut bytes.as_slice())?,
+  ))
+}
+
+impl Snapshot {
+  pub fn read(path: &Path) -> MyResult<Self> {
+    let mut snapshot = Self::default();
+    for line in BufReader::new(File::open(path)?).lines() {
+      let line = line?;
+      if line.is_empty() {
+        continue;
+      }
+      match serde_json::from_str::<ArchiveRecord>(&line)? {
+        ArchiveRecord::Snapshot {
+          epoch,
+          unix_timestamp,
+          slot,
+        } => {
+          snapshot.epoch = epoch;
+          snapshot.unix_timestamp = unix_timestamp;
+          snapshot.slot = slot;
+        }
+        ArchiveRecord::SubDao { key, data } => {
+          let (key, value) = decode(&key, &data)?;
+          snapshot.sub_daos.insert(key, value);
+        }
+        ArchiveRecord::SubDaoEpochInfo { key, data } => {
+          let (key, value) = decode(&key, &data)?;
+          snapshot.sub_dao_epoch_infos.insert(key, value);
+        }
+        ArchiveRecord::DaoEpochInfo { key, data } => {
+          let (key, value) = decode(&key, &data)?;
+          snapshot.dao_epoch_infos.insert(key, value);
+        }
+        ArchiveRecord::Registrar { key, data } => {
+          let (key, value) = decode(&key, &data)?;
+          snapshot.registrars.insert(key, value);
+        }
+        ArchiveRecord::Position { key, data } => {
+          let (key, value) = decode(&key, &data)?;
+          snapshot.positions.insert(key, value);
+        }
+        ArchiveRecord::DelegatedPosition { key, data } => {
+          snapshot.delegated_positions.push(decode(&key, &data)?);
+        }
+      }
+    }
+    Ok(snapshot)
+  }
+}
+
+/// Lists the snapshot files in `inputs`, expanding directories, ordered by epoch.
+pub fn snapshot_paths(inputs: &[PathBuf]) -> MyResult<Vec<PathBuf>> {
+  let mut paths = Vec::new();
+  for input in inputs {
+    if input.is_dir() {
+      for entry in fs::read_dir(input)? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "jsonl") {
+          paths.push(path);
+        }
+      }
+    } else {
+      paths.push(input.clone());
+    }
+  }
+  paths.sort_by_key(|path| {
+    path
+      .file_stem()
+      .and_then(|stem| stem.to_str())
+      .and_then(|stem| stem.parse::<u64>().ok())
+  });
+  Ok(paths)
+}
+
+const BATCH_SIZE: usize = 100;
+
+async fn get_multiple_accounts(
+  rpc_client: &RpcClient,
+  keys: &[Pubkey],
+) -> MyResult<Vec<(Pubkey, solana_sdk::account::Account)>> {
+  let mut accounts = Vec::new();
+  for chunk in keys.chunks(BATCH_SIZE) {
+    let chunk_accounts = rpc_client.get_multiple_accounts(chunk).await?;
+    accounts.extend(
+      chunk
+        .iter()
+        .zip(chunk_accounts)
+        .filter_map(|(key, account)| account.map(|account| (*key, account))),
+    );
+  }
+  Ok(accounts)
+}
+
+impl Archive {
+  pub async fn run(self, rpc_client: RpcClient, _solana_url: String) -> MyResult {
+    let clock_account = rpc_client.get_account(&sysvar::clock::id()).await?;
+    let clock: Clock = solana_sdk::account::from_account(&clock_account).unwrap();
+
+    let sub_dao_keys = [
+      Pubkey::from_str(IOT_SUBDAO)?,
+      Pubkey::from_str(MOBILE_SUBDAO)?,
+    ];
+    let sub_daos = get_multiple_accounts(&rpc_client, &sub_dao_keys).await?;
+
+    let delegated_positions = get_stake_accounts(&rpc_client).await?;
+    let position_keys = delegated_positions
+      .iter()
+      .map(|(_, account)| {
+        DelegatedPositionV0::try_deserialize(&mut account.data.as_slice())
+          .map(|delegated_position| delegated_position.position)
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    let positions = get_multiple_accounts(&rpc_client, &position_keys).await?;
+
+    let registrar_keys = positions
+      .iter()
+      .map(|(_, account)| {
+        PositionV0::try_deserialize(&mut account.data.as_slice()).map(|position| position.registrar)
+      })
+      .collect::<Result<HashSet<_>, _>>()?
+      .into_iter()
+      .collect::<Vec<_>>();
+    let registrars = get_multiple_accounts(&rpc_client, &registrar_keys).await?;
+
+    // Registrars may run with a time offset on devnet
+    let unix_timestamp = registrars
+      .first()
+      .map(|(_, account)| Registrar::try_deserialize(&mut account.data.as_slice()))
+      .transpose()?
+      .map_or(clock.unix_timestamp, |registrar| {
+        clock.unix_timestamp + registrar.time_offset
+      });
+    let epoch = current_epoch(unix_timestamp);
+
+    let sub_dao_epoch_infos =
+      get_accounts_with_prefix(&rpc_client, SubDaoEpochInfoV0::DISCRIMINATOR).await?;
+
+    let mut dao_keys = HashSet::new();
+    for (_, account) in sub_daos.iter() {
+      dao_keys.insert(SubDaoV0::try_deserialize(&mut account.data.as_slice())?.dao);
+    }
+    let dao_epoch_info_keys = dao_keys
+      .iter()
+      .flat_map(|dao| {
+        [epoch - 1, epoch].map(|epoch| {
+          Pubkey::find_program_address(
+            &[b"dao_epoch_info", dao.as_ref(), &epoch.to_le_bytes()],
+            &helium_sub_daos::id(),
+          )
+          .0
+        })
+      })
+      .collect::<Vec<_>>();
+    let dao_epoch_infos = get_multiple_accounts(&rpc_client, &dao_epoch_info_keys).await?;
+
+    fs::create_dir_all(&self.dir)?;
+    let path = self.dir.join(format!("{}.jsonl", epoch));
+    let mut writer = BufWriter::new(File::create(&path)?);
+    let mut write = |record: ArchiveRecord| -> MyResult {
+      serde_json::to_writer(&mut writer, &record)?;
+      writer.write_all(b"\n")?;
+      Ok(())
+    };
+
+    write(ArchiveRecord::Snapshot {
+      epoch,
+      unix_timestamp,
+      slot: clock.slot,
+    })?;
+    for (key, account) in sub_daos.iter() {
+      let (key, data) = encode(key, &account.data);
+      write(ArchiveRecord::SubDao { key, data })?;
+    }
+    for (key, account) in sub_dao_epoch_infos.iter() {
+      let info = SubDaoEpochInfoV0::try_deserialize(&mut account.data.as_slice())?;
+      if sub_dao_keys.contains(&info.sub_dao) {
+        let (key, data) = encode(key, &account.data);
+        write(ArchiveRecord::SubDaoEpochInfo { key, data })?;
+      }
+    }
+    for (key, account) in dao_epoch_infos.iter() {
+      let (key, data) = encode(key, &account.data);
+      write(ArchiveRecord::DaoEpochInfo { key, data })?;
+    }
+    for (key, account) in registrars.iter() {
+      let (key, data) = encode(key, &account.data);
+      write(ArchiveRecord::Registrar { key, data })?;
+    }
+    for (key, account) in positions.iter() {
+      let (key, data) = encode(key, &account.data);
+      write(ArchiveRecord::Position { key, data })?;
+    }
+    for (key, account) in delegated_positions.iter() {
+      let (key, data) = encode(key, &account.data);
+      write(ArchiveRecord::DelegatedPosition { key, data })?;
+    }
+    drop(write);
+    writer.flush()?;
+
+    println!(
+      "Archived epoch {} ({} delegated positions) to {}",
+      epoch,
+      delegated_positions.len(),
+      path.display()
+    );
+    Ok(())
+  }
+}
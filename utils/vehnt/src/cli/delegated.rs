@@ -48,14 +48,14 @@ async fn get_stake_accounts_incremental(
 }
 
 /// This function will work until there's too many to fetch in a single call
-async fn get_stake_accounts(
+pub async fn get_stake_accounts(
   rpc_client: &RpcClient,
 ) -> MyResult<Vec<(Pubkey, solana_sdk::account::Account)>> {
   const DELEGATE_POSITION_V0_DESCRIMINATOR: [u8; 8] = [251, 212, 32, 100, 102, 1, 247, 81];
   get_accounts_with_prefix(&rpc_client, &DELEGATE_POSITION_V0_DESCRIMINATOR).await
 }
 
-async fn get_accounts_with_prefix(
+pub async fn get_accounts_with_prefix(
   rpc_client: &RpcClient,
   input: &[u8],
 ) -> MyResult<Vec<(Pubkey, solana_sdk::account::Account)>> {
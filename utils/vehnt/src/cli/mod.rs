@@ -1,7 +1,10 @@
 use super::*;
 
+mod archive;
 mod delegated;
 mod epoch_info;
+mod replay;
+mod simulate;
 
 #[derive(Debug, clap::Parser)]
 #[clap(version = env!("CARGO_PKG_VERSION"))]
@@ -15,15 +18,21 @@ pub struct Cli {
 
 #[derive(Debug, Clone, clap::Subcommand)]
 pub enum Cmd {
+  Archive(archive::Archive),
   Delegated(delegated::Delegated),
   EpochInfo(epoch_info::EpochInfo),
+  Replay(replay::Replay),
+  Simulate(simulate::Simulate),
 }
 
 impl Cli {
   pub async fn run(self, rpc_client: RpcClient) -> MyResult {
     match self.cmd {
+      Cmd::Archive(cmd) => cmd.run(rpc_client, self.solana_url).await,
       Cmd::Delegated(cmd) => cmd.run(rpc_client, self.solana_url).await,
       Cmd::EpochInfo(cmd) => cmd.run(rpc_client, self.solana_url).await,
+      Cmd::Replay(cmd) => cmd.run(rpc_client, self.solana_url).await,
+      Cmd::Simulate(cmd) => cmd.run(rpc_client, self.solana_url).await,
     }
   }
 }
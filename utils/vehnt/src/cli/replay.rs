@@ -0,0 +1,237 @@
+use std::{
+  cmp::min,
+  collections::{BTreeMap, HashSet},
+  path::PathBuf,
+};
+
+use helium_sub_daos::{
+  apply_fall_rate_factor, caclulate_vhnt_info, current_epoch, DelegatedPositionV0, PrecisePosition,
+  SubDaoEpochInfoV0, EPOCH_LENGTH,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use voter_stake_registry::state::{PositionV0, VotingMintConfigV0};
+
+use super::*;
+use crate::cli::archive::{snapshot_paths, Snapshot};
+
+#[derive(Debug, Clone, clap::Args)]
+/// Replays archived snapshots, recomputing sub dao epoch accounting from the archived positions
+/// and flagging every epoch where the on-chain values diverge.
+pub struct Replay {
+  /// Snapshot files or directories written by `archive`
+  #[arg(required = true)]
+  pub inputs: Vec<PathBuf>,
+  /// Maximum absolute difference (in the field's own units) that is not flagged
+  #[arg(long, default_value_t = 0)]
+  pub tolerance: u128,
+}
+
+#[derive(Debug, Default)]
+struct EpochAccounting {
+  // With full precision, like `SubDaoV0::vehnt_delegated`
+  vehnt_at_epoch_start: u128,
+  vehnt_in_closing_positions: u128,
+  fall_rates_from_closing_positions: u128,
+}
+
+fn sub_dao_epoch_info_key(sub_dao: &Pubkey, epoch: u64) -> Pubkey {
+  Pubkey::find_program_address(
+    &[
+      b"sub_dao_epoch_info",
+      sub_dao.as_ref(),
+      &epoch.to_le_bytes(),
+    ],
+    &helium_sub_daos::id(),
+  )
+  .0
+}
+
+// Precise voting power at `ts` without rewards compounded into the position after `ts`
+fn voting_power_precise_before_compounding(
+  position: &PositionV0,
+  voting_mint_config: &VotingMintConfigV0,
+  delegated_position: &DelegatedPositionV0,
+  ts: i64,
+) -> MyResult<u128> {
+  let mut position = position.clone();
+  position.amount_deposited_native -= delegated_position.compounded_after(ts);
+  Ok(position.voting_power_precise(voting_mint_config, ts)?)
+}
+
+/// Recomputes what the sub dao epoch infos of each sub dao should hold given the delegated
+/// positions in the snapshot, the same way delegation and closing update them on-chain.
+///
+/// Closing corrections can only be checked for epochs after the snapshot epoch, since positions
+/// that already closed are no longer in the snapshot.
+fn recompute(snapshot: &Snapshot) -> MyResult<BTreeMap<(Pubkey, u64), EpochAccounting>> {
+  let epoch_start_ts = i64::try_from(snapshot.epoch).unwrap() * EPOCH_LENGTH;
+  let mut expected: BTreeMap<(Pubkey, u64), EpochAccounting> = BTreeMap::new();
+  for sub_dao in snapshot.sub_daos.keys() {
+    expected.entry((*sub_dao, snapshot.epoch)).or_default();
+  }
+
+  for (_, delegated_position) in snapshot.delegated_positions.iter() {
+    let Some(position) = snapshot.positions.get(&delegated_position.position) else {
+      continue;
+    };
+    let Some(registrar) = snapshot.registrars.get(&position.registrar) else {
+      continue;
+    };
+    let voting_mint_config = &registrar.voting_mints[position.voting_mint_config_idx as usize];
+    let sub_dao = delegated_position.sub_dao;
+    let vehnt_info = caclulate_vhnt_info(
+      delegated_position.start_ts,
+      position,
+      voting_mint_config,
+      delegated_position.expiration_ts,
+    )?;
+
+    let end_ts = position.lockup.effective_end_ts();
+    let closing_epoch = current_epoch(if delegated_position.expiration_ts == 0 {
+      end_ts
+    } else {
+      min(end_ts, delegated_position.expiration_ts)
+    });
+    if closing_epoch > snapshot.epoch {
+      let closing = expected.entry((sub_dao, closing_epoch)).or_default();
+      closing.vehnt_in_closing_positions += vehnt_info.end_vehnt_correction;
+      closing.fall_rates_from_closing_positions += vehnt_info.end_fall_rate_correction;
+    }
+
+    let genesis_end_epoch = current_epoch(position.genesis_end);
+    if vehnt_info.has_genesis && genesis_end_epoch > snapshot.epoch {
+      let genesis_end = expected.entry((sub_dao, genesis_end_epoch)).or_default();
+      genesis_end.vehnt_in_closing_positions += vehnt_info.genesis_end_vehnt_correction;
+      genesis_end.fall_rates_from_closing_positions += vehnt_info.genesis_end_fall_rate_correction;
+    }
+
+    // Only positions delegated before the epoch started and not yet purged count towards its start
+    if current_epoch(delegated_position.start_ts) < snapshot.epoch && closing_epoch > snapshot.epoch
+    {
+      expected
+        .entry((sub_dao, snapshot.epoch))
+        .or_default()
+        .vehnt_at_epoch_start += voting_power_precise_before_compounding(
+        position,
+        voting_mint_config,
+        delegated_position,
+        epoch_start_ts,
+      )?;
+    }
+  }
+
+  Ok(expected)
+}
+
+fn flag(
+  tolerance: u128,
+  epoch: u64,
+  sub_dao: &Pubkey,
+  field: &str,
+  on_chain: u128,
+  recomputed: u128,
+) -> bool {
+  let diff = i128::try_from(on_chain).unwrap() - i128::try_from(recomputed).unwrap();
+  if diff.unsigned_abs() <= tolerance {
+    return false;
+  }
+  let sub_dao_name = SubDao::try_from(*sub_dao)
+    .map(|sub_dao| format!("{:?}", sub_dao))
+    .unwrap_or_else(|_| sub_dao.to_string());
+  println!(
+    "  epoch {} {} {}: on-chain {} recomputed {} diff {}",
+    epoch, sub_dao_name, field, on_chain, recomputed, diff
+  );
+  true
+}
+
+impl Replay {
+  pub async fn run(self, _rpc_client: RpcClient, _solana_url: String) -> MyResult {
+    let mut total_divergences = 0;
+    for path in snapshot_paths(&self.inputs)? {
+      let snapshot = Snapshot::read(&path)?;
+      println!(
+        "Snapshot {} (epoch {}, slot {}, {} delegated positions)",
+        path.display(),
+        snapshot.epoch,
+        snapshot.slot,
+        snapshot.delegated_positions.len()
+      );
+
+      let expected = recompute(&snapshot)?;
+      let default_info = SubDaoEpochInfoV0::default();
+      let mut divergences = 0;
+      for ((sub_dao, epoch), accounting) in expected.iter() {
+        let on_chain = snapshot
+          .sub_dao_epoch_infos
+          .get(&sub_dao_epoch_info_key(sub_dao, *epoch))
+          .unwrap_or(&default_info);
+        if *epoch == snapshot.epoch {
+          // On-chain the epoch start is summed with full precision and rounded once
+          divergences += u32::from(flag(
+            self.tolerance,
+            *epoch,
+            sub_dao,
+            "vehnt_at_epoch_start",
+            u128::from(on_chain.vehnt_at_epoch_start),
+            apply_fall_rate_factor(accounting.vehnt_at_epoch_start).unwrap(),
+          ));
+          continue;
+        }
+        divergences += u32::from(flag(
+          self.tolerance,
+          *epoch,
+          sub_dao,
+          "vehnt_in_closing_positions",
+          on_chain.vehnt_in_closing_positions,
+          accounting.vehnt_in_closing_positions,
+        ));
+        divergences += u32::from(flag(
+          self.tolerance,
+          *epoch,
+          sub_dao,
+          "fall_rates_from_closing_positions",
+          on_chain.fall_rates_from_closing_positions,
+          accounting.fall_rates_from_closing_positions,
+        ));
+      }
+
+      // Future epochs with closing values but no remaining positions closing in them
+      let expected_keys: HashSet<Pubkey> = expected
+        .keys()
+        .map(|(sub_dao, epoch)| sub_dao_epoch_info_key(sub_dao, *epoch))
+        .collect();
+      for (key, info) in snapshot.sub_dao_epoch_infos.iter() {
+        if info.epoch <= snapshot.epoch || expected_keys.contains(key) {
+          continue;
+        }
+        if sub_dao_epoch_info_key(&info.sub_dao, info.epoch) != *key {
+          continue;
+        }
+        divergences += u32::from(flag(
+          self.tolerance,
+          info.epoch,
+          &info.sub_dao,
+          "vehnt_in_closing_positions",
+          info.vehnt_in_closing_positions,
+          0,
+        ));
+        divergences += u32::from(flag(
+          self.tolerance,
+          info.epoch,
+          &info.sub_dao,
+          "fall_rates_from_closing_positions",
+          info.fall_rates_from_closing_positions,
+          0,
+        ));
+      }
+
+      println!("  {} divergences", divergences);
+      total_divergences += divergences;
+    }
+
+    println!("Total divergences: {}", total_divergences);
+    Ok(())
+  }
+}
@@ -0,0 +1,215 @@
+use std::{fs::File, io::BufReader, path::PathBuf};
+
+use anchor_lang::AccountDeserialize;
+use helium_sub_daos::{
+  simulator::{
+    simulate, EpochInput, HntPrice, SimulationConfig, SimulationState, SimulationSubDao,
+    SubDaoEpochInput,
+  },
+  DaoV0, SubDaoV0,
+};
+use serde::Deserialize;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+
+use super::*;
+
+const HNT_DECIMALS: u8 = 8;
+// Prices are given in dollars and passed on with the exponent of the HNT Pyth feed
+const PRICE_EXPONENT: i32 = -8;
+
+#[derive(Debug, Clone, clap::Args)]
+/// Projects per-epoch emissions, delegator pool flows, earnings cap overflows and supplement
+/// mints with the helium-sub-daos reward math, writing one CSV row per epoch.
+///
+/// The emission schedules and DAO parameters are read from chain unless overridden in the
+/// scenario file.
+pub struct Simulate {
+  /// Scenario JSON with the start state and the per-epoch DC burn and price series
+  pub scenario: PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Scenario {
+  #[serde(default)]
+  config: ConfigOverrides,
+  start: StartState,
+  epochs: Vec<ScenarioEpoch>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigOverrides {
+  net_emissions_cap: Option<u64>,
+  delegator_rewards_percent: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StartState {
+  epoch: u64,
+  hnt_supply: u64,
+  smoothed_hnt_burned: u64,
+  sub_daos: Vec<StartSubDao>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StartSubDao {
+  name: String,
+  #[serde(default)]
+  is_mobile: bool,
+  vehnt_at_epoch_start: u64,
+  previous_percentage: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScenarioEpoch {
+  /// One entry per start sub dao
+  dc_burned: Vec<u64>,
+  vehnt_at_epoch_start: Option<Vec<u64>>,
+  /// HNT price in dollars, leaving the backstop dormant when missing
+  hnt_price: Option<f64>,
+  #[serde(default)]
+  hnt_price_conf: f64,
+  hnt_burned: Option<u64>,
+  #[serde(default)]
+  not_emitted: u64,
+}
+
+fn to_price(dollars: f64) -> i64 {
+  (dollars * 10_f64.powi(-PRICE_EXPONENT)) as i64
+}
+
+impl Simulate {
+  pub async fn run(self, rpc_client: RpcClient, _solana_url: String) -> MyResult {
+    let scenario: Scenario = serde_json::from_reader(BufReader::new(File::open(&self.scenario)?))?;
+
+    let sub_dao_raw = rpc_client
+      .get_account(&Pubkey::from_str(IOT_SUBDAO)?)
+      .await?;
+    let sub_dao = SubDaoV0::try_deserialize(&mut sub_dao_raw.data.as_slice())?;
+    let dao_raw = rpc_client.get_account(&sub_dao.dao).await?;
+    let dao = DaoV0::try_deserialize(&mut dao_raw.data.as_slice())?;
+    let mut config = SimulationConfig::from_dao(&dao, HNT_DECIMALS);
+    if let Some(net_emissions_cap) = scenario.config.net_emissions_cap {
+      config.net_emissions_cap = net_emissions_cap;
+    }
+    if let Some(delegator_rewards_percent) = scenario.config.delegator_rewards_percent {
+      config.delegator_rewards_percent = delegator_rewards_percent;
+    }
+
+    let num_sub_daos = scenario.start.sub_daos.len();
+    let state = SimulationState {
+      epoch: scenario.start.epoch,
+      hnt_supply: scenario.start.hnt_supply,
+      smoothed_hnt_burned: scenario.start.smoothed_hnt_burned,
+      sub_daos: scenario
+        .start
+        .sub_daos
+        .iter()
+        .map(|sub_dao| SimulationSubDao {
+          is_mobile: sub_dao.is_mobile,
+          vehnt_at_epoch_start: sub_dao.vehnt_at_epoch_start,
+          previous_percentage: sub_dao.previous_percentage,
+        })
+        .collect(),
+    };
+    let mut inputs = Vec::with_capacity(scenario.epochs.len());
+    for (i, epoch) in scenario.epochs.iter().enumerate() {
+      if epoch.dc_burned.len() != num_sub_daos
+        || epoch
+          .vehnt_at_epoch_start
+          .as_ref()
+          .is_some_and(|vehnt| vehnt.len() != num_sub_daos)
+      {
+        return Err(crate::error::Error::InvalidScenario(format!(
+          "epoch {} must have one value per sub dao",
+          i
+        )));
+      }
+      inputs.push(EpochInput {
+        sub_daos: epoch
+          .dc_burned
+          .iter()
+          .enumerate()
+          .map(|(j, dc_burned)| SubDaoEpochInput {
+            dc_burned: *dc_burned,
+            vehnt_at_epoch_start: epoch.vehnt_at_epoch_start.as_ref().map(|vehnt| vehnt[j]),
+          })
+          .collect(),
+        hnt_price: epoch.hnt_price.map(|price| HntPrice {
+          ema_price: to_price(price),
+          ema_conf: to_price(epoch.hnt_price_conf) as u64,
+          exponent: PRICE_EXPONENT,
+        }),
+        hnt_burned: epoch.hnt_burned,
+        not_emitted: epoch.not_emitted,
+      });
+    }
+
+    let epochs = simulate(&config, state, &inputs)?;
+
+    let mut header = vec![
+      "epoch",
+      "emission",
+      "hnt_burned",
+      "smoothed_hnt_burned",
+      "existing_re_emit",
+      "top_up",
+      "total_rewards",
+      "deployer_cap_hnt",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect::<Vec<_>>();
+    for sub_dao in scenario.start.sub_daos.iter() {
+      for column in ["rewards", "delegation_pool", "escrow", "staker_overflow"] {
+        header.push(format!("{}_{}", sub_dao.name, column));
+      }
+    }
+    header.extend(
+      [
+        "delegation_rewards_issued",
+        "supplement_minted",
+        "council_minted",
+        "current_hnt_supply",
+      ]
+      .map(String::from),
+    );
+    println!("{}", header.join(","));
+
+    for epoch in epochs {
+      let mut row = vec![
+        epoch.epoch,
+        epoch.emission,
+        epoch.total_hnt_burned,
+        epoch.smoothed_hnt_burned,
+        epoch.backstop.existing_re_emit,
+        epoch.backstop.top_up,
+        epoch.backstop.total_rewards,
+        epoch.backstop.deployer_cap_hnt,
+      ];
+      for sub_dao in epoch.sub_daos.iter() {
+        row.extend([
+          sub_dao.rewards.rewards_amount,
+          sub_dao.rewards.delegation_pool_amount,
+          sub_dao.rewards.escrow_amount,
+          sub_dao.rewards.staker_overflow,
+        ]);
+      }
+      row.extend([
+        epoch.delegation_rewards_issued,
+        epoch.supplement_minted,
+        epoch.sub_daos.iter().map(|sub_dao| sub_dao.council).sum(),
+        epoch.current_hnt_supply,
+      ]);
+      println!(
+        "{}",
+        row
+          .iter()
+          .map(|value| value.to_string())
+          .collect::<Vec<_>>()
+          .join(",")
+      );
+    }
+    Ok(())
+  }
+}
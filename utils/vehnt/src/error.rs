@@ -16,6 +16,10 @@ pub enum Error {
   InvalidSubDao(solana_sdk::pubkey::Pubkey),
   #[error("reqwest error: {0}")]
   Reqwest(#[from] reqwest::Error),
+  #[error("serde json error: {0}")]
+  SerdeJson(#[from] serde_json::Error),
+  #[error("invalid scenario: {0}")]
+  InvalidScenario(String),
   #[error("parse int error: {0}")]
   ParseInt(#[from] std::num::ParseIntError),
 }